@@ -0,0 +1,203 @@
+use crate::error::{Error, ErrorKind};
+use futures::Future;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// A token used to cooperatively cancel operations.
+///
+/// Insert a clone of the token into the [`Context`](crate::Context) of an operation with
+/// [`Context::set_cancellation_token`](crate::Context::set_cancellation_token). Once
+/// [`cancel`](CancellationToken::cancel) is called, the pipeline stops at the next opportunity:
+/// before an attempt, while waiting for a response or while sleeping before a retry. The
+/// operation then fails with [`ErrorKind::Cancelled`].
+///
+/// Cloned tokens share the same state, so cancelling any clone cancels them all.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    waiters: Mutex<Waiters>,
+}
+
+/// The wakers of the pending [`Cancelled`] futures, each one removing its own on drop.
+#[derive(Debug, Default)]
+struct Waiters {
+    next_key: u64,
+    wakers: HashMap<u64, Waker>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the operations using this token.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        let wakers = std::mem::take(&mut self.inner.waiters.lock().unwrap().wakers);
+        for waker in wakers.into_values() {
+            waker.wake();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Returns an error of kind [`ErrorKind::Cancelled`] if the token has been cancelled.
+    pub fn check(&self) -> crate::Result<()> {
+        if self.is_cancelled() {
+            Err(cancelled_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// A future completing once the token is cancelled.
+    pub fn cancelled(&self) -> Cancelled {
+        Cancelled {
+            token: self.clone(),
+            key: None,
+        }
+    }
+}
+
+/// The future returned by [`CancellationToken::cancelled`].
+#[derive(Debug)]
+pub struct Cancelled {
+    token: CancellationToken,
+    key: Option<u64>,
+}
+
+impl Future for Cancelled {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if this.token.is_cancelled() {
+            return Poll::Ready(());
+        }
+        let mut waiters = this.token.inner.waiters.lock().unwrap();
+        // check again while holding the lock, so a concurrent `cancel` cannot be missed
+        if this.token.is_cancelled() {
+            return Poll::Ready(());
+        }
+        let key = *this.key.get_or_insert_with(|| {
+            waiters.next_key += 1;
+            waiters.next_key
+        });
+        match waiters.wakers.get_mut(&key) {
+            Some(waker) if waker.will_wake(cx.waker()) => {}
+            Some(waker) => *waker = cx.waker().clone(),
+            None => {
+                waiters.wakers.insert(key, cx.waker().clone());
+            }
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for Cancelled {
+    fn drop(&mut self) {
+        if let Some(key) = self.key {
+            self.token.inner.waiters.lock().unwrap().wakers.remove(&key);
+        }
+    }
+}
+
+pub(crate) fn cancelled_error() -> Error {
+    Error::message(ErrorKind::Cancelled, "the operation was cancelled")
+}
+
+/// Awaits `future` unless `token` is cancelled first, in which case an error of kind
+/// [`ErrorKind::Cancelled`] is returned.
+pub(crate) async fn cancellable<F, T>(
+    token: Option<&CancellationToken>,
+    future: F,
+) -> crate::Result<T>
+where
+    F: Future<Output = crate::Result<T>>,
+{
+    let token = match token {
+        Some(token) => token,
+        None => return future.await,
+    };
+    token.check()?;
+
+    futures::pin_mut!(future);
+    match futures::future::select(future, token.cancelled()).await {
+        futures::future::Either::Left((output, _)) => output,
+        futures::future::Either::Right(_) => Err(cancelled_error()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sleep::sleep;
+    use std::time::Duration;
+
+    #[test]
+    fn cancel_wakes_pending_futures() {
+        let token = CancellationToken::new();
+        assert!(token.check().is_ok());
+
+        let canceller = token.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            canceller.cancel();
+        });
+
+        let result = futures::executor::block_on(cancellable(Some(&token), async {
+            sleep(Duration::from_secs(10)).await;
+            Ok(())
+        }));
+        assert_eq!(result.unwrap_err().kind(), &ErrorKind::Cancelled);
+        assert!(token.is_cancelled());
+        assert_eq!(token.check().unwrap_err().kind(), &ErrorKind::Cancelled);
+    }
+
+    #[test]
+    fn dropped_futures_unregister_their_waker() {
+        let token = CancellationToken::new();
+        let waker = futures::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let registered = || token.inner.waiters.lock().unwrap().wakers.len();
+
+        let mut pending = Box::pin(token.cancelled());
+        assert!(pending.as_mut().poll(&mut cx).is_pending());
+        for _ in 0..1000 {
+            let mut cancelled = Box::pin(token.cancelled());
+            assert!(cancelled.as_mut().poll(&mut cx).is_pending());
+            assert!(cancelled.as_mut().poll(&mut cx).is_pending());
+            assert_eq!(registered(), 2);
+        }
+        assert_eq!(registered(), 1);
+
+        for _ in 0..1000 {
+            let result = futures::executor::block_on(cancellable(Some(&token), async { Ok(()) }));
+            assert!(result.is_ok());
+        }
+        assert_eq!(registered(), 1);
+
+        token.cancel();
+        assert_eq!(registered(), 0);
+        assert!(pending.as_mut().poll(&mut cx).is_ready());
+    }
+
+    #[test]
+    fn completes_without_cancellation() {
+        let token = CancellationToken::new();
+        let result = futures::executor::block_on(cancellable(Some(&token), async { Ok(42) }));
+        assert_eq!(result.unwrap(), 42);
+        let result = futures::executor::block_on(cancellable(None, async { Ok(42) }));
+        assert_eq!(result.unwrap(), 42);
+    }
+}
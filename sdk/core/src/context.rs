@@ -1,6 +1,12 @@
+use crate::CancellationToken;
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// The point in time after which the operations sent with a [`Context`] are abandoned.
+#[derive(Clone, Copy, Debug)]
+struct Deadline(Instant);
 
 /// Pipeline execution context.
 #[derive(Clone, Debug)]
@@ -69,6 +75,34 @@ impl Context {
             .and_then(|item| item.downcast_ref())
     }
 
+    /// Sets the point in time after which the operations sent with this context fail with
+    /// [`ErrorKind::Timeout`](crate::error::ErrorKind::Timeout).
+    ///
+    /// The deadline covers the whole operation, retries and the delays between them included.
+    pub fn set_deadline(&mut self, deadline: Instant) -> &mut Self {
+        self.insert(Deadline(deadline))
+    }
+
+    /// Sets the deadline of the operations sent with this context to `timeout` from now.
+    pub fn set_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.set_deadline(Instant::now() + timeout)
+    }
+
+    /// Returns the deadline of the operations sent with this context, if any.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.get::<Deadline>().map(|deadline| deadline.0)
+    }
+
+    /// Sets the token used to cancel the operations sent with this context.
+    pub fn set_cancellation_token(&mut self, token: CancellationToken) -> &mut Self {
+        self.insert(token)
+    }
+
+    /// Returns the token used to cancel the operations sent with this context, if any.
+    pub fn cancellation_token(&self) -> Option<&CancellationToken> {
+        self.get()
+    }
+
     /// Returns the number of entities in the type map.
     pub fn len(&self) -> usize {
         self.type_map.len()
@@ -130,6 +164,20 @@ mod tests {
         assert_eq!(Some(&"static str"), context.get());
     }
 
+    #[test]
+    fn deadline() {
+        let mut context = Context::new();
+        assert_eq!(None, context.deadline());
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        context.set_deadline(deadline);
+        assert_eq!(Some(deadline), context.deadline());
+
+        context.set_timeout(Duration::from_secs(60));
+        assert!(context.deadline().unwrap() > deadline);
+        assert_eq!(1, context.len());
+    }
+
     fn require_send_sync<T: Send + Sync>(_: &T) {}
 
     #[test]
@@ -42,6 +42,11 @@ impl HttpError {
     pub fn error_code(&self) -> Option<&str> {
         self.error_code.as_deref()
     }
+
+    /// Get a reference to the http error's headers.
+    pub fn headers(&self) -> &HashMap<String, String> {
+        &self.headers
+    }
 }
 
 impl std::fmt::Display for HttpError {
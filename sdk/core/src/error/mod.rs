@@ -23,6 +23,10 @@ pub enum ErrorKind {
     DataConversion,
     /// An error getting an API credential token
     Credential,
+    /// The operation, or one of its attempts, did not complete in time
+    Timeout,
+    /// The operation was cancelled through its cancellation token
+    Cancelled,
     #[cfg(feature = "mock_transport_framework")]
     /// An error having to do with the mock framework
     MockFramework,
@@ -61,6 +65,8 @@ impl Display for ErrorKind {
             ErrorKind::Io => write!(f, "Io"),
             ErrorKind::DataConversion => write!(f, "DataConversion"),
             ErrorKind::Credential => write!(f, "Credential"),
+            ErrorKind::Timeout => write!(f, "Timeout"),
+            ErrorKind::Cancelled => write!(f, "Cancelled"),
             #[cfg(feature = "mock_transport_framework")]
             ErrorKind::MockFramework => write!(f, "MockFramework"),
             ErrorKind::Other => write!(f, "Other"),
@@ -231,6 +231,7 @@ pub const ACCOUNT_KIND: HeaderName = HeaderName::from_static("x-ms-account-kind"
 pub const ACTIVITY_ID: HeaderName = HeaderName::from_static("x-ms-activity-id");
 pub const APP: HeaderName = HeaderName::from_static("x-ms-app");
 pub const AUTHORIZATION: HeaderName = HeaderName::from_static("authorization");
+pub const AZURE_ASYNCOPERATION: HeaderName = HeaderName::from_static("azure-asyncoperation");
 pub const APPEND_POSITION: HeaderName = HeaderName::from_static("x-ms-blob-condition-appendpos");
 pub const BLOB_ACCESS_TIER: HeaderName = HeaderName::from_static("x-ms-access-tier");
 pub const BLOB_CONTENT_LENGTH: HeaderName = HeaderName::from_static("x-ms-blob-content-length");
@@ -292,6 +293,7 @@ pub const MAX_ITEM_COUNT: HeaderName = HeaderName::from_static("x-ms-max-item-co
 pub const META_PREFIX: HeaderName = HeaderName::from_static("x-ms-meta-");
 pub const MS_DATE: HeaderName = HeaderName::from_static("x-ms-date");
 pub const MS_RANGE: HeaderName = HeaderName::from_static("x-ms-range");
+pub const MS_RETRY_AFTER_MS: HeaderName = HeaderName::from_static("x-ms-retry-after-ms");
 pub const NAMESPACE_ENABLED: HeaderName = HeaderName::from_static("x-ms-namespace-enabled");
 pub const PAGE_WRITE: HeaderName = HeaderName::from_static("x-ms-page-write");
 pub const PROPERTIES: HeaderName = HeaderName::from_static("x-ms-properties");
@@ -305,6 +307,7 @@ pub const REQUEST_SERVER_ENCRYPTED: HeaderName =
     HeaderName::from_static("x-ms-request-server-encrypted");
 pub const REQUIRES_SYNC: HeaderName = HeaderName::from_static("x-ms-requires-sync");
 pub const RETRY_AFTER: HeaderName = HeaderName::from_static("retry-after");
+pub const RETRY_AFTER_MS: HeaderName = HeaderName::from_static("retry-after-ms");
 pub const SERVER: HeaderName = HeaderName::from_static("server");
 pub const SERVER_ENCRYPTED: HeaderName = HeaderName::from_static("x-ms-server-encrypted");
 pub const SESSION_TOKEN: HeaderName = HeaderName::from_static("x-ms-session-token");
@@ -316,6 +319,8 @@ pub const SOURCE_IF_NONE_MATCH: HeaderName = HeaderName::from_static("x-ms-sourc
 pub const SOURCE_IF_UNMODIFIED_SINCE: HeaderName =
     HeaderName::from_static("x-ms-source-if-unmodified-since");
 pub const SOURCE_LEASE_ID: HeaderName = HeaderName::from_static("x-ms-source-lease-id");
+pub const TRACEPARENT: HeaderName = HeaderName::from_static("traceparent");
+pub const TRACESTATE: HeaderName = HeaderName::from_static("tracestate");
 pub const USER: HeaderName = HeaderName::from_static("x-ms-user");
 pub const USER_AGENT: HeaderName = HeaderName::from_static("user-agent");
 pub const VERSION: HeaderName = HeaderName::from_static("x-ms-version");
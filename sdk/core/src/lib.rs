@@ -12,6 +12,7 @@
 mod macros;
 
 mod bytes_stream;
+mod cancellation;
 mod constants;
 mod context;
 pub mod error;
@@ -28,6 +29,7 @@ mod seekable_stream;
 
 pub mod auth;
 pub mod headers;
+pub mod lro;
 #[cfg(feature = "mock_transport_framework")]
 pub mod mock;
 pub mod parsing;
@@ -38,6 +40,7 @@ pub mod util;
 use uuid::Uuid;
 
 pub use bytes_stream::*;
+pub use cancellation::{CancellationToken, Cancelled};
 pub use constants::*;
 pub use context::Context;
 pub use error::Result;
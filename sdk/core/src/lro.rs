@@ -0,0 +1,703 @@
+//! Long-running operation support.
+//!
+//! Azure Resource Manager operations that cannot complete within a single request answer with
+//! `201 Created` or `202 Accepted` and an `Azure-AsyncOperation` and/or `Location` header pointing
+//! to a status monitor. The [`Poller`] drives such an operation to completion: it polls the
+//! monitor, waiting as instructed by the `Retry-After` header, yields every intermediate status
+//! and finally resolves to the result of the operation.
+//!
+//! See <https://github.com/Azure/azure-resource-manager-rpc/blob/master/v1.0/async-api-reference.md>.
+
+use crate::error::{Error, ErrorKind};
+use crate::headers::{HeaderName, Headers, AZURE_ASYNCOPERATION, LOCATION};
+use crate::sleep::sleep;
+use crate::{
+    collect_pinned_stream, get_retry_after, BytesStream, Method, Request, Response, StatusCode, Url,
+};
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::stream::{unfold, Stream, StreamExt};
+use pin_project::pin_project;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The delay between two polls when the service does not send a `Retry-After` header.
+pub const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+/// Where the final result of a long-running operation is read from once it has succeeded.
+///
+/// This maps to the `final-state-via` value of the `x-ms-long-running-operation-options`
+/// OpenAPI extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FinalStateVia {
+    /// The final result is the body of the `Azure-AsyncOperation` status monitor, the resource
+    /// itself when the operation is a `PUT` or a `PATCH`, or the URL of the `Location` header
+    /// when a `POST` returns one.
+    ///
+    /// This is the default.
+    #[default]
+    AzureAsyncOperation,
+    /// The final result is fetched from the URL of the `Location` header.
+    Location,
+    /// The final result is fetched from the URL of the initial request.
+    OriginalUri,
+}
+
+/// The status of a long-running operation, as reported by the service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LroStatus {
+    InProgress,
+    Succeeded,
+    Failed,
+    Canceled,
+    /// A service specific, non terminal status such as `Creating` or `Updating`.
+    Other(String),
+}
+
+impl LroStatus {
+    /// Returns `true` if the operation will not make any further progress.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            LroStatus::Succeeded | LroStatus::Failed | LroStatus::Canceled
+        )
+    }
+}
+
+impl From<&str> for LroStatus {
+    fn from(s: &str) -> Self {
+        match s.to_ascii_lowercase().as_str() {
+            "inprogress" => LroStatus::InProgress,
+            "succeeded" => LroStatus::Succeeded,
+            "failed" => LroStatus::Failed,
+            "canceled" | "cancelled" => LroStatus::Canceled,
+            _ => LroStatus::Other(s.to_owned()),
+        }
+    }
+}
+
+/// An item yielded by a [`Poller`].
+#[derive(Debug)]
+pub enum PollerState<T> {
+    /// The operation is still running.
+    InProgress(LroStatus),
+    /// The operation has succeeded and this is its final result.
+    Done(T),
+}
+
+/// A stream polling a long-running operation until it reaches a terminal state.
+///
+/// Every poll that finds the operation still running yields a [`PollerState::InProgress`] item.
+/// The last item is either a [`PollerState::Done`] carrying the final result or an error if the
+/// operation failed or was canceled. Use [`Poller::wait`] to skip the intermediate states.
+#[pin_project]
+pub struct Poller<T> {
+    #[pin]
+    stream: std::pin::Pin<Box<dyn Stream<Item = crate::Result<PollerState<T>>> + Send>>,
+}
+
+type GetFn = Arc<dyn Fn(Url) -> BoxFuture<'static, crate::Result<Response>> + Send + Sync>;
+type ConvertFn<T> = Box<dyn FnOnce(Response) -> BoxFuture<'static, crate::Result<T>> + Send>;
+
+impl<T> Poller<T>
+where
+    T: Send + 'static,
+{
+    /// Creates a new poller.
+    ///
+    /// * `initial_request` sends the request starting the operation and returns it along with
+    ///   the service response.
+    /// * `get` sends an authenticated `GET` request to the specified URL. It's used to poll the
+    ///   status monitor and to fetch the final result.
+    /// * `convert` turns the final response into the operation result. When the result is not
+    ///   fetched from another URL after an `Azure-AsyncOperation` status monitor succeeds, the
+    ///   body of the response is the `properties` of the status monitor, which is empty if it has
+    ///   none.
+    pub fn new<I, G, GF, C, CF>(
+        final_state_via: FinalStateVia,
+        initial_request: I,
+        get: G,
+        convert: C,
+    ) -> Self
+    where
+        I: Future<Output = crate::Result<(Request, Response)>> + Send + 'static,
+        G: Fn(Url) -> GF + Send + Sync + 'static,
+        GF: Future<Output = crate::Result<Response>> + Send + 'static,
+        C: FnOnce(Response) -> CF + Send + 'static,
+        CF: Future<Output = crate::Result<T>> + Send + 'static,
+    {
+        let get: GetFn = Arc::new(move |url| Box::pin(get(url)));
+        let convert: ConvertFn<T> = Box::new(move |response| Box::pin(convert(response)));
+        let state = State::Init {
+            initial_request: Box::pin(initial_request),
+            get,
+            convert,
+            final_state_via,
+        };
+
+        let stream = unfold(state, |state| async move {
+            match state.next().await {
+                Ok(Some((item, state))) => Some((Ok(item), state)),
+                Ok(None) => None,
+                Err(error) => Some((Err(error), State::Done)),
+            }
+        });
+
+        Self {
+            stream: Box::pin(stream),
+        }
+    }
+
+    /// Polls the operation until it completes, returning its final result.
+    pub async fn wait(mut self) -> crate::Result<T> {
+        while let Some(state) = self.next().await {
+            if let PollerState::Done(result) = state? {
+                return Ok(result);
+            }
+        }
+        Err(Error::message(
+            ErrorKind::Other,
+            "long running operation poller ended without a result",
+        ))
+    }
+}
+
+impl<T> Stream for Poller<T> {
+    type Item = crate::Result<PollerState<T>>;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        let this = self.project();
+        this.stream.poll_next(cx)
+    }
+}
+
+impl<T> std::fmt::Debug for Poller<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Poller").finish_non_exhaustive()
+    }
+}
+
+enum State<T> {
+    Init {
+        initial_request: BoxFuture<'static, crate::Result<(Request, Response)>>,
+        get: GetFn,
+        convert: ConvertFn<T>,
+        final_state_via: FinalStateVia,
+    },
+    Polling {
+        operation: Box<Operation>,
+        get: GetFn,
+        convert: ConvertFn<T>,
+        delay: Duration,
+    },
+    Done,
+}
+
+/// What the poller knows about the operation after the initial request.
+struct Operation {
+    method: Method,
+    original_url: Url,
+    async_operation_url: Option<Url>,
+    location_url: Option<Url>,
+    final_state_via: FinalStateVia,
+}
+
+impl Operation {
+    fn polling_url(&self) -> &Url {
+        self.async_operation_url
+            .as_ref()
+            .or(self.location_url.as_ref())
+            .unwrap_or(&self.original_url)
+    }
+
+    /// The URL the final result must be fetched from, if it's not the last polled response.
+    fn final_url(&self) -> Option<&Url> {
+        // only the Azure-AsyncOperation monitor does not return the final result itself
+        self.async_operation_url.as_ref()?;
+        match (self.final_state_via, &self.method) {
+            // a POST fetches its result from the Location URL when there is one
+            (FinalStateVia::Location, _) | (FinalStateVia::AzureAsyncOperation, Method::Post)
+                if self.location_url.is_some() =>
+            {
+                self.location_url.as_ref()
+            }
+            (FinalStateVia::OriginalUri, _) | (_, Method::Put) | (_, Method::Patch) => {
+                Some(&self.original_url)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<T> State<T>
+where
+    T: Send + 'static,
+{
+    async fn next(self) -> crate::Result<Option<(PollerState<T>, Self)>> {
+        match self {
+            State::Init {
+                initial_request,
+                get,
+                convert,
+                final_state_via,
+            } => {
+                let (request, response) = initial_request.await?;
+                let (status, headers, body) = read_response(response).await?;
+
+                let operation = Box::new(Operation {
+                    method: *request.method(),
+                    async_operation_url: header_url(
+                        &headers,
+                        &AZURE_ASYNCOPERATION,
+                        request.url(),
+                    )?,
+                    location_url: header_url(&headers, &LOCATION, request.url())?,
+                    original_url: request.url().clone(),
+                    final_state_via,
+                });
+                let provisioning_state = provisioning_state(&body);
+                if let Some(status) = &provisioning_state {
+                    check_not_failed(status)?;
+                }
+
+                let is_resource_update = matches!(operation.method, Method::Put | Method::Patch);
+                let is_running = provisioning_state
+                    .as_ref()
+                    .is_some_and(|status| !status.is_terminal());
+                let has_monitor =
+                    operation.async_operation_url.is_some() || operation.location_url.is_some();
+                let needs_polling = operation.async_operation_url.is_some()
+                    || is_running
+                    || (status == StatusCode::Accepted && (has_monitor || is_resource_update));
+
+                if !needs_polling {
+                    let result = convert(new_response(status, headers, body)).await?;
+                    return Ok(Some((PollerState::Done(result), State::Done)));
+                }
+
+                let delay = get_retry_after(&headers).unwrap_or(DEFAULT_RETRY_AFTER);
+                let status = provisioning_state.unwrap_or(LroStatus::InProgress);
+                Ok(Some((
+                    PollerState::InProgress(status),
+                    State::Polling {
+                        operation,
+                        get,
+                        convert,
+                        delay,
+                    },
+                )))
+            }
+            State::Polling {
+                operation,
+                get,
+                convert,
+                delay,
+            } => {
+                sleep(delay).await;
+
+                let response = get(operation.polling_url().clone()).await?;
+                let (status_code, headers, body) = read_response(response).await?;
+
+                let status = if operation.async_operation_url.is_some() {
+                    operation_status(&body)?
+                } else if operation.location_url.is_some() {
+                    if status_code == StatusCode::Accepted {
+                        LroStatus::InProgress
+                    } else {
+                        LroStatus::Succeeded
+                    }
+                } else {
+                    provisioning_state(&body).unwrap_or(LroStatus::Succeeded)
+                };
+                check_not_failed(&status)?;
+
+                if status != LroStatus::Succeeded {
+                    let delay = get_retry_after(&headers).unwrap_or(DEFAULT_RETRY_AFTER);
+                    return Ok(Some((
+                        PollerState::InProgress(status),
+                        State::Polling {
+                            operation,
+                            get,
+                            convert,
+                            delay,
+                        },
+                    )));
+                }
+
+                let final_response = match operation.final_url() {
+                    Some(url) => get(url.clone()).await?,
+                    // the status monitor is not the result, which it may hold in its properties
+                    None if operation.async_operation_url.is_some() => {
+                        new_response(status_code, headers, status_properties(&body)?)
+                    }
+                    None => new_response(status_code, headers, body),
+                };
+                let result = convert(final_response).await?;
+                Ok(Some((PollerState::Done(result), State::Done)))
+            }
+            State::Done => Ok(None),
+        }
+    }
+}
+
+async fn read_response(response: Response) -> crate::Result<(StatusCode, Headers, Bytes)> {
+    let (status, headers, body) = response.deconstruct();
+    let body = collect_pinned_stream(body).await?;
+    Ok((status, headers, body))
+}
+
+fn new_response(status: StatusCode, headers: Headers, body: Bytes) -> Response {
+    Response::new(status, headers, Box::pin(BytesStream::new(body)))
+}
+
+fn header_url(headers: &Headers, name: &HeaderName, base: &Url) -> crate::Result<Option<Url>> {
+    match headers.get_optional_str(name) {
+        Some(value) => Ok(Some(base.join(value)?)),
+        None => Ok(None),
+    }
+}
+
+/// The `properties.provisioningState` of a resource, if present.
+fn provisioning_state(body: &[u8]) -> Option<LroStatus> {
+    let value: serde_json::Value = serde_json::from_slice(body).ok()?;
+    value
+        .pointer("/properties/provisioningState")
+        .and_then(serde_json::Value::as_str)
+        .map(LroStatus::from)
+}
+
+/// The `status` of an `Azure-AsyncOperation` status monitor.
+fn operation_status(body: &[u8]) -> crate::Result<LroStatus> {
+    let value: serde_json::Value = serde_json::from_slice(body)?;
+    value
+        .get("status")
+        .and_then(serde_json::Value::as_str)
+        .map(LroStatus::from)
+        .ok_or_else(|| {
+            Error::message(
+                ErrorKind::DataConversion,
+                "the operation status monitor response has no status",
+            )
+        })
+}
+
+/// The `properties` of an `Azure-AsyncOperation` status monitor, or an empty body if it has none.
+fn status_properties(body: &[u8]) -> crate::Result<Bytes> {
+    let value: serde_json::Value = serde_json::from_slice(body)?;
+    match value.get("properties") {
+        Some(properties) => Ok(serde_json::to_vec(properties)?.into()),
+        None => Ok(Bytes::new()),
+    }
+}
+
+fn check_not_failed(status: &LroStatus) -> crate::Result<()> {
+    match status {
+        LroStatus::Failed | LroStatus::Canceled => {
+            Err(Error::with_message(ErrorKind::Other, || {
+                format!("long running operation ended with status {:?}", status)
+            }))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn response(
+        status: StatusCode,
+        headers: &[(&'static str, &'static str)],
+        body: &'static str,
+    ) -> Response {
+        let mut h = Headers::new();
+        for (name, value) in headers {
+            h.insert(*name, *value);
+        }
+        new_response(status, h, Bytes::from_static(body.as_bytes()))
+    }
+
+    fn initial(
+        method: Method,
+        response: Response,
+    ) -> impl Future<Output = crate::Result<(Request, Response)>> {
+        let request = Request::new(
+            Url::parse("https://management.azure.com/resource?api-version=1").unwrap(),
+            method,
+        );
+        async move { Ok((request, response)) }
+    }
+
+    /// Builds a `get` function replying with the given responses in order and recording the urls.
+    fn get_fn(
+        responses: Vec<Response>,
+        urls: Arc<Mutex<Vec<String>>>,
+    ) -> impl Fn(Url) -> futures::future::Ready<crate::Result<Response>> {
+        let responses = Mutex::new(responses.into_iter());
+        move |url: Url| {
+            urls.lock().unwrap().push(url.to_string());
+            futures::future::ready(Ok(responses.lock().unwrap().next().unwrap()))
+        }
+    }
+
+    async fn body(response: Response) -> crate::Result<String> {
+        Ok(response.into_body_string().await)
+    }
+
+    #[test]
+    fn completes_immediately() {
+        let urls = Arc::new(Mutex::new(Vec::new()));
+        let poller = Poller::new(
+            FinalStateVia::AzureAsyncOperation,
+            initial(
+                Method::Put,
+                response(
+                    StatusCode::Ok,
+                    &[],
+                    r#"{"properties":{"provisioningState":"Succeeded"}}"#,
+                ),
+            ),
+            get_fn(Vec::new(), urls.clone()),
+            body,
+        );
+        let result = futures::executor::block_on(poller.wait()).unwrap();
+        assert_eq!(
+            result,
+            r#"{"properties":{"provisioningState":"Succeeded"}}"#
+        );
+        assert!(urls.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn polls_azure_async_operation_then_gets_original_uri() {
+        let urls = Arc::new(Mutex::new(Vec::new()));
+        let poller = Poller::new(
+            FinalStateVia::AzureAsyncOperation,
+            initial(
+                Method::Put,
+                response(
+                    StatusCode::Created,
+                    &[
+                        (
+                            "azure-asyncoperation",
+                            "https://management.azure.com/operations/1",
+                        ),
+                        ("retry-after", "0"),
+                    ],
+                    r#"{"properties":{"provisioningState":"Creating"}}"#,
+                ),
+            ),
+            get_fn(
+                vec![
+                    response(
+                        StatusCode::Ok,
+                        &[("retry-after", "0")],
+                        r#"{"status":"InProgress"}"#,
+                    ),
+                    response(StatusCode::Ok, &[], r#"{"status":"Succeeded"}"#),
+                    response(StatusCode::Ok, &[], r#"{"name":"resource"}"#),
+                ],
+                urls.clone(),
+            ),
+            body,
+        );
+
+        let states = futures::executor::block_on(poller.collect::<Vec<_>>());
+        let states: Vec<_> = states.into_iter().map(Result::unwrap).collect();
+        assert_eq!(states.len(), 3);
+        assert!(
+            matches!(&states[0], PollerState::InProgress(LroStatus::Other(s)) if s == "Creating")
+        );
+        assert!(matches!(
+            &states[1],
+            PollerState::InProgress(LroStatus::InProgress)
+        ));
+        assert!(matches!(&states[2], PollerState::Done(s) if s == r#"{"name":"resource"}"#));
+        assert_eq!(
+            *urls.lock().unwrap(),
+            vec![
+                "https://management.azure.com/operations/1",
+                "https://management.azure.com/operations/1",
+                "https://management.azure.com/resource?api-version=1",
+            ]
+        );
+    }
+
+    #[test]
+    fn post_gets_location_after_azure_async_operation() {
+        let urls = Arc::new(Mutex::new(Vec::new()));
+        let poller = Poller::new(
+            FinalStateVia::AzureAsyncOperation,
+            initial(
+                Method::Post,
+                response(
+                    StatusCode::Accepted,
+                    &[
+                        (
+                            "azure-asyncoperation",
+                            "https://management.azure.com/operations/1",
+                        ),
+                        ("location", "/results/1"),
+                        ("retry-after", "0"),
+                    ],
+                    "",
+                ),
+            ),
+            get_fn(
+                vec![
+                    response(StatusCode::Ok, &[], r#"{"status":"Succeeded"}"#),
+                    response(StatusCode::Ok, &[], "result"),
+                ],
+                urls.clone(),
+            ),
+            body,
+        );
+        let result = futures::executor::block_on(poller.wait()).unwrap();
+        assert_eq!(result, "result");
+        assert_eq!(
+            *urls.lock().unwrap(),
+            vec![
+                "https://management.azure.com/operations/1",
+                "https://management.azure.com/results/1",
+            ]
+        );
+    }
+
+    #[test]
+    fn post_reads_result_from_azure_async_operation_properties() {
+        let urls = Arc::new(Mutex::new(Vec::new()));
+        let poller = Poller::new(
+            FinalStateVia::AzureAsyncOperation,
+            initial(
+                Method::Post,
+                response(
+                    StatusCode::Accepted,
+                    &[
+                        (
+                            "azure-asyncoperation",
+                            "https://management.azure.com/operations/1",
+                        ),
+                        ("retry-after", "0"),
+                    ],
+                    "",
+                ),
+            ),
+            get_fn(
+                vec![response(
+                    StatusCode::Ok,
+                    &[],
+                    r#"{"status":"Succeeded","properties":{"name":"result"}}"#,
+                )],
+                urls.clone(),
+            ),
+            body,
+        );
+        let result = futures::executor::block_on(poller.wait()).unwrap();
+        assert_eq!(result, r#"{"name":"result"}"#);
+        assert_eq!(
+            *urls.lock().unwrap(),
+            vec!["https://management.azure.com/operations/1"]
+        );
+
+        let poller = Poller::new(
+            FinalStateVia::AzureAsyncOperation,
+            initial(
+                Method::Delete,
+                response(
+                    StatusCode::Accepted,
+                    &[
+                        (
+                            "azure-asyncoperation",
+                            "https://management.azure.com/operations/2",
+                        ),
+                        ("retry-after", "0"),
+                    ],
+                    "",
+                ),
+            ),
+            get_fn(
+                vec![response(StatusCode::Ok, &[], r#"{"status":"Succeeded"}"#)],
+                urls,
+            ),
+            body,
+        );
+        let result = futures::executor::block_on(poller.wait()).unwrap();
+        assert_eq!(result, "");
+    }
+
+    #[test]
+    fn polls_location() {
+        let urls = Arc::new(Mutex::new(Vec::new()));
+        let poller = Poller::new(
+            FinalStateVia::Location,
+            initial(
+                Method::Post,
+                response(
+                    StatusCode::Accepted,
+                    &[("location", "/results/1"), ("retry-after", "0")],
+                    "",
+                ),
+            ),
+            get_fn(
+                vec![
+                    response(StatusCode::Accepted, &[("retry-after", "0")], ""),
+                    response(StatusCode::Ok, &[], "result"),
+                ],
+                urls.clone(),
+            ),
+            body,
+        );
+        let result = futures::executor::block_on(poller.wait()).unwrap();
+        assert_eq!(result, "result");
+        assert_eq!(urls.lock().unwrap().len(), 2);
+        assert_eq!(
+            urls.lock().unwrap()[0],
+            "https://management.azure.com/results/1"
+        );
+    }
+
+    #[test]
+    fn failed_operation_is_an_error() {
+        let urls = Arc::new(Mutex::new(Vec::new()));
+        let poller = Poller::new(
+            FinalStateVia::AzureAsyncOperation,
+            initial(
+                Method::Delete,
+                response(
+                    StatusCode::Accepted,
+                    &[
+                        (
+                            "azure-asyncoperation",
+                            "https://management.azure.com/operations/1",
+                        ),
+                        ("retry-after", "0"),
+                    ],
+                    "",
+                ),
+            ),
+            get_fn(
+                vec![response(StatusCode::Ok, &[], r#"{"status":"Failed"}"#)],
+                urls,
+            ),
+            body,
+        );
+        let error = futures::executor::block_on(poller.wait()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::Other);
+    }
+
+    #[test]
+    fn parse_status() {
+        assert_eq!(LroStatus::from("succeeded"), LroStatus::Succeeded);
+        assert_eq!(LroStatus::from("Cancelled"), LroStatus::Canceled);
+        assert_eq!(
+            LroStatus::from("Updating"),
+            LroStatus::Other("Updating".to_owned())
+        );
+        assert!(!LroStatus::from("Updating").is_terminal());
+    }
+}
@@ -0,0 +1,241 @@
+use super::mock_request::SerializedRequest;
+use crate::error::{Error, ErrorKind};
+use crate::headers::{
+    HeaderName, AUTHORIZATION, DATE, MS_DATE, TRACEPARENT, TRACESTATE, USER_AGENT,
+};
+
+/// Decides whether a request received by the player matches a recorded one.
+///
+/// By default the URI, the method, the headers and the body must be identical, except for the
+/// headers bound to change every time such as `x-ms-date`, and the recorded requests are replayed
+/// strictly in order.
+#[derive(Debug, Clone)]
+pub struct RequestMatcher {
+    /// The headers which are not compared.
+    ignored_headers: Vec<HeaderName>,
+    /// Whether the query parameters may appear in a different order.
+    ignore_query_order: bool,
+    /// Whether the headers are compared at all.
+    compare_headers: bool,
+    /// Whether the bodies are compared.
+    compare_body: bool,
+    /// Whether any unused recorded request can be matched, instead of only the next one.
+    allow_out_of_order: bool,
+}
+
+impl Default for RequestMatcher {
+    fn default() -> Self {
+        Self {
+            ignored_headers: vec![
+                AUTHORIZATION,
+                DATE,
+                MS_DATE,
+                TRACEPARENT,
+                TRACESTATE,
+                USER_AGENT,
+            ],
+            ignore_query_order: false,
+            compare_headers: true,
+            compare_body: true,
+            allow_out_of_order: false,
+        }
+    }
+}
+
+impl RequestMatcher {
+    /// Ignores the value and the presence of more headers.
+    pub fn ignore_headers(mut self, names: impl IntoIterator<Item = HeaderName>) -> Self {
+        self.ignored_headers.extend(names);
+        self
+    }
+
+    setters! {
+        ignore_query_order: bool => ignore_query_order,
+        compare_headers: bool => compare_headers,
+        compare_body: bool => compare_body,
+        allow_out_of_order: bool => allow_out_of_order,
+    }
+
+    pub(crate) fn allows_out_of_order(&self) -> bool {
+        self.allow_out_of_order
+    }
+
+    /// Returns an error describing the first difference between the requests, if any.
+    pub(crate) fn check(
+        &self,
+        actual: &SerializedRequest,
+        expected: &SerializedRequest,
+    ) -> crate::Result<()> {
+        if !self.uri_matches(&actual.uri, &expected.uri) {
+            return Err(Error::with_message(ErrorKind::MockFramework, || {
+                format!(
+                    "mismatched request uri. Actual '{0}', Expected: '{1}'",
+                    actual.uri, expected.uri
+                )
+            }));
+        }
+
+        if actual.method != expected.method {
+            return Err(Error::with_message(ErrorKind::MockFramework, || {
+                format!(
+                    "mismatched HTTP request method. Actual: {0}, Expected: {1}",
+                    actual.method, expected.method,
+                )
+            }));
+        }
+
+        if self.compare_headers {
+            self.check_headers(actual, expected)?;
+        }
+
+        if self.compare_body && actual.body != expected.body {
+            return Err(Error::with_message(ErrorKind::MockFramework, || {
+                format!(
+                    "mismatched request body. Actual: {0:?}, Expected: {1:?}",
+                    base64::decode(&actual.body).unwrap_or_default(),
+                    base64::decode(&expected.body).unwrap_or_default(),
+                )
+            }));
+        }
+
+        Ok(())
+    }
+
+    fn uri_matches(&self, actual: &str, expected: &str) -> bool {
+        if !self.ignore_query_order {
+            return actual == expected;
+        }
+        split_uri(actual) == split_uri(expected)
+    }
+
+    fn check_headers(
+        &self,
+        actual: &SerializedRequest,
+        expected: &SerializedRequest,
+    ) -> crate::Result<()> {
+        let is_compared = |name: &&String| {
+            !self
+                .ignored_headers
+                .iter()
+                .any(|ignored| ignored.as_str().eq_ignore_ascii_case(name))
+        };
+        let actual_headers: Vec<_> = actual
+            .headers
+            .iter()
+            .filter(|(name, _)| is_compared(name))
+            .collect();
+        let expected_headers: Vec<_> = expected
+            .headers
+            .iter()
+            .filter(|(name, _)| is_compared(name))
+            .collect();
+
+        // In order to accept a request, we make sure that:
+        // 1. There are no extra headers (in both the received and read request).
+        // 2. Each header has the same value.
+        if actual_headers.len() != expected_headers.len() {
+            return Err(Error::with_message(ErrorKind::MockFramework, || {
+                format!(
+                    "different number of headers in request. Actual: {0}, Expected: {1}",
+                    actual_headers.len(),
+                    expected_headers.len(),
+                )
+            }));
+        }
+
+        for (actual_name, actual_value) in actual_headers {
+            let expected_value = expected
+                .headers
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(actual_name))
+                .map(|(_, value)| value)
+                .ok_or_else(|| {
+                    Error::with_message(ErrorKind::MockFramework, || {
+                        format!(
+                            "received request have header '{0}' but it was not present in the read request",
+                            actual_name
+                        )
+                    })
+                })?;
+
+            if actual_value != expected_value {
+                return Err(Error::with_message(ErrorKind::MockFramework, || {
+                    format!(
+                        "request header '{0}' value is different. Actual: {1}, Expected: {2}",
+                        actual_name, actual_value, expected_value,
+                    )
+                }));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Splits an URI into its path and its sorted query pairs.
+fn split_uri(uri: &str) -> (&str, Vec<(String, String)>) {
+    let (path, query) = uri.split_once('?').unwrap_or((uri, ""));
+    let mut pairs: Vec<_> = url::form_urlencoded::parse(query.as_bytes())
+        .into_owned()
+        .collect();
+    pairs.sort();
+    (path, pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(uri: &str, headers: &[(&str, &str)]) -> SerializedRequest {
+        SerializedRequest {
+            uri: uri.to_owned(),
+            method: "GET".to_owned(),
+            headers: headers
+                .iter()
+                .map(|(name, value)| ((*name).to_owned(), (*value).to_owned()))
+                .collect(),
+            body: String::new(),
+        }
+    }
+
+    #[test]
+    fn ignores_volatile_headers() {
+        let actual = request(
+            "/dbs",
+            &[("x-ms-date", "today"), ("x-ms-version", "2018-12-31")],
+        );
+        let expected = request(
+            "/dbs",
+            &[("x-ms-date", "yesterday"), ("x-ms-version", "2018-12-31")],
+        );
+        assert!(RequestMatcher::default().check(&actual, &expected).is_ok());
+
+        let expected = request("/dbs", &[("x-ms-version", "2020-01-01")]);
+        assert!(RequestMatcher::default().check(&actual, &expected).is_err());
+        assert!(RequestMatcher::default()
+            .ignore_headers([HeaderName::from_static("x-ms-version")])
+            .check(&actual, &expected)
+            .is_ok());
+        assert!(RequestMatcher::default()
+            .compare_headers(false)
+            .check(&actual, &expected)
+            .is_ok());
+    }
+
+    #[test]
+    fn query_order() {
+        let actual = request("/c?restype=container&comp=list", &[]);
+        let expected = request("/c?comp=list&restype=container", &[]);
+        assert!(RequestMatcher::default().check(&actual, &expected).is_err());
+        assert!(RequestMatcher::default()
+            .ignore_query_order(true)
+            .check(&actual, &expected)
+            .is_ok());
+
+        let expected = request("/other?comp=list&restype=container", &[]);
+        assert!(RequestMatcher::default()
+            .ignore_query_order(true)
+            .check(&actual, &expected)
+            .is_err());
+    }
+}
@@ -0,0 +1,475 @@
+use crate::error::{Error, ErrorKind, ResultExt};
+use crate::headers::{HeaderName, HeaderValue, Headers};
+use crate::sleep::sleep;
+use crate::{BytesStream, HttpClient, Method, Request, Response, StatusCode};
+use bytes::Bytes;
+use regex::Regex;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+type HeaderPredicate = Arc<dyn Fn(Option<&str>) -> bool + Send + Sync>;
+
+/// An [`HttpClient`] answering requests with scripted replies, for unit tests.
+///
+/// Register [`Expectation`]s, each one matching requests by method, URL and headers, then send
+/// requests through a pipeline using this client and finally [`verify`](MockHttpClient::verify)
+/// that every expectation was met. No file is read or written and no request goes to the network.
+///
+/// Clones share the same expectations, so a clone can be handed to the pipeline, through
+/// [`TransportOptions::new_with_mock_http_client`](crate::TransportOptions::new_with_mock_http_client),
+/// while the test keeps the original to verify it.
+///
+/// # Examples
+///
+/// ```
+/// use azure_core::mock::{Expectation, MockHttpClient, Reply};
+/// use azure_core::{Method, StatusCode};
+///
+/// let client = MockHttpClient::new();
+/// client.expect(
+///     Expectation::new(Method::Get, r"/dbs$")
+///         .unwrap()
+///         .reply(Reply::new(StatusCode::ServiceUnavailable))
+///         .reply(Reply::new(StatusCode::Ok).body(r#"{"Databases":[]}"#)),
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockHttpClient {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    expectations: Vec<Expectation>,
+    unmatched: Vec<String>,
+}
+
+impl MockHttpClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an expectation.
+    ///
+    /// When several expectations match a request, the first one registered and not exhausted is used.
+    pub fn expect(&self, expectation: Expectation) -> &Self {
+        self.state.lock().unwrap().expectations.push(expectation);
+        self
+    }
+
+    /// Checks that every expectation was met and that every request matched an expectation.
+    ///
+    /// An expectation is met when it was called exactly the expected number of times if one was
+    /// set with [`Expectation::times`], or else often enough to use all of its replies.
+    pub fn verify(&self) -> crate::Result<()> {
+        let state = self.state.lock().unwrap();
+        let mut failures: Vec<String> = state
+            .unmatched
+            .iter()
+            .map(|request| format!("unexpected request {}", request))
+            .collect();
+        for expectation in &state.expectations {
+            let expected = expectation.times.unwrap_or(expectation.replies.len());
+            let met = match expectation.times {
+                Some(times) => expectation.calls == times,
+                None => expectation.calls >= expected,
+            };
+            if !met {
+                failures.push(format!(
+                    "expected {} {} to be called {} time(s) but it was called {} time(s)",
+                    expectation.method, expectation.url, expected, expectation.calls
+                ));
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::message(
+                ErrorKind::MockFramework,
+                failures.join("\n"),
+            ))
+        }
+    }
+
+    /// Finds the reply to a request, recording the call.
+    fn reply_to(&self, request: &Request) -> crate::Result<Reply> {
+        let mut state = self.state.lock().unwrap();
+        let expectation = state
+            .expectations
+            .iter_mut()
+            .find(|expectation| !expectation.is_exhausted() && expectation.matches(request));
+        match expectation {
+            Some(expectation) => {
+                let reply = expectation.next_reply();
+                expectation.calls += 1;
+                Ok(reply)
+            }
+            None => {
+                let description = format!("{} {}", request.method(), request.url());
+                state.unmatched.push(description.clone());
+                Err(Error::with_message(ErrorKind::MockFramework, || {
+                    format!("no expectation matches the request {}", description)
+                }))
+            }
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl HttpClient for MockHttpClient {
+    async fn execute_request(&self, request: &Request) -> crate::Result<Response> {
+        let reply = self.reply_to(request)?;
+        if let Some(delay) = reply.delay {
+            sleep(delay).await;
+        }
+        match reply.outcome {
+            Outcome::Response {
+                status,
+                headers,
+                body,
+            } => Ok(Response::new(
+                status,
+                headers,
+                Box::pin(BytesStream::new(body)),
+            )),
+            Outcome::Error { kind, message } => Err(Error::message(kind, message)),
+        }
+    }
+}
+
+/// The requests an [`MockHttpClient`] expects, and how to reply to them.
+#[derive(Clone)]
+pub struct Expectation {
+    method: Method,
+    url: Regex,
+    headers: Vec<(HeaderName, HeaderPredicate)>,
+    replies: VecDeque<Reply>,
+    times: Option<usize>,
+    calls: usize,
+}
+
+impl Expectation {
+    /// Expects requests with the given method and whose full URL matches the regular expression
+    /// `url_pattern`.
+    ///
+    /// The pattern is not anchored: use `^` and `$` to match the whole URL.
+    pub fn new(method: Method, url_pattern: &str) -> crate::Result<Self> {
+        let url = Regex::new(url_pattern).with_context(ErrorKind::MockFramework, || {
+            format!("invalid URL pattern: {}", url_pattern)
+        })?;
+        Ok(Self {
+            method,
+            url,
+            headers: Vec::new(),
+            replies: VecDeque::new(),
+            times: None,
+            calls: 0,
+        })
+    }
+
+    /// Only matches requests with a header of this value.
+    pub fn header(self, name: impl Into<HeaderName>, value: impl Into<String>) -> Self {
+        let value = value.into();
+        self.header_predicate(name, move |actual| actual == Some(value.as_str()))
+    }
+
+    /// Only matches requests whose header value, `None` if missing, satisfies the predicate.
+    pub fn header_predicate<F>(mut self, name: impl Into<HeaderName>, predicate: F) -> Self
+    where
+        F: Fn(Option<&str>) -> bool + Send + Sync + 'static,
+    {
+        self.headers.push((name.into(), Arc::new(predicate)));
+        self
+    }
+
+    /// Adds a reply to the sequence of replies.
+    ///
+    /// Matching requests get the replies in order, and the last one is repeated once the
+    /// sequence is over. Without any reply, requests get an empty `200 OK` response.
+    pub fn reply(mut self, reply: Reply) -> Self {
+        self.replies.push_back(reply);
+        self
+    }
+
+    /// Expects exactly this number of matching requests.
+    ///
+    /// Once they were received, the expectation no longer matches any request.
+    pub fn times(self, times: usize) -> Self {
+        Self {
+            times: Some(times),
+            ..self
+        }
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.times.is_some_and(|times| self.calls >= times)
+    }
+
+    fn matches(&self, request: &Request) -> bool {
+        request.method() == &self.method
+            && self.url.is_match(request.url().as_str())
+            && self
+                .headers
+                .iter()
+                .all(|(name, predicate)| predicate(request.headers().get_optional_str(name)))
+    }
+
+    fn next_reply(&mut self) -> Reply {
+        if self.replies.len() > 1 {
+            self.replies.pop_front().unwrap()
+        } else {
+            self.replies
+                .front()
+                .cloned()
+                .unwrap_or_else(|| Reply::new(StatusCode::Ok))
+        }
+    }
+}
+
+impl std::fmt::Debug for Expectation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Expectation")
+            .field("method", &self.method)
+            .field("url", &self.url)
+            .field(
+                "headers",
+                &self
+                    .headers
+                    .iter()
+                    .map(|(name, _)| name.as_str())
+                    .collect::<Vec<_>>(),
+            )
+            .field("replies", &self.replies)
+            .field("times", &self.times)
+            .field("calls", &self.calls)
+            .finish()
+    }
+}
+
+/// A scripted reply of an [`MockHttpClient`]: either a response or a transport error.
+#[derive(Debug, Clone)]
+pub struct Reply {
+    outcome: Outcome,
+    delay: Option<Duration>,
+}
+
+#[derive(Debug, Clone)]
+enum Outcome {
+    Response {
+        status: StatusCode,
+        headers: Headers,
+        body: Bytes,
+    },
+    Error {
+        kind: ErrorKind,
+        message: String,
+    },
+}
+
+impl Reply {
+    /// A response with the given status, no header and an empty body.
+    pub fn new(status: StatusCode) -> Self {
+        Self {
+            outcome: Outcome::Response {
+                status,
+                headers: Headers::new(),
+                body: Bytes::new(),
+            },
+            delay: None,
+        }
+    }
+
+    /// A transport error, such as [`ErrorKind::Io`] for a connection reset.
+    pub fn error(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            outcome: Outcome::Error {
+                kind,
+                message: message.into(),
+            },
+            delay: None,
+        }
+    }
+
+    /// Adds a header to the response.
+    pub fn header(mut self, name: impl Into<HeaderName>, value: impl Into<HeaderValue>) -> Self {
+        if let Outcome::Response { headers, .. } = &mut self.outcome {
+            headers.insert(name, value);
+        }
+        self
+    }
+
+    /// Sets the body of the response.
+    pub fn body(mut self, body: impl Into<Bytes>) -> Self {
+        if let Outcome::Response { body: b, .. } = &mut self.outcome {
+            *b = body.into();
+        }
+        self
+    }
+
+    /// Waits before replying.
+    pub fn delay(self, delay: Duration) -> Self {
+        Self {
+            delay: Some(delay),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::headers::{CONTINUATION, RETRY_AFTER};
+    use crate::prelude::Continuation;
+    use crate::{
+        ClientOptions, Context, Continuable, Pageable, Pipeline, RetryMode, RetryOptions,
+        TransportOptions, Url,
+    };
+    use futures::StreamExt;
+
+    fn pipeline(client: &MockHttpClient, retry: RetryOptions) -> Pipeline {
+        let options =
+            ClientOptions::new(TransportOptions::new_with_mock_http_client(client.clone()))
+                .retry(retry.mode(RetryMode::Fixed).delay(Duration::ZERO));
+        Pipeline::new(None, None, options, Vec::new(), Vec::new())
+    }
+
+    fn get(pipeline: &Pipeline, url: &str) -> crate::Result<Response> {
+        let mut request = Request::new(Url::parse(url).unwrap(), Method::Get);
+        futures::executor::block_on(pipeline.send(&mut Context::new(), &mut request))
+    }
+
+    #[test]
+    fn retries_scripted_failures() {
+        let client = MockHttpClient::new();
+        client.expect(
+            Expectation::new(Method::Get, r"^https://example\.com/dbs$")
+                .unwrap()
+                .header_predicate("user-agent", |agent| agent.is_some())
+                .reply(Reply::error(ErrorKind::Io, "connection reset"))
+                .reply(Reply::new(StatusCode::ServiceUnavailable).header(RETRY_AFTER, "0"))
+                .reply(Reply::new(StatusCode::Ok).body("done")),
+        );
+
+        let response = get(
+            &pipeline(&client, RetryOptions::default()),
+            "https://example.com/dbs",
+        )
+        .unwrap();
+        let body = futures::executor::block_on(response.into_body_string());
+        assert_eq!(body, "done");
+        client.verify().unwrap();
+    }
+
+    #[test]
+    fn unmet_and_unexpected_requests_fail_verification() {
+        let client = MockHttpClient::new();
+        client.expect(
+            Expectation::new(Method::Delete, "/dbs/db")
+                .unwrap()
+                .times(1),
+        );
+        let pipeline = pipeline(&client, RetryOptions::default().mode(RetryMode::None));
+
+        let error = get(&pipeline, "https://example.com/colls").unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::MockFramework);
+        let error = client.verify().unwrap_err();
+        assert!(error.to_string().contains("unexpected request GET"));
+        assert!(error
+            .to_string()
+            .contains("called 1 time(s) but it was called 0"));
+    }
+
+    #[test]
+    fn delayed_replies_time_out() {
+        let client = MockHttpClient::new();
+        client.expect(
+            Expectation::new(Method::Get, "/slow")
+                .unwrap()
+                .reply(Reply::new(StatusCode::Ok).delay(Duration::from_secs(5)))
+                .times(1),
+        );
+        client.expect(Expectation::new(Method::Get, "/slow").unwrap().times(1));
+        let retry = RetryOptions::default().try_timeout(Duration::from_millis(50));
+
+        let response = get(&pipeline(&client, retry), "https://example.com/slow");
+        assert_eq!(response.unwrap().status(), StatusCode::Ok);
+        client.verify().unwrap();
+    }
+
+    struct Page {
+        continuation: Option<String>,
+    }
+
+    impl Continuable for Page {
+        fn continuation(&self) -> Option<Continuation> {
+            self.continuation.as_deref().map(Continuation::from)
+        }
+    }
+
+    #[test]
+    fn pageable_follows_continuations() {
+        let client = MockHttpClient::new();
+        client
+            .expect(
+                Expectation::new(Method::Get, "/items")
+                    .unwrap()
+                    .header_predicate(CONTINUATION, |continuation| continuation.is_none())
+                    .reply(Reply::new(StatusCode::Ok).header(CONTINUATION, "page2"))
+                    .times(1),
+            )
+            .expect(
+                Expectation::new(Method::Get, "/items")
+                    .unwrap()
+                    .header(CONTINUATION, "page2")
+                    .times(1),
+            );
+        let pipeline = Arc::new(pipeline(&client, RetryOptions::default()));
+
+        let pages: Pageable<Page, Error> =
+            Pageable::new(move |continuation: Option<Continuation>| {
+                let pipeline = pipeline.clone();
+                async move {
+                    let mut request = Request::new(
+                        Url::parse("https://example.com/items").unwrap(),
+                        Method::Get,
+                    );
+                    if let Some(Continuation::String(continuation)) = continuation {
+                        request.insert_header(CONTINUATION, continuation);
+                    }
+                    let response = pipeline.send(&mut Context::new(), &mut request).await?;
+                    Ok(Page {
+                        continuation: response.headers().get_optional_string(&CONTINUATION),
+                    })
+                }
+            });
+
+        assert_eq!(futures::executor::block_on(pages.count()), 2);
+        client.verify().unwrap();
+    }
+
+    #[test]
+    fn only_opted_out_clients_bypass_the_recordings() {
+        if std::env::var(crate::mock::TESTING_MODE_KEY).as_deref()
+            == Ok(crate::mock::TESTING_MODE_RECORD)
+        {
+            return;
+        }
+        let client = MockHttpClient::new();
+        // a client given without a transaction name is replaced by the player
+        let options = ClientOptions::new(TransportOptions::new(Arc::new(client.clone())));
+        let player = Pipeline::new(None, None, options, Vec::new(), Vec::new());
+        // which fails, as it requires a transaction name
+        let replayed = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            get(&player, "https://example.com/items")
+        }));
+        assert!(replayed.map_or(true, |result| result.is_err()));
+        client.verify().unwrap();
+
+        let mock = pipeline(&client, RetryOptions::default());
+        assert!(get(&mock, "https://example.com/items").is_err());
+        assert!(client.verify().is_err());
+    }
+}
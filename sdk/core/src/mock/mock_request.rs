@@ -1,9 +1,10 @@
+use super::Sanitizer;
 use crate::Method;
 use crate::{Body, Request};
 use serde::de::Visitor;
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 use serde::{Deserialize, Deserializer};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
 use url::Url;
 
@@ -125,3 +126,26 @@ impl Serialize for Request {
         state.end()
     }
 }
+
+/// A request as written to disk by the recorder.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct SerializedRequest {
+    pub(crate) uri: String,
+    pub(crate) method: String,
+    pub(crate) headers: BTreeMap<String, String>,
+    pub(crate) body: String,
+}
+
+impl SerializedRequest {
+    pub(crate) fn new(request: &Request) -> crate::Result<Self> {
+        Ok(serde_json::from_value(serde_json::to_value(request)?)?)
+    }
+
+    pub(crate) fn sanitize(&mut self, sanitizers: &[Sanitizer]) {
+        for sanitizer in sanitizers {
+            sanitizer.sanitize_uri(&mut self.uri);
+            sanitizer.sanitize_headers(&mut self.headers);
+            sanitizer.sanitize_body(&mut self.body);
+        }
+    }
+}
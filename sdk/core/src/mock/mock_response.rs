@@ -1,3 +1,4 @@
+use super::Sanitizer;
 use crate::{
     collect_pinned_stream, error,
     headers::{HeaderName, HeaderValue, Headers},
@@ -103,3 +104,16 @@ pub(crate) struct SerializedMockResponse {
     headers: BTreeMap<String, String>,
     body: String,
 }
+
+impl SerializedMockResponse {
+    pub(crate) fn new(response: &MockResponse) -> crate::Result<Self> {
+        Ok(serde_json::from_value(serde_json::to_value(response)?)?)
+    }
+
+    pub(crate) fn sanitize(&mut self, sanitizers: &[Sanitizer]) {
+        for sanitizer in sanitizers {
+            sanitizer.sanitize_headers(&mut self.headers);
+            sanitizer.sanitize_body(&mut self.body);
+        }
+    }
+}
@@ -1,12 +1,18 @@
+mod matcher;
+mod mock_http_client;
 mod mock_request;
 mod mock_response;
 mod mock_transaction;
 mod player_policy;
 mod recorder_policy;
+mod sanitizer;
 
+pub use matcher::RequestMatcher;
+pub use mock_http_client::{Expectation, MockHttpClient, Reply};
 use mock_transaction::MockTransaction;
 use player_policy::MockTransportPlayerPolicy;
 use recorder_policy::MockTransportRecorderPolicy;
+pub use sanitizer::Sanitizer;
 use std::sync::Arc;
 
 pub const TESTING_MODE_KEY: &str = "TESTING_MODE";
@@ -15,13 +21,17 @@ pub const TESTING_MODE_RECORD: &str = "RECORD";
 
 // Replace the default transport policy at runtime
 //
-// Replacement happens if these two conditions are met:
+// Replacement happens if these three conditions are met:
 // 1. The mock_transport_framework is enabled
-// 2. The environmental variable TESTING_MODE is either RECORD or PLAY
+// 2. The transport options were not created with `TransportOptions::new_with_mock_http_client`
+// 3. The environmental variable TESTING_MODE is either RECORD or PLAY
 pub(crate) fn set_mock_transport_policy(
     policy: &mut std::sync::Arc<dyn crate::Policy>,
     transport_options: crate::TransportOptions,
 ) {
+    if !transport_options.use_recordings {
+        return;
+    }
     match std::env::var(TESTING_MODE_KEY)
         .as_deref()
         .unwrap_or(TESTING_MODE_REPLAY)
@@ -1,15 +1,20 @@
+use super::mock_request::SerializedRequest;
 use super::mock_response::MockResponse;
 use super::mock_transaction::MockTransaction;
 use crate::error::{Error, ErrorKind};
 use crate::policies::{Policy, PolicyResult};
 use crate::{Context, Request, TransportOptions};
-use std::sync::Arc;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
 
 #[derive(Debug, Clone)]
 pub struct MockTransportPlayerPolicy {
     #[allow(unused)]
     pub(crate) transport_options: TransportOptions,
     transaction: MockTransaction,
+    /// The recorded transactions already replayed, when playing out of order.
+    replayed: Arc<Mutex<HashSet<usize>>>,
 }
 
 impl MockTransportPlayerPolicy {
@@ -18,8 +23,46 @@ impl MockTransportPlayerPolicy {
         Self {
             transport_options,
             transaction,
+            replayed: Arc::new(Mutex::new(HashSet::new())),
         }
     }
+
+    fn read_request(path: &Path, number: usize) -> crate::Result<SerializedRequest> {
+        let request = std::fs::read_to_string(path.join(format!("{}_request.json", number)))?;
+        Ok(serde_json::from_str(&request)?)
+    }
+
+    fn read_response(path: &Path, number: usize) -> crate::Result<MockResponse> {
+        let response = std::fs::read_to_string(path.join(format!("{}_response.json", number)))?;
+        Ok(serde_json::from_str(&response)?)
+    }
+
+    /// Finds the first recorded request not replayed yet matching `actual`.
+    fn find_unordered(&self, path: &Path, actual: &SerializedRequest) -> crate::Result<usize> {
+        let matcher = &self.transport_options.matcher;
+        let mut replayed = self.replayed.lock().unwrap();
+        let mut last_error = None;
+        for number in
+            (0..).take_while(|number| path.join(format!("{}_request.json", number)).exists())
+        {
+            if replayed.contains(&number) {
+                continue;
+            }
+            match matcher.check(actual, &Self::read_request(path, number)?) {
+                Ok(()) => {
+                    replayed.insert(number);
+                    return Ok(number);
+                }
+                Err(error) => last_error = Some(error),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| {
+            Error::message(
+                ErrorKind::MockFramework,
+                "all the recorded requests have already been replayed",
+            )
+        }))
+    }
 }
 
 #[async_trait::async_trait]
@@ -33,114 +76,23 @@ impl Policy for MockTransportPlayerPolicy {
         // there must be no more policies
         assert_eq!(0, next.len());
 
-        // deserialize to file both the request and the response
-        let (expected_request, expected_response) = {
-            let mut request_path = self.transaction.file_path(false)?;
-            let mut response_path = request_path.clone();
+        // the request is sanitized the same way the recorded one was
+        let mut actual_request = SerializedRequest::new(request)?;
+        actual_request.sanitize(&self.transport_options.sanitizers);
 
+        let path = self.transaction.file_path(false)?;
+        let number = if self.transport_options.matcher.allows_out_of_order() {
+            self.find_unordered(&path, &actual_request)?
+        } else {
             let number = self.transaction.number();
-            request_path.push(format!("{}_request.json", number));
-            response_path.push(format!("{}_response.json", number));
-
-            let request = std::fs::read_to_string(&request_path)?;
-            let response = std::fs::read_to_string(&response_path)?;
-
-            (request, response)
+            let expected_request = Self::read_request(&path, number)?;
+            self.transport_options
+                .matcher
+                .check(&actual_request, &expected_request)?;
+            number
         };
 
-        let expected_request: Request = serde_json::from_str(&expected_request)?;
-        let expected_response = serde_json::from_str::<MockResponse>(&expected_response)?;
-
-        let expected_uri = expected_request.path_and_query();
-        let actual_uri = request.path_and_query();
-        if expected_uri != actual_uri {
-            return Err(Error::with_message(ErrorKind::MockFramework, || {
-                format!(
-                    "mismatched request uri. Actual '{0}', Expected: '{1}'",
-                    actual_uri, expected_uri
-                )
-            }));
-        }
-
-        // check if the passed request matches the one read from disk
-        // We will ignore some headers that are bound to change every time
-        // We'll probabily want to make the exclusion list dynamic at some point.
-        const SKIPPED_HEADERS: &[&str] = &["Date", "x-ms-date", "authorization", "user-agent"];
-        let actual_headers = request
-            .headers()
-            .iter()
-            .filter(|(h, _)| !SKIPPED_HEADERS.contains(&h.as_str()))
-            .collect::<Vec<_>>();
-
-        let expected_headers = expected_request
-            .headers()
-            .iter()
-            .filter(|(h, _)| !SKIPPED_HEADERS.contains(&h.as_str()))
-            .collect::<Vec<_>>();
-
-        // In order to accept a request, we make sure that:
-        // 1. There are no extra headers (in both the received and read request).
-        // 2. Each header has the same value.
-        if actual_headers.len() != expected_headers.len() {
-            return Err(Error::with_message(ErrorKind::MockFramework, || {
-                format!(
-                    "different number of headers in request. Actual: {0}, Expected: {1}",
-                    actual_headers.len(),
-                    expected_headers.len(),
-                )
-            }));
-        }
-
-        for (actual_header_key, actual_header_value) in actual_headers.iter() {
-            let (_, expected_header_value) = expected_headers
-                .iter()
-                .find(|(h, _)| actual_header_key.as_str() == h.as_str())
-                .ok_or_else(|| Error::with_message(ErrorKind::MockFramework, ||
-                    format!("received request have header '{0}' but it was not present in the read request",
-                    actual_header_key.as_str(),
-                )))?;
-
-            if actual_header_value != expected_header_value {
-                return Err(Error::with_message(ErrorKind::MockFramework, || {
-                    format!(
-                        "request header '{0}' value is different. Actual: {1}, Expected: {2}",
-                        actual_header_key.as_str().to_owned(),
-                        actual_header_value.as_str().to_owned(),
-                        expected_header_value.as_str().to_owned(),
-                    )
-                }));
-            }
-        }
-
-        if expected_request.method() != request.method() {
-            return Err(Error::with_message(ErrorKind::MockFramework, || {
-                format!(
-                    "mismatched HTTP request method. Actual: {0}, Expected: {1}",
-                    expected_request.method(),
-                    request.method(),
-                )
-            }));
-        }
-
-        let actual_body = match request.body() {
-            crate::Body::Bytes(bytes) => bytes as &[u8],
-            crate::Body::SeekableStream(_) => unimplemented!(),
-        };
-
-        let expected_body = match expected_request.body() {
-            crate::Body::Bytes(bytes) => bytes as &[u8],
-            crate::Body::SeekableStream(_) => unimplemented!(),
-        };
-
-        if actual_body != expected_body {
-            return Err(Error::with_message(ErrorKind::MockFramework, || {
-                format!(
-                    "mismatched request body. Actual: {0:?}, Expected: {1:?}",
-                    actual_body.to_vec(),
-                    expected_body.to_vec(),
-                )
-            }));
-        }
+        let expected_response = Self::read_response(&path, number)?;
 
         self.transaction.increment_number();
         Ok(expected_response.into())
@@ -1,4 +1,5 @@
-use super::mock_response::MockResponse;
+use super::mock_request::SerializedRequest;
+use super::mock_response::{MockResponse, SerializedMockResponse};
 use super::MockTransaction;
 use crate::error::ResultExt;
 use crate::policies::{Policy, PolicyResult};
@@ -41,7 +42,10 @@ impl Policy for MockTransportRecorderPolicy {
         request_path.push(format!("{}_request.json", number));
         response_path.push(format!("{}_response.json", number));
 
-        let request_contents = serde_json::to_string(&request).unwrap();
+        let sanitizers = &self.transport_options.sanitizers;
+        let mut serialized_request = SerializedRequest::new(request)?;
+        serialized_request.sanitize(sanitizers);
+        let request_contents = serde_json::to_string(&serialized_request).unwrap();
         {
             let mut request_contents_stream = std::fs::File::create(&request_path).unwrap();
             request_contents_stream
@@ -61,7 +65,9 @@ impl Policy for MockTransportRecorderPolicy {
         // we need to duplicate the response because we are about to consume the response stream.
         // We replace the HTTP stream with a memory-backed stream.
         let (response, mock_response) = MockResponse::duplicate(response).await?;
-        let response_contents = serde_json::to_string(&mock_response).unwrap();
+        let mut serialized_response = SerializedMockResponse::new(&mock_response)?;
+        serialized_response.sanitize(sanitizers);
+        let response_contents = serde_json::to_string(&serialized_response).unwrap();
         {
             let mut response_contents_stream = std::fs::File::create(&response_path).unwrap();
             response_contents_stream
@@ -0,0 +1,229 @@
+use crate::error::{ErrorKind, ResultExt};
+use crate::headers::{HeaderName, AUTHORIZATION};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Removes secrets from the transactions written by the recorder.
+///
+/// Sanitizers are applied to every request and response before they are written to disk, and to
+/// every request received by the player before it is compared to the recorded one, so that a
+/// sanitized recording still matches a live request.
+#[derive(Debug, Clone)]
+pub enum Sanitizer {
+    /// Replaces every match of a regular expression in URIs, header values and bodies.
+    ///
+    /// The replacement can refer to capture groups, such as `$1`.
+    Regex { regex: Regex, replacement: String },
+    /// Replaces the value of a header.
+    Header {
+        name: HeaderName,
+        replacement: String,
+    },
+    /// Replaces the values found at a JSON path in JSON bodies.
+    ///
+    /// Paths are made of `.`-separated keys, optionally followed by an array index or `[*]` to
+    /// select every element, for example `$.keys[*].value`.
+    JsonPath { path: String, replacement: String },
+}
+
+impl Sanitizer {
+    pub fn regex(pattern: &str, replacement: impl Into<String>) -> crate::Result<Self> {
+        let regex = Regex::new(pattern).with_context(ErrorKind::MockFramework, || {
+            format!("invalid sanitizer regex: {}", pattern)
+        })?;
+        Ok(Self::Regex {
+            regex,
+            replacement: replacement.into(),
+        })
+    }
+
+    pub fn header(name: impl Into<HeaderName>, replacement: impl Into<String>) -> Self {
+        Self::Header {
+            name: name.into(),
+            replacement: replacement.into(),
+        }
+    }
+
+    pub fn json_path(path: impl Into<String>, replacement: impl Into<String>) -> Self {
+        Self::JsonPath {
+            path: path.into(),
+            replacement: replacement.into(),
+        }
+    }
+
+    /// The sanitizers used unless specified otherwise.
+    ///
+    /// They strip the `Authorization` header, SAS signatures, account keys in connection
+    /// strings and OAuth access tokens.
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::header(AUTHORIZATION, "<<STRIPPED>>"),
+            Self::regex(r"([?&]sig=)[^&]+", "${1}REDACTED").unwrap(),
+            Self::regex(r"(AccountKey=)[^;]+", "${1}REDACTED").unwrap(),
+            Self::json_path("$.access_token", "REDACTED"),
+            Self::json_path("$.refresh_token", "REDACTED"),
+        ]
+    }
+
+    pub(crate) fn sanitize_uri(&self, uri: &mut String) {
+        if let Self::Regex { regex, replacement } = self {
+            *uri = regex.replace_all(uri, replacement.as_str()).into_owned();
+        }
+    }
+
+    pub(crate) fn sanitize_headers(&self, headers: &mut BTreeMap<String, String>) {
+        match self {
+            Self::Regex { regex, replacement } => {
+                for value in headers.values_mut() {
+                    *value = regex.replace_all(value, replacement.as_str()).into_owned();
+                }
+            }
+            Self::Header { name, replacement } => {
+                for (header_name, value) in headers.iter_mut() {
+                    if header_name.eq_ignore_ascii_case(name.as_str()) {
+                        *value = replacement.clone();
+                    }
+                }
+            }
+            Self::JsonPath { .. } => {}
+        }
+    }
+
+    /// Sanitizes a base64 encoded body.
+    pub(crate) fn sanitize_body(&self, body: &mut String) {
+        let decoded = match base64::decode(&body) {
+            Ok(decoded) if !decoded.is_empty() => decoded,
+            _ => return,
+        };
+        let sanitized = match self {
+            Self::Regex { regex, replacement } => match std::str::from_utf8(&decoded) {
+                Ok(text) => regex.replace_all(text, replacement.as_str()).into_owned(),
+                Err(_) => return,
+            },
+            Self::JsonPath { path, replacement } => {
+                let mut json: Value = match serde_json::from_slice(&decoded) {
+                    Ok(json) => json,
+                    Err(_) => return,
+                };
+                // the body is left untouched if there is nothing to replace
+                if !replace_json_path(&mut json, &parse_json_path(path), replacement) {
+                    return;
+                }
+                json.to_string()
+            }
+            Self::Header { .. } => return,
+        };
+        *body = base64::encode(sanitized);
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+    Wildcard,
+}
+
+fn parse_json_path(path: &str) -> Vec<Segment> {
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let mut segments = Vec::new();
+    for part in path.split('.').filter(|part| !part.is_empty()) {
+        let mut parts = part.split('[');
+        if let Some(key) = parts.next().filter(|key| !key.is_empty()) {
+            segments.push(Segment::Key(key.to_owned()));
+        }
+        for index in parts {
+            let index = index.trim_end_matches(']');
+            segments.push(match index {
+                "*" => Segment::Wildcard,
+                index => match index.parse() {
+                    Ok(index) => Segment::Index(index),
+                    Err(_) => {
+                        Segment::Key(index.trim_matches(|c| c == '\'' || c == '"').to_owned())
+                    }
+                },
+            });
+        }
+    }
+    segments
+}
+
+/// Replaces the values at `segments`, returning whether any was found.
+fn replace_json_path(value: &mut Value, segments: &[Segment], replacement: &str) -> bool {
+    let (segment, rest) = match segments.split_first() {
+        Some(split) => split,
+        None => {
+            *value = Value::String(replacement.to_owned());
+            return true;
+        }
+    };
+    match (segment, value) {
+        (Segment::Key(key), Value::Object(map)) => map
+            .get_mut(key)
+            .is_some_and(|value| replace_json_path(value, rest, replacement)),
+        (Segment::Index(index), Value::Array(array)) => array
+            .get_mut(*index)
+            .is_some_and(|value| replace_json_path(value, rest, replacement)),
+        (Segment::Wildcard, Value::Array(array)) => array.iter_mut().fold(false, |found, value| {
+            replace_json_path(value, rest, replacement) || found
+        }),
+        (Segment::Wildcard, Value::Object(map)) => map.values_mut().fold(false, |found, value| {
+            replace_json_path(value, rest, replacement) || found
+        }),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regex_sanitizer() {
+        let sanitizer = Sanitizer::regex(r"([?&]sig=)[^&]+", "${1}REDACTED").unwrap();
+        let mut uri = "/c/b?sv=2020&sig=abc%2Fdef&se=2022".to_owned();
+        sanitizer.sanitize_uri(&mut uri);
+        assert_eq!(uri, "/c/b?sv=2020&sig=REDACTED&se=2022");
+
+        let mut body = base64::encode("https://a.blob.core.windows.net/c?sig=secret");
+        sanitizer.sanitize_body(&mut body);
+        assert_eq!(
+            base64::decode(body).unwrap(),
+            b"https://a.blob.core.windows.net/c?sig=REDACTED"
+        );
+    }
+
+    #[test]
+    fn header_sanitizer() {
+        let sanitizer = Sanitizer::header("x-ms-copy-source", "REDACTED");
+        let mut headers = BTreeMap::new();
+        headers.insert("x-ms-copy-source".to_owned(), "https://secret".to_owned());
+        headers.insert("x-ms-version".to_owned(), "2019-12-12".to_owned());
+        sanitizer.sanitize_headers(&mut headers);
+        assert_eq!(headers["x-ms-copy-source"], "REDACTED");
+        assert_eq!(headers["x-ms-version"], "2019-12-12");
+    }
+
+    #[test]
+    fn json_path_sanitizer() {
+        let sanitizer = Sanitizer::json_path("$.keys[*].value", "REDACTED");
+        let mut body = base64::encode(
+            r#"{"keys":[{"keyName":"key1","value":"secret1"},{"keyName":"key2","value":"secret2"}]}"#,
+        );
+        sanitizer.sanitize_body(&mut body);
+        let json: Value = serde_json::from_slice(&base64::decode(body).unwrap()).unwrap();
+        assert_eq!(json["keys"][0]["keyName"], "key1");
+        assert_eq!(json["keys"][0]["value"], "REDACTED");
+        assert_eq!(json["keys"][1]["value"], "REDACTED");
+
+        assert_eq!(
+            parse_json_path("$.a[0].b"),
+            vec![
+                Segment::Key("a".to_owned()),
+                Segment::Index(0),
+                Segment::Key("b".to_owned())
+            ]
+        );
+    }
+}
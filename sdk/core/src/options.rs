@@ -1,4 +1,9 @@
-use crate::policies::{ExponentialRetryPolicy, FixedRetryPolicy, NoRetryPolicy, Policy};
+use crate::headers::HeaderName;
+use crate::policies::{
+    DefaultRetryClassifier, ExponentialRetryPolicy, FixedRetryPolicy, NoRetryPolicy, Policy,
+    RetryClassifier, TryTimeoutPolicy, DEFAULT_ALLOWED_HEADER_NAMES,
+    DEFAULT_ALLOWED_QUERY_PARAMETERS,
+};
 use crate::HttpClient;
 use std::sync::Arc;
 use std::time::Duration;
@@ -32,6 +37,8 @@ pub struct ClientOptions {
     pub(crate) retry: RetryOptions,
     /// Telemetry options.
     pub(crate) telemetry: TelemetryOptions,
+    /// Logging options.
+    pub(crate) logging: LoggingOptions,
     /// Transport options.
     #[allow(dead_code)] // wasm
     pub(crate) transport: TransportOptions,
@@ -44,6 +51,7 @@ impl ClientOptions {
             per_retry_policies: Vec::new(),
             retry: RetryOptions::default(),
             telemetry: TelemetryOptions::default(),
+            logging: LoggingOptions::default(),
             transport,
         }
     }
@@ -56,6 +64,7 @@ impl ClientOptions {
             per_retry_policies: Vec::new(),
             retry: RetryOptions::default(),
             telemetry: TelemetryOptions::default(),
+            logging: LoggingOptions::default(),
         }
     }
 
@@ -69,11 +78,19 @@ impl ClientOptions {
         &mut self.per_retry_policies
     }
 
+    /// A mutable reference to logging options.
+    ///
+    /// Client libraries use it to allow the service specific headers to be logged.
+    pub fn logging_mut(&mut self) -> &mut LoggingOptions {
+        &mut self.logging
+    }
+
     setters! {
         per_call_policies: Vec<Arc<dyn Policy>> => per_call_policies,
         per_retry_policies: Vec<Arc<dyn Policy>> => per_retry_policies,
         retry: RetryOptions => retry,
         telemetry: TelemetryOptions => telemetry,
+        logging: LoggingOptions => logging,
         transport: TransportOptions => transport,
     }
 }
@@ -120,10 +137,26 @@ pub struct RetryOptions {
     /// The default is 3.
     max_retries: u32,
 
-    /// The maximum permissible delay between retry attempts.
+    /// The maximum time spent retrying, from the first failed attempt.
     ///
     /// The default is 1 minute.
     max_delay: Duration,
+
+    /// The maximum wait before a single retry attempt, including a wait requested by the service.
+    ///
+    /// The default is 30 seconds.
+    max_retry_delay: Duration,
+
+    /// Decides which failed attempts are retried.
+    ///
+    /// The default is [`DefaultRetryClassifier`].
+    classifier: Arc<dyn RetryClassifier>,
+
+    /// The maximum duration of a single attempt.
+    ///
+    /// An attempt taking longer fails with [`ErrorKind::Timeout`](crate::error::ErrorKind::Timeout)
+    /// and is retried like any other error. The default is no timeout.
+    try_timeout: Option<Duration>,
 }
 
 impl RetryOptions {
@@ -132,6 +165,9 @@ impl RetryOptions {
         delay: Duration => delay,
         max_retries: u32 => max_retries,
         max_delay: Duration => max_delay,
+        max_retry_delay: Duration => max_retry_delay,
+        classifier: Arc<dyn RetryClassifier> => classifier,
+        try_timeout: Duration => Some(try_timeout),
     }
 }
 
@@ -142,6 +178,9 @@ impl Default for RetryOptions {
             delay: Duration::from_millis(800),
             max_retries: 3,
             max_delay: Duration::from_secs(60),
+            max_retry_delay: Duration::from_secs(30),
+            classifier: Arc::new(DefaultRetryClassifier),
+            try_timeout: None,
         }
     }
 }
@@ -153,15 +192,23 @@ impl RetryOptions {
                 self.delay,
                 self.max_retries,
                 self.max_delay,
+                self.max_retry_delay,
+                self.classifier.clone(),
             )),
             RetryMode::Fixed => Arc::new(FixedRetryPolicy::new(
                 self.delay,
                 self.max_retries,
                 self.max_delay,
+                self.max_retry_delay,
+                self.classifier.clone(),
             )),
             RetryMode::None => Arc::new(NoRetryPolicy::default()),
         }
     }
+
+    pub(crate) fn to_try_timeout_policy(&self) -> Arc<dyn Policy> {
+        Arc::new(TryTimeoutPolicy::new(self.try_timeout))
+    }
 }
 
 /// Telemetry options.
@@ -177,6 +224,57 @@ impl TelemetryOptions {
     }
 }
 
+/// Logging options.
+///
+/// Requests and responses are logged at the `debug` level. Only the values of the allowed
+/// headers and query parameters are printed, all the other values are redacted.
+#[derive(Clone, Debug)]
+pub struct LoggingOptions {
+    /// The names of the headers whose values are logged.
+    pub(crate) allowed_header_names: Vec<HeaderName>,
+    /// The names of the query parameters whose values are logged.
+    pub(crate) allowed_query_parameters: Vec<String>,
+    /// Bodies up to this size, in bytes, are logged.
+    ///
+    /// The default is 0, which means bodies are not logged.
+    pub(crate) max_body_size: usize,
+}
+
+impl Default for LoggingOptions {
+    fn default() -> Self {
+        Self {
+            allowed_header_names: DEFAULT_ALLOWED_HEADER_NAMES.to_vec(),
+            allowed_query_parameters: DEFAULT_ALLOWED_QUERY_PARAMETERS
+                .iter()
+                .map(|name| (*name).to_owned())
+                .collect(),
+            max_body_size: 0,
+        }
+    }
+}
+
+impl LoggingOptions {
+    /// Allows the values of more headers to be logged.
+    pub fn add_allowed_header_names(&mut self, names: impl IntoIterator<Item = HeaderName>) {
+        self.allowed_header_names.extend(names);
+    }
+
+    /// Allows the values of more query parameters to be logged.
+    pub fn add_allowed_query_parameters<S: Into<String>>(
+        &mut self,
+        names: impl IntoIterator<Item = S>,
+    ) {
+        self.allowed_query_parameters
+            .extend(names.into_iter().map(Into::into));
+    }
+
+    setters! {
+        allowed_header_names: Vec<HeaderName> => allowed_header_names,
+        allowed_query_parameters: Vec<String> => allowed_query_parameters,
+        max_body_size: usize => max_body_size,
+    }
+}
+
 /// Transport options.
 #[derive(Clone, Debug)]
 pub struct TransportOptions {
@@ -186,6 +284,15 @@ pub struct TransportOptions {
     #[cfg(feature = "mock_transport_framework")]
     /// The name of the transaction used when reading or writing mock requests and responses.
     pub(crate) transaction_name: String,
+    #[cfg(feature = "mock_transport_framework")]
+    /// The sanitizers applied to mock requests and responses.
+    pub(crate) sanitizers: Vec<crate::mock::Sanitizer>,
+    #[cfg(feature = "mock_transport_framework")]
+    /// Decides which recorded request matches a request when replaying.
+    pub(crate) matcher: crate::mock::RequestMatcher,
+    #[cfg(feature = "mock_transport_framework")]
+    /// Whether the requests are recorded or replayed rather than sent with the HTTP client.
+    pub(crate) use_recordings: bool,
 }
 
 impl TransportOptions {
@@ -196,6 +303,12 @@ impl TransportOptions {
             http_client,
             #[cfg(feature = "mock_transport_framework")]
             transaction_name: String::new(),
+            #[cfg(feature = "mock_transport_framework")]
+            sanitizers: crate::mock::Sanitizer::defaults(),
+            #[cfg(feature = "mock_transport_framework")]
+            matcher: crate::mock::RequestMatcher::default(),
+            #[cfg(feature = "mock_transport_framework")]
+            use_recordings: true,
         }
     }
 
@@ -205,8 +318,40 @@ impl TransportOptions {
         Self {
             http_client: crate::http_client::new_http_client(),
             transaction_name,
+            sanitizers: crate::mock::Sanitizer::defaults(),
+            matcher: crate::mock::RequestMatcher::default(),
+            use_recordings: true,
         }
     }
+
+    /// Creates a new `TransportOptions` sending the requests to `client` whatever the
+    /// `TESTING_MODE`, rather than recording or replaying them.
+    #[cfg(feature = "mock_transport_framework")]
+    pub fn new_with_mock_http_client(client: crate::mock::MockHttpClient) -> Self {
+        Self {
+            use_recordings: false,
+            ..Self::new(Arc::new(client))
+        }
+    }
+
+    /// Sets the sanitizers applied to mock requests and responses, replacing the default ones.
+    #[cfg(feature = "mock_transport_framework")]
+    pub fn sanitizers(self, sanitizers: Vec<crate::mock::Sanitizer>) -> Self {
+        Self { sanitizers, ..self }
+    }
+
+    /// Adds a sanitizer applied to mock requests and responses.
+    #[cfg(feature = "mock_transport_framework")]
+    pub fn add_sanitizer(mut self, sanitizer: crate::mock::Sanitizer) -> Self {
+        self.sanitizers.push(sanitizer);
+        self
+    }
+
+    /// Sets the matcher deciding which recorded request matches a request when replaying.
+    #[cfg(feature = "mock_transport_framework")]
+    pub fn matcher(self, matcher: crate::mock::RequestMatcher) -> Self {
+        Self { matcher, ..self }
+    }
 }
 
 #[cfg(any(feature = "enable_reqwest", feature = "enable_reqwest_rustls"))]
@@ -1,8 +1,11 @@
+use futures::future::{select, Either};
 use futures::stream::unfold;
-use futures::Stream;
+use futures::{Stream, StreamExt};
 use pin_project::pin_project;
 
+use crate::cancellation::cancelled_error;
 use crate::prelude::Continuation;
+use crate::CancellationToken;
 
 macro_rules! r#try {
     ($expr:expr $(,)?) => {
@@ -64,6 +67,32 @@ where
     }
 }
 
+impl<T, E> Pageable<T, E>
+where
+    T: Send + 'static,
+    E: From<crate::error::Error> + Send + 'static,
+{
+    /// Ends the stream with an error of kind
+    /// [`ErrorKind::Cancelled`](crate::error::ErrorKind::Cancelled) once `token` is cancelled,
+    /// even while a page is being fetched.
+    pub fn with_cancellation_token(self, token: CancellationToken) -> Self {
+        let stream = unfold(Some((self.stream, token)), |state| async move {
+            let (mut stream, token) = state?;
+            if token.is_cancelled() {
+                return Some((Err(cancelled_error().into()), None));
+            }
+            match select(stream.next(), token.cancelled()).await {
+                Either::Left((Some(page), _)) => Some((page, Some((stream, token)))),
+                Either::Left((None, _)) => None,
+                Either::Right(_) => Some((Err(cancelled_error().into()), None)),
+            }
+        });
+        Self {
+            stream: Box::pin(stream),
+        }
+    }
+}
+
 impl<T, E> Stream for Pageable<T, E> {
     type Item = Result<T, E>;
 
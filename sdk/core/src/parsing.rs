@@ -12,6 +12,12 @@ impl FromStringOptional<u64> for u64 {
     }
 }
 
+impl FromStringOptional<u32> for u32 {
+    fn from_str_optional(s: &str) -> crate::Result<u32> {
+        s.parse::<u32>().map_kind(ErrorKind::DataConversion)
+    }
+}
+
 impl FromStringOptional<String> for String {
     fn from_str_optional(s: &str) -> crate::Result<String> {
         Ok(s.to_owned())
@@ -1,6 +1,9 @@
 #[cfg(not(target_arch = "wasm32"))]
 use crate::policies::TransportPolicy;
-use crate::policies::{CustomHeadersPolicy, Policy, TelemetryPolicy};
+use crate::policies::{
+    CustomHeadersPolicy, DeadlinePolicy, DistributedTracingPolicy, LoggingPolicy, Policy,
+    RequestTracingPolicy, TelemetryPolicy,
+};
 use crate::{ClientOptions, Context, Request, Response};
 use std::sync::Arc;
 
@@ -12,15 +15,20 @@ use std::sync::Arc;
 ///    immediately.
 /// 2. User-specified per-call policies are executed.
 /// 3. Telemetry policy.
-/// 4. Retry policy. It allows to re-execute the following policies.
-/// 5. Client library-specified per-retry policies. Per-retry polices are always executed at least once but are re-executed
-///    in case of retries.
-/// 6. User-specified per-retry policies are executed.
-/// 7. Authorization policy. Authorization can depend on the HTTP headers and/or the request body so it
-///    must be executed right before sending the request to the transport. Also, the authorization
-///    can depend on the current time so it must be executed at every retry.
-/// 8. Transport policy. Transport policy is always the last policy and is the policy that
-///    actually constructs the `Response` to be passed up the pipeline.
+/// 4. Distributed tracing policy. It opens a span covering the whole operation.
+/// 5. Deadline policy. It fails the operation once the deadline set on the `Context`, if any, passes.
+/// 6. Retry policy. It allows to re-execute the following policies.
+/// 7. Request tracing policy. It opens a span for the attempt and propagates it with the `traceparent` header.
+/// 8. Try timeout policy. It fails attempts taking longer than the configured timeout, so they can be retried.
+/// 9. Logging policy. It logs every attempt, redacting the headers and query parameters which are not explicitly allowed.
+/// 10. Client library-specified per-retry policies. Per-retry polices are always executed at least once but are re-executed
+///     in case of retries.
+/// 11. User-specified per-retry policies are executed.
+/// 12. Authorization policy. Authorization can depend on the HTTP headers and/or the request body so it
+///     must be executed right before sending the request to the transport. Also, the authorization
+///     can depend on the current time so it must be executed at every retry.
+/// 13. Transport policy. Transport policy is always the last policy and is the policy that
+///     actually constructs the `Response` to be passed up the pipeline.
 ///
 /// A pipeline is immutable. In other words a policy can either succeed and call the following
 /// policy of fail and return to the calling policy. Arbitrary policy "skip" must be avoided (but
@@ -54,7 +62,7 @@ impl Pipeline {
                 + per_call_policies.len()
                 + options.per_retry_policies.len()
                 + per_retry_policies.len()
-                + 3,
+                + 8,
         );
 
         pipeline.extend_from_slice(&per_call_policies);
@@ -65,9 +73,19 @@ impl Pipeline {
 
         pipeline.push(Arc::new(CustomHeadersPolicy::default()));
 
+        pipeline.push(Arc::new(DistributedTracingPolicy::new()));
+
+        pipeline.push(Arc::new(DeadlinePolicy::new()));
+
         let retry_policy = options.retry.to_policy();
         pipeline.push(retry_policy);
 
+        pipeline.push(Arc::new(RequestTracingPolicy::new()));
+
+        pipeline.push(options.retry.to_try_timeout_policy());
+
+        pipeline.push(Arc::new(LoggingPolicy::new(options.logging.clone())));
+
         pipeline.extend_from_slice(&per_retry_policies);
         pipeline.extend_from_slice(&options.per_retry_policies);
 
@@ -27,11 +27,7 @@ impl Policy for CustomHeadersPolicy {
             custom_headers
                 .iter()
                 .for_each(|(header_name, header_value)| {
-                    log::trace!(
-                        "injecting custom context header {:?} with value {:?}",
-                        header_name,
-                        header_value
-                    );
+                    log::trace!("injecting custom context header {:?}", header_name);
                     request.insert_header(header_name.clone(), header_value.clone());
                 });
         }
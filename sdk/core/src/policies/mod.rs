@@ -1,13 +1,24 @@
 mod custom_headers_policy;
+mod distributed_tracing_policy;
+mod logging_policy;
 mod retry_policies;
 mod telemetry_policy;
+mod timeout_policy;
 mod transport;
 
 use crate::{Context, Request, Response};
 pub use custom_headers_policy::{CustomHeaders, CustomHeadersPolicy};
+pub use distributed_tracing_policy::{
+    DistributedTracingPolicy, RequestTracingPolicy, TraceContext,
+};
+pub use logging_policy::LoggingPolicy;
+pub(crate) use logging_policy::{
+    redact_url, DEFAULT_ALLOWED_HEADER_NAMES, DEFAULT_ALLOWED_QUERY_PARAMETERS,
+};
 pub use retry_policies::*;
 use std::sync::Arc;
 pub use telemetry_policy::*;
+pub use timeout_policy::{DeadlinePolicy, TryTimeoutPolicy};
 pub use transport::*;
 
 /// A specialized `Result` type for policies.
@@ -1,30 +1,56 @@
+use super::RetryClassifier;
 use chrono::{DateTime, Local};
+use rand::Rng;
+use std::sync::Arc;
 use std::time::Duration;
 
 /// Retry policy with exponential back-off.
 ///
-/// Retry policy with exponential back-off (with an added random delay up to 256 ms). Each retry
-/// will happen at least after an exponential wait time. So if x is the first retry wait, the
-/// second will be x*2, the third x*4 and so on. The policy will retry until the maximum number of
-/// retries have been reached or the maximum allowed delay has passed (whichever comes first). The
-/// wait time is not precise.
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Retry policy with exponential back-off and jitter. Each retry will happen after an exponential
+/// wait time, randomly spread by up to 20% in either direction so that clients throttled at the
+/// same time do not retry in lockstep. So if x is the first retry wait, the second will be about
+/// x*2, the third about x*4 and so on, never exceeding the maximum retry delay. The policy will
+/// retry until the maximum number of retries have been reached or the maximum allowed delay has
+/// passed since the first failure (whichever comes first). The wait time is not precise.
+#[derive(Debug, Clone)]
 pub struct ExponentialRetryPolicy {
     delay: Duration,
     max_retries: u32,
     max_delay: Duration,
+    max_retry_delay: Duration,
+    classifier: Arc<dyn RetryClassifier>,
 }
 
 impl ExponentialRetryPolicy {
-    pub(crate) fn new(delay: Duration, max_retries: u32, max_delay: Duration) -> Self {
+    pub(crate) fn new(
+        delay: Duration,
+        max_retries: u32,
+        max_delay: Duration,
+        max_retry_delay: Duration,
+        classifier: Arc<dyn RetryClassifier>,
+    ) -> Self {
         ExponentialRetryPolicy {
             delay,
             max_retries,
             max_delay,
+            max_retry_delay,
+            classifier,
         }
     }
 }
 
+impl PartialEq for ExponentialRetryPolicy {
+    fn eq(&self, other: &Self) -> bool {
+        self.delay == other.delay
+            && self.max_retries == other.max_retries
+            && self.max_delay == other.max_delay
+            && self.max_retry_delay == other.max_retry_delay
+            && Arc::ptr_eq(&self.classifier, &other.classifier)
+    }
+}
+
+impl Eq for ExponentialRetryPolicy {}
+
 impl super::RetryPolicy for ExponentialRetryPolicy {
     fn is_expired(&self, first_retry_time: &mut Option<DateTime<Local>>, retry_count: u32) -> bool {
         if retry_count > self.max_retries {
@@ -39,8 +65,86 @@ impl super::RetryPolicy for ExponentialRetryPolicy {
     }
 
     fn sleep_duration(&self, retry_count: u32) -> Duration {
-        let sleep_ms = self.delay.as_millis() as u64 * u64::pow(2u64, retry_count - 1)
-            + rand::random::<u8>() as u64;
-        Duration::from_millis(sleep_ms)
+        let exponent = retry_count.saturating_sub(1).min(31);
+        let sleep_ms = (self.delay.as_millis() as u64).saturating_mul(1 << exponent);
+        let jitter = rand::thread_rng().gen_range(0.8..1.2);
+        Duration::from_millis((sleep_ms as f64 * jitter) as u64).min(self.max_retry_delay)
+    }
+
+    fn max_retry_delay(&self) -> Duration {
+        self.max_retry_delay
+    }
+
+    fn classifier(&self) -> &dyn RetryClassifier {
+        self.classifier.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policies::retry_policies::{DefaultRetryClassifier, RetryPolicy};
+
+    #[test]
+    fn sleep_duration_grows_with_jitter() {
+        let policy = ExponentialRetryPolicy::new(
+            Duration::from_millis(1000),
+            10,
+            Duration::from_secs(60),
+            Duration::from_secs(30),
+            Arc::new(DefaultRetryClassifier),
+        );
+        for (retry_count, expected_ms) in [(1, 1000), (2, 2000), (3, 4000)] {
+            let sleep_ms = policy.sleep_duration(retry_count).as_millis() as u64;
+            assert!(sleep_ms >= expected_ms * 8 / 10, "{} too short", sleep_ms);
+            assert!(sleep_ms <= expected_ms * 12 / 10, "{} too long", sleep_ms);
+        }
+    }
+
+    #[test]
+    fn sleep_duration_is_capped() {
+        let policy = ExponentialRetryPolicy::new(
+            Duration::from_millis(800),
+            100,
+            Duration::from_secs(600),
+            Duration::from_secs(10),
+            Arc::new(DefaultRetryClassifier),
+        );
+        assert_eq!(policy.sleep_duration(64), Duration::from_secs(10));
+        assert_eq!(policy.max_retry_delay(), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn max_delay_bounds_the_total_retry_time() {
+        let policy = ExponentialRetryPolicy::new(
+            Duration::from_millis(800),
+            100,
+            Duration::from_secs(60),
+            Duration::from_secs(10),
+            Arc::new(DefaultRetryClassifier),
+        );
+        let mut first_retry_time = None;
+        assert!(!policy.is_expired(&mut first_retry_time, 1));
+        // the total budget is not the wait before a single retry
+        let mut first_retry_time = Some(Local::now() - chrono::Duration::seconds(30));
+        assert!(!policy.is_expired(&mut first_retry_time, 2));
+        let mut first_retry_time = Some(Local::now() - chrono::Duration::seconds(61));
+        assert!(policy.is_expired(&mut first_retry_time, 2));
+    }
+
+    #[test]
+    fn equality_compares_the_classifier_instance() {
+        let classifier: Arc<dyn RetryClassifier> = Arc::new(DefaultRetryClassifier);
+        let policy = |classifier: Arc<dyn RetryClassifier>| {
+            ExponentialRetryPolicy::new(
+                Duration::from_millis(800),
+                3,
+                Duration::from_secs(60),
+                Duration::from_secs(10),
+                classifier,
+            )
+        };
+        assert_eq!(policy(classifier.clone()), policy(classifier.clone()));
+        assert_ne!(policy(classifier), policy(Arc::new(DefaultRetryClassifier)));
     }
 }
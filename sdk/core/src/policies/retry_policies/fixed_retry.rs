@@ -1,4 +1,6 @@
+use super::RetryClassifier;
 use chrono::{DateTime, Local};
+use std::sync::Arc;
 use std::time::Duration;
 
 /// Retry policy with fixed back-off.
@@ -7,23 +9,45 @@ use std::time::Duration;
 /// happen at least after the same, configured sleep time. The policy will retry until the maximum number of
 /// retries have been reached or the maximum allowed delay has passed (whichever comes first). The
 /// wait time is not precise.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct FixedRetryPolicy {
     delay: Duration,
     max_retries: u32,
     max_delay: Duration,
+    max_retry_delay: Duration,
+    classifier: Arc<dyn RetryClassifier>,
 }
 
 impl FixedRetryPolicy {
-    pub(crate) fn new(delay: Duration, max_retries: u32, max_delay: Duration) -> Self {
+    pub(crate) fn new(
+        delay: Duration,
+        max_retries: u32,
+        max_delay: Duration,
+        max_retry_delay: Duration,
+        classifier: Arc<dyn RetryClassifier>,
+    ) -> Self {
         Self {
             delay,
             max_retries,
             max_delay,
+            max_retry_delay,
+            classifier,
         }
     }
 }
 
+impl PartialEq for FixedRetryPolicy {
+    fn eq(&self, other: &Self) -> bool {
+        self.delay == other.delay
+            && self.max_retries == other.max_retries
+            && self.max_delay == other.max_delay
+            && self.max_retry_delay == other.max_retry_delay
+            && Arc::ptr_eq(&self.classifier, &other.classifier)
+    }
+}
+
+impl Eq for FixedRetryPolicy {}
+
 impl super::RetryPolicy for FixedRetryPolicy {
     fn is_expired(&self, first_retry_time: &mut Option<DateTime<Local>>, retry_count: u32) -> bool {
         if retry_count > self.max_retries {
@@ -39,6 +63,14 @@ impl super::RetryPolicy for FixedRetryPolicy {
 
     fn sleep_duration(&self, _retry_count: u32) -> Duration {
         let sleep_ms = self.delay.as_millis() as u64 + rand::random::<u8>() as u64;
-        Duration::from_millis(sleep_ms)
+        Duration::from_millis(sleep_ms).min(self.max_retry_delay)
+    }
+
+    fn max_retry_delay(&self) -> Duration {
+        self.max_retry_delay
+    }
+
+    fn classifier(&self) -> &dyn RetryClassifier {
+        self.classifier.as_ref()
     }
 }
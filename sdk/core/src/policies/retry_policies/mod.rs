@@ -1,9 +1,12 @@
 mod exponential_retry;
 mod fixed_retry;
 mod no_retry;
+mod retry_classifier;
 mod retry_policy;
 
 pub use exponential_retry::*;
 pub use fixed_retry::*;
 pub use no_retry::*;
+pub use retry_classifier::*;
+pub use retry_policy::get_retry_after;
 use retry_policy::RetryPolicy;
@@ -0,0 +1,103 @@
+use crate::error::Error;
+use crate::headers::Headers;
+use crate::{Request, StatusCode};
+
+/// The status codes where a retry should be attempted by the [`DefaultRetryClassifier`].
+///
+/// On all other 4xx and 5xx status codes no retry is attempted.
+const RETRY_STATUSES: &[StatusCode] = &[
+    StatusCode::RequestTimeout,
+    StatusCode::TooManyRequests,
+    StatusCode::InternalServerError,
+    StatusCode::BadGateway,
+    StatusCode::ServiceUnavailable,
+    StatusCode::GatewayTimeout,
+];
+
+/// Why an attempt failed.
+#[derive(Debug)]
+pub enum RetryCause<'a> {
+    /// The service answered with an unsuccessful status code.
+    Response {
+        status: StatusCode,
+        headers: &'a Headers,
+        /// The error code sent in the `x-ms-error-code` header or in the response body, if any.
+        error_code: Option<&'a str>,
+    },
+    /// No response was received, for example because the connection was reset.
+    Error(&'a Error),
+}
+
+/// Decides whether a failed attempt should be retried.
+///
+/// The retry policy asks the classifier after every failed attempt, before checking whether
+/// the retry budget is exhausted.
+///
+/// # Examples
+///
+/// A classifier that never retries a `POST` request once it may have reached the service:
+///
+/// ```
+/// use azure_core::{DefaultRetryClassifier, Method, Request, RetryCause, RetryClassifier, RetryOptions};
+/// use std::sync::Arc;
+///
+/// #[derive(Debug)]
+/// struct IdempotentOnly;
+///
+/// impl RetryClassifier for IdempotentOnly {
+///     fn should_retry(&self, request: &Request, cause: &RetryCause<'_>) -> bool {
+///         request.method() != &Method::Post && DefaultRetryClassifier.should_retry(request, cause)
+///     }
+/// }
+///
+/// let classifier: Arc<dyn RetryClassifier> = Arc::new(IdempotentOnly);
+/// let options = RetryOptions::default().classifier(classifier);
+/// # let _ = options;
+/// ```
+pub trait RetryClassifier: Send + Sync + std::fmt::Debug {
+    /// Returns `true` if the request should be sent again.
+    fn should_retry(&self, request: &Request, cause: &RetryCause<'_>) -> bool;
+}
+
+/// The classifier used unless specified otherwise.
+///
+/// It retries on request timeouts, throttling and server errors, as well as on any error
+/// preventing a response from being received.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultRetryClassifier;
+
+impl RetryClassifier for DefaultRetryClassifier {
+    fn should_retry(&self, _request: &Request, cause: &RetryCause<'_>) -> bool {
+        match cause {
+            RetryCause::Response { status, .. } => RETRY_STATUSES.contains(status),
+            RetryCause::Error(_) => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+    use crate::{Method, Url};
+
+    #[test]
+    fn default_classifier() {
+        let request = Request::new(Url::parse("https://example.com").unwrap(), Method::Get);
+        let headers = Headers::new();
+        let response = |status| RetryCause::Response {
+            status,
+            headers: &headers,
+            error_code: None,
+        };
+
+        let classifier = DefaultRetryClassifier;
+        assert!(classifier.should_retry(&request, &response(StatusCode::TooManyRequests)));
+        assert!(classifier.should_retry(&request, &response(StatusCode::ServiceUnavailable)));
+        assert!(!classifier.should_retry(&request, &response(StatusCode::NotFound)));
+        assert!(!classifier.should_retry(&request, &response(StatusCode::Conflict)));
+
+        let error = Error::from(ErrorKind::Io);
+        assert!(classifier.should_retry(&request, &RetryCause::Error(&error)));
+    }
+}
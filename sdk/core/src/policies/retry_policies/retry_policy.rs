@@ -1,8 +1,10 @@
+use super::{RetryCause, RetryClassifier};
 use crate::error::{Error, ErrorKind, HttpError};
+use crate::headers::{Headers, MS_RETRY_AFTER_MS, RETRY_AFTER, RETRY_AFTER_MS};
 use crate::policies::{Policy, PolicyResult, Request};
-use crate::sleep::sleep;
-use crate::{Context, StatusCode};
-use chrono::{DateTime, Local};
+use crate::sleep::sleep_with_context;
+use crate::Context;
+use chrono::{DateTime, Local, Utc};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -18,19 +20,40 @@ pub trait RetryPolicy {
     fn is_expired(&self, first_retry_time: &mut Option<DateTime<Local>>, retry_count: u32) -> bool;
     /// Determine how long before the next retry should be attempted.
     fn sleep_duration(&self, retry_count: u32) -> Duration;
+    /// The maximum delay before a single retry.
+    ///
+    /// A longer delay requested by the service is capped to this value.
+    fn max_retry_delay(&self) -> Duration;
+    /// The classifier deciding which failed attempts can be retried.
+    fn classifier(&self) -> &dyn RetryClassifier;
 }
 
-/// The status codes where a retry should be attempted.
+/// Get the delay the service asked to wait for before retrying, if any.
 ///
-/// On all other 4xx and 5xx status codes no retry is attempted.
-const RETRY_STATUSES: &[StatusCode] = &[
-    StatusCode::RequestTimeout,
-    StatusCode::TooManyRequests,
-    StatusCode::InternalServerError,
-    StatusCode::BadGateway,
-    StatusCode::ServiceUnavailable,
-    StatusCode::GatewayTimeout,
-];
+/// The `x-ms-retry-after-ms` and `retry-after-ms` headers (in milliseconds) take precedence over
+/// the standard `Retry-After` header, which holds either a number of seconds or an HTTP date.
+pub fn get_retry_after(headers: &Headers) -> Option<Duration> {
+    [MS_RETRY_AFTER_MS, RETRY_AFTER_MS]
+        .iter()
+        .find_map(|name| headers.get_optional_str(name))
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_millis)
+        .or_else(|| {
+            let value = headers.get_optional_str(&RETRY_AFTER)?.trim();
+            match value.parse::<u64>() {
+                Ok(seconds) => Some(Duration::from_secs(seconds)),
+                Err(_) => {
+                    let date = DateTime::parse_from_rfc2822(value).ok()?;
+                    // a date in the past means we can retry right away
+                    Some(
+                        (date.with_timezone(&Utc) - Utc::now())
+                            .to_std()
+                            .unwrap_or_default(),
+                    )
+                }
+            }
+        })
+}
 
 #[async_trait::async_trait]
 impl<T> Policy for T
@@ -47,38 +70,38 @@ where
         let mut retry_count = 0;
 
         loop {
+            if let Some(token) = ctx.cancellation_token() {
+                token.check()?;
+            }
+
+            let mut retry_after = None;
             let error = match next[0].send(ctx, request, &next[1..]).await {
                 Ok(response) if (200..400).contains(&u16::from(response.status())) => {
-                    log::trace!(
-                        "Succesful response. Request={:?} response={:?}",
-                        request,
-                        response
-                    );
+                    log::trace!("successful response: {}", response.status());
                     // Successful status code
                     return Ok(response);
                 }
                 Ok(response) => {
                     // Error status code
-                    let code = response.status() as u16;
+                    let status = response.status();
+                    let headers = response.headers().clone();
+                    retry_after = get_retry_after(&headers);
 
                     let http_error = HttpError::new(response).await;
-                    // status code should already be parsed as valid from the underlying HTTP
-                    // implementations.
-                    let status = StatusCode::try_from(code).map_err(|_| {
-                        Error::with_message(ErrorKind::DataConversion, || {
-                            format!("invalid status code '{code}'")
-                        })
-                    })?;
+                    let error_code = http_error.error_code().map(|s| s.to_owned());
+                    let cause = RetryCause::Response {
+                        status,
+                        headers: &headers,
+                        error_code: error_code.as_deref(),
+                    };
+                    let should_retry = self.classifier().should_retry(request, &cause);
                     let error = Error::full(
-                        ErrorKind::http_response(
-                            code,
-                            http_error.error_code().map(|s| s.to_owned()),
-                        ),
+                        ErrorKind::http_response(status as u16, error_code),
                         http_error,
                         "server returned error status which will not be retried",
                     );
 
-                    if !RETRY_STATUSES.contains(&status) {
+                    if !should_retry {
                         log::error!(
                             "server returned error status which will not be retried: {}",
                             status
@@ -93,6 +116,16 @@ where
                     error
                 }
                 Err(error) => {
+                    if !self
+                        .classifier()
+                        .should_retry(request, &RetryCause::Error(&error))
+                    {
+                        log::error!(
+                            "error occurred when making request which will not be retried: {}",
+                            error
+                        );
+                        return Err(error);
+                    }
                     log::debug!(
                         "error occurred when making request which will be retried: {}",
                         error
@@ -106,7 +139,163 @@ where
             }
             retry_count += 1;
 
-            sleep(self.sleep_duration(retry_count)).await;
+            let sleep_duration = match retry_after {
+                Some(retry_after) => {
+                    log::debug!("server asked to retry after {:?}", retry_after);
+                    retry_after.min(self.max_retry_delay())
+                }
+                None => self.sleep_duration(retry_count),
+            };
+            sleep_with_context(sleep_duration, ctx).await?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policies::{DefaultRetryClassifier, FixedRetryPolicy};
+    use crate::{Method, Url};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, Default)]
+    struct FailingTransport {
+        attempts: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl Policy for FailingTransport {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            _request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            Err(Error::message(ErrorKind::Io, "connection reset"))
+        }
+    }
+
+    #[derive(Debug)]
+    struct IdempotentOnly;
+
+    impl RetryClassifier for IdempotentOnly {
+        fn should_retry(&self, request: &Request, cause: &RetryCause<'_>) -> bool {
+            request.method() != &Method::Post && DefaultRetryClassifier.should_retry(request, cause)
+        }
+    }
+
+    fn attempts(method: Method) -> usize {
+        let policy = FixedRetryPolicy::new(
+            Duration::ZERO,
+            2,
+            Duration::from_secs(60),
+            Duration::from_secs(30),
+            Arc::new(IdempotentOnly),
+        );
+        let transport = Arc::new(FailingTransport::default());
+        let next: Vec<Arc<dyn Policy>> = vec![transport.clone()];
+        let mut request = Request::new(Url::parse("https://example.com").unwrap(), method);
+
+        let result = futures::executor::block_on(Policy::send(
+            &policy,
+            &Context::new(),
+            &mut request,
+            &next,
+        ));
+        assert_eq!(result.unwrap_err().kind(), &ErrorKind::Io);
+        transport.attempts.load(Ordering::SeqCst)
+    }
+
+    #[test]
+    fn classifier_decides_retries() {
+        assert!(attempts(Method::Get) > 1);
+        assert_eq!(attempts(Method::Post), 1);
+    }
+
+    #[derive(Debug, Default)]
+    struct ThrottlingTransport {
+        attempts: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl Policy for ThrottlingTransport {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            _request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            let mut headers = Headers::new();
+            headers.insert(RETRY_AFTER, "3600");
+            Ok(crate::Response::new(
+                crate::StatusCode::TooManyRequests,
+                headers,
+                Box::pin(crate::bytes_stream::BytesStream::new_empty()),
+            ))
         }
     }
+
+    #[test]
+    fn retry_after_is_capped_by_max_retry_delay() {
+        // the total budget is larger than the wait the service asks for, but a single wait is not
+        let policy = FixedRetryPolicy::new(
+            Duration::ZERO,
+            2,
+            Duration::from_secs(7200),
+            Duration::from_millis(10),
+            Arc::new(DefaultRetryClassifier),
+        );
+        let transport = Arc::new(ThrottlingTransport::default());
+        let next: Vec<Arc<dyn Policy>> = vec![transport.clone()];
+        let mut request = Request::new(Url::parse("https://example.com").unwrap(), Method::Get);
+
+        let start = std::time::Instant::now();
+        let result = futures::executor::block_on(Policy::send(
+            &policy,
+            &Context::new(),
+            &mut request,
+            &next,
+        ));
+        assert!(matches!(
+            result.unwrap_err().kind(),
+            ErrorKind::HttpResponse { status: 429, .. }
+        ));
+        assert!(transport.attempts.load(Ordering::SeqCst) > 1);
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn retry_after_ms_takes_precedence() {
+        let mut headers = Headers::new();
+        headers.insert(RETRY_AFTER, "10");
+        assert_eq!(get_retry_after(&headers), Some(Duration::from_secs(10)));
+
+        headers.insert(RETRY_AFTER_MS, "1500");
+        assert_eq!(get_retry_after(&headers), Some(Duration::from_millis(1500)));
+
+        headers.insert(MS_RETRY_AFTER_MS, "250");
+        assert_eq!(get_retry_after(&headers), Some(Duration::from_millis(250)));
+    }
+
+    #[test]
+    fn retry_after_http_date() {
+        let mut headers = Headers::new();
+        headers.insert(RETRY_AFTER, "Wed, 21 Oct 2015 07:28:00 GMT");
+        assert_eq!(get_retry_after(&headers), Some(Duration::ZERO));
+
+        let date = (Utc::now() + chrono::Duration::seconds(120)).to_rfc2822();
+        headers.insert(RETRY_AFTER, date);
+        let retry_after = get_retry_after(&headers).unwrap();
+        assert!(retry_after > Duration::from_secs(100) && retry_after <= Duration::from_secs(120));
+    }
+
+    #[test]
+    fn retry_after_missing_or_invalid() {
+        let mut headers = Headers::new();
+        assert_eq!(get_retry_after(&headers), None);
+        headers.insert(RETRY_AFTER, "soon");
+        assert_eq!(get_retry_after(&headers), None);
+    }
 }
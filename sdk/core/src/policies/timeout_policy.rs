@@ -0,0 +1,160 @@
+use crate::error::{Error, ErrorKind};
+use crate::policies::{Policy, PolicyResult};
+use crate::sleep::sleep;
+use crate::{Context, Request};
+use futures::future::{select, Either};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Fails an operation with [`ErrorKind::Timeout`] once the deadline set on its [`Context`] passes.
+///
+/// This policy runs once per call, before the retry policy, so the deadline covers all the
+/// attempts and the delays between them.
+#[derive(Debug, Clone, Default)]
+pub struct DeadlinePolicy {}
+
+impl DeadlinePolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl Policy for DeadlinePolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let deadline = match ctx.deadline() {
+            Some(deadline) => deadline,
+            None => return next[0].send(ctx, request, &next[1..]).await,
+        };
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(Error::message(
+                ErrorKind::Timeout,
+                "the operation deadline passed before the request was sent",
+            ));
+        }
+
+        timeout(remaining, next[0].send(ctx, request, &next[1..]))
+            .await
+            .unwrap_or_else(|| {
+                Err(Error::with_message(ErrorKind::Timeout, || {
+                    format!("the operation did not complete in {:?}", remaining)
+                }))
+            })
+    }
+}
+
+/// Fails an attempt with [`ErrorKind::Timeout`] if it takes longer than the configured duration.
+///
+/// This policy runs after the retry policy, so a timed out attempt can be retried.
+#[derive(Debug, Clone, Default)]
+pub struct TryTimeoutPolicy {
+    try_timeout: Option<Duration>,
+}
+
+impl TryTimeoutPolicy {
+    pub fn new(try_timeout: Option<Duration>) -> Self {
+        Self { try_timeout }
+    }
+}
+
+#[async_trait::async_trait]
+impl Policy for TryTimeoutPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let try_timeout = match self.try_timeout {
+            Some(try_timeout) => try_timeout,
+            None => return next[0].send(ctx, request, &next[1..]).await,
+        };
+
+        timeout(try_timeout, next[0].send(ctx, request, &next[1..]))
+            .await
+            .unwrap_or_else(|| {
+                Err(Error::with_message(ErrorKind::Timeout, || {
+                    format!("the attempt did not complete in {:?}", try_timeout)
+                }))
+            })
+    }
+}
+
+/// Awaits `future` for at most `duration`, returning `None` if it did not complete in time.
+async fn timeout<F>(duration: Duration, future: F) -> Option<F::Output>
+where
+    F: std::future::Future + Unpin,
+{
+    match select(future, sleep(duration)).await {
+        Either::Left((output, _)) => Some(output),
+        Either::Right(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::headers::Headers;
+    use crate::{Method, Response, StatusCode, Url};
+
+    /// A transport answering after a delay.
+    #[derive(Debug)]
+    struct SlowTransport(Duration);
+
+    #[async_trait::async_trait]
+    impl Policy for SlowTransport {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            _request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            sleep(self.0).await;
+            Ok(Response::new(
+                StatusCode::Ok,
+                Headers::new(),
+                Box::pin(futures::stream::empty()),
+            ))
+        }
+    }
+
+    fn send(policy: &dyn Policy, ctx: &Context, transport_delay: Duration) -> PolicyResult {
+        let next: Vec<Arc<dyn Policy>> = vec![Arc::new(SlowTransport(transport_delay))];
+        let mut request = Request::new(Url::parse("https://example.com").unwrap(), Method::Get);
+        futures::executor::block_on(policy.send(ctx, &mut request, &next))
+    }
+
+    #[test]
+    fn try_timeout() {
+        let policy = TryTimeoutPolicy::new(Some(Duration::from_millis(50)));
+        let error = send(&policy, &Context::new(), Duration::from_secs(2)).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::Timeout);
+
+        assert!(send(&policy, &Context::new(), Duration::from_millis(1)).is_ok());
+
+        let policy = TryTimeoutPolicy::new(None);
+        assert!(send(&policy, &Context::new(), Duration::from_millis(100)).is_ok());
+    }
+
+    #[test]
+    fn deadline() {
+        let policy = DeadlinePolicy::new();
+        assert!(send(&policy, &Context::new(), Duration::from_millis(100)).is_ok());
+
+        let mut ctx = Context::new();
+        ctx.set_timeout(Duration::from_millis(50));
+        let error = send(&policy, &ctx, Duration::from_secs(2)).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::Timeout);
+
+        let mut ctx = Context::new();
+        ctx.set_deadline(Instant::now() - Duration::from_millis(1));
+        let error = send(&policy, &ctx, Duration::from_millis(1)).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::Timeout);
+    }
+}
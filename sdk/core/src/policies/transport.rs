@@ -25,7 +25,7 @@ impl TransportPolicy {
 impl Policy for TransportPolicy {
     async fn send(
         &self,
-        _ctx: &Context,
+        ctx: &Context,
         request: &mut Request,
         next: &[Arc<dyn Policy>],
     ) -> PolicyResult {
@@ -34,6 +34,6 @@ impl Policy for TransportPolicy {
 
         let response = { self.transport_options.http_client.execute_request(request) };
 
-        response.await
+        crate::cancellation::cancellable(ctx.cancellation_token(), response).await
     }
 }
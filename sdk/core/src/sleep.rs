@@ -1,36 +1,225 @@
 use futures::Future;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
 use std::pin::Pin;
-use std::task::{Context, Poll};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::task::{Context, Poll, Waker};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+/// Sleeps for `duration` unless the cancellation token of `ctx` is cancelled first, in which
+/// case an error of kind [`ErrorKind::Cancelled`](crate::error::ErrorKind::Cancelled) is returned.
+pub async fn sleep_with_context(duration: Duration, ctx: &crate::Context) -> crate::Result<()> {
+    crate::cancellation::cancellable(ctx.cancellation_token(), async {
+        sleep(duration).await;
+        Ok(())
+    })
+    .await
+}
+
+/// Completes once `duration` has elapsed since the future was first polled.
+///
+/// All the sleeps are woken up by a single background thread, and a sleep dropped before it
+/// completes, such as a timeout raced against a response, is unregistered right away.
 pub fn sleep(duration: Duration) -> Sleep {
-    Sleep {
-        thread: None,
-        duration,
-    }
+    Sleep::new(Timer::global().clone(), duration)
 }
 
 #[derive(Debug)]
 pub struct Sleep {
-    thread: Option<thread::JoinHandle<()>>,
+    timer: Arc<Timer>,
     duration: Duration,
+    deadline: Option<Instant>,
+    key: Option<u64>,
+}
+
+impl Sleep {
+    fn new(timer: Arc<Timer>, duration: Duration) -> Self {
+        Self {
+            timer,
+            duration,
+            deadline: None,
+            key: None,
+        }
+    }
 }
 
 impl Future for Sleep {
     type Output = ();
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        if self.thread.is_none() {
-            let waker = cx.waker().clone();
-            let duration = self.duration;
-            self.get_mut().thread = Some(thread::spawn(move || {
-                thread::sleep(duration);
-                waker.wake();
-            }));
-            Poll::Pending
-        } else {
-            Poll::Ready(())
+        let this = self.get_mut();
+        let now = Instant::now();
+        let duration = this.duration;
+        let deadline = *this.deadline.get_or_insert_with(|| {
+            now.checked_add(duration)
+                .unwrap_or_else(|| now + Duration::from_secs(u32::MAX.into()))
+        });
+
+        if now >= deadline {
+            if let Some(key) = this.key.take() {
+                this.timer.cancel(key);
+            }
+            return Poll::Ready(());
+        }
+
+        // the future may be polled again before the deadline, e.g. when it is raced against
+        // another future, so the latest waker must be the one woken
+        match this.key {
+            Some(key) => this.timer.update(key, cx.waker()),
+            None => this.key = Some(this.timer.register(deadline, cx.waker().clone())),
         }
+        Poll::Pending
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.timer.cancel(key);
+        }
+    }
+}
+
+/// Wakes the registered sleeps from a single thread once their deadline passes.
+#[derive(Debug, Default)]
+struct Timer {
+    state: Mutex<TimerState>,
+    condvar: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct TimerState {
+    next_key: u64,
+    /// The wakers of the pending sleeps.
+    wakers: HashMap<u64, Waker>,
+    /// The deadlines of the sleeps, the cancelled ones being removed lazily.
+    deadlines: BinaryHeap<Reverse<(Instant, u64)>>,
+    thread_started: bool,
+}
+
+impl Timer {
+    fn global() -> &'static Arc<Timer> {
+        static TIMER: OnceLock<Arc<Timer>> = OnceLock::new();
+        TIMER.get_or_init(Default::default)
+    }
+
+    fn register(self: &Arc<Self>, deadline: Instant, waker: Waker) -> u64 {
+        let mut state = self.state.lock().unwrap();
+        let key = state.next_key;
+        state.next_key += 1;
+        state.wakers.insert(key, waker);
+        state.deadlines.push(Reverse((deadline, key)));
+
+        if !state.thread_started {
+            state.thread_started = true;
+            let timer = self.clone();
+            thread::Builder::new()
+                .name("azure_core-timer".to_owned())
+                .spawn(move || timer.run())
+                .expect("failed to spawn the timer thread");
+        } else if state.deadlines.peek() == Some(&Reverse((deadline, key))) {
+            // the thread waits for a later deadline
+            self.condvar.notify_one();
+        }
+        key
+    }
+
+    fn update(&self, key: u64, waker: &Waker) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(registered) = state.wakers.get_mut(&key) {
+            if !registered.will_wake(waker) {
+                *registered = waker.clone();
+            }
+        }
+    }
+
+    fn cancel(&self, key: u64) {
+        let mut state = self.state.lock().unwrap();
+        state.wakers.remove(&key);
+        // don't let the deadlines of dropped sleeps pile up until they pass
+        if state.deadlines.len() > 2 * state.wakers.len() + 64 {
+            let TimerState {
+                wakers, deadlines, ..
+            } = &mut *state;
+            deadlines.retain(|Reverse((_, key))| wakers.contains_key(key));
+        }
+    }
+
+    fn run(&self) {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            let now = Instant::now();
+            let mut expired = Vec::new();
+            while let Some(Reverse((deadline, key))) = state.deadlines.peek().copied() {
+                if deadline > now {
+                    break;
+                }
+                state.deadlines.pop();
+                if let Some(waker) = state.wakers.remove(&key) {
+                    expired.push(waker);
+                }
+            }
+
+            if !expired.is_empty() {
+                drop(state);
+                expired.into_iter().for_each(Waker::wake);
+                state = self.state.lock().unwrap();
+                continue;
+            }
+
+            state = match state.deadlines.peek() {
+                Some(Reverse((deadline, _))) => {
+                    let timeout = deadline.saturating_duration_since(now);
+                    self.condvar.wait_timeout(state, timeout).unwrap().0
+                }
+                None => self.condvar.wait(state).unwrap(),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::future::{select, Either};
+
+    #[test]
+    fn sleeps_are_woken_in_order() {
+        let timer = Arc::new(Timer::default());
+        let start = Instant::now();
+        let first = Sleep::new(timer.clone(), Duration::from_millis(20));
+        let second = Sleep::new(timer.clone(), Duration::from_millis(200));
+        match futures::executor::block_on(select(second, first)) {
+            Either::Right((_, second)) => futures::executor::block_on(second),
+            Either::Left(_) => panic!("the longer sleep completed first"),
+        }
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert!(timer.state.lock().unwrap().wakers.is_empty());
+    }
+
+    #[test]
+    fn completed_requests_leave_no_timer_behind() {
+        let timer = Arc::new(Timer::default());
+        for _ in 0..1000 {
+            // a response arriving before a long timeout
+            let response = futures::future::ready(());
+            let timeout = Sleep::new(timer.clone(), Duration::from_secs(30));
+            let mut timeout = Box::pin(timeout);
+            // register the timeout before the response completes, like a slower response would
+            assert!(futures::executor::block_on(futures::future::poll_fn(
+                |cx| { Poll::Ready(timeout.as_mut().poll(cx).is_pending()) }
+            )));
+            match futures::executor::block_on(select(response, timeout)) {
+                Either::Left(_) => {}
+                Either::Right(_) => panic!("the timeout completed first"),
+            }
+        }
+
+        let state = timer.state.lock().unwrap();
+        assert!(state.wakers.is_empty());
+        assert!(state.deadlines.len() <= 64);
+        // a single thread serves all the sleeps
+        assert!(state.thread_started);
     }
 }
@@ -1,7 +1,7 @@
+use azure_core::error::ErrorKind;
+use azure_core::{CancellationToken, Context};
 use azure_data_cosmos::prelude::*;
-use stop_token::prelude::*;
-use stop_token::StopSource;
-use tokio::time::{Duration, Instant};
+use tokio::time::Duration;
 
 #[tokio::main]
 async fn main() -> azure_core::Result<()> {
@@ -18,37 +18,46 @@ async fn main() -> azure_core::Result<()> {
     let client = CosmosClient::new(account.clone(), authorization_token.clone(), options);
 
     // Create a new database, and time out if it takes more than 1 second.
-    let future = client.create_database("my_database").into_future();
-    let deadline = Instant::now() + Duration::from_secs(1);
-    match future.timeout_at(deadline).await {
-        Ok(Ok(r)) => println!("successful response: {:?}", r),
-        Ok(Err(e)) => println!("request was made but failed: {:?}", e),
-        Err(_) => println!("request timed out!"),
+    let mut context = Context::new();
+    context.set_timeout(Duration::from_secs(1));
+    let future = client
+        .create_database("my_database")
+        .context(context)
+        .into_future();
+    match future.await {
+        Ok(r) => println!("successful response: {:?}", r),
+        Err(e) if e.kind() == &ErrorKind::Timeout => println!("request timed out!"),
+        Err(e) => println!("request was made but failed: {:?}", e),
     };
 
     // Create multiple new databases, and cancel them if they don't complete before
     // they're sent a stop signal.
-    let source = StopSource::new();
+    let token = CancellationToken::new();
     for _ in 1..10 {
         let client = client.clone();
-        // Clone the stop token for each request.
-        let deadline = source.token();
+        // Every request shares the same token.
+        let mut context = Context::new();
+        context.set_cancellation_token(token.clone());
         tokio::spawn(async move {
-            let future = client.create_database("my_database").into_future();
-            match future.timeout_at(deadline).await {
-                Ok(Ok(r)) => println!("successful response: {:?}", r),
-                Ok(Err(e)) => println!("request was made but failed: {:?}", e),
-                Err(_) => println!("request was cancelled!"),
+            let future = client
+                .create_database("my_database")
+                .context(context)
+                .into_future();
+            match future.await {
+                Ok(r) => println!("successful response: {:?}", r),
+                Err(e) if e.kind() == &ErrorKind::Cancelled => {
+                    println!("request was cancelled!")
+                }
+                Err(e) => println!("request was made but failed: {:?}", e),
             };
         });
     }
 
     tokio::time::sleep(Duration::from_secs(5)).await;
-    // This causes all cancel tokens to fire. Any request tied to a stop token created
-    // from this source will be canceled.
+    // This cancels every request sharing the token. Any request that has not yet completed
+    // stops at its next opportunity, for example while waiting for a response or before retrying.
     println!("cancelling all requests");
-    drop(source);
-    // Any request that has not yet completed will be canceled at this point
+    token.cancel();
 
     // Keep the program alive for a bit longer so the tasks get a chance to
     // print before exiting.
@@ -0,0 +1,57 @@
+use azure_core::{CancellationToken, Context};
+use azure_data_cosmos::change_feed::{ChangeFeedBatch, ChangeFeedStartFrom};
+use azure_data_cosmos::prelude::*;
+use serde_json::Value;
+use tokio::time::Duration;
+
+#[tokio::main]
+async fn main() -> azure_core::Result<()> {
+    env_logger::init();
+    // This example prints the changes made to a collection for a minute. The progress is stored
+    // in a lease collection, which must be partitioned by `/id`. Run it several times at once
+    // to see the partition key ranges being shared between the instances.
+    let account = std::env::var("COSMOS_ACCOUNT").expect("Set env variable COSMOS_ACCOUNT first!");
+    let primary_key =
+        std::env::var("COSMOS_PRIMARY_KEY").expect("Set env variable COSMOS_PRIMARY_KEY first!");
+
+    let database_name = std::env::args()
+        .nth(1)
+        .expect("please specify the database name as first command line parameter");
+    let collection_name = std::env::args()
+        .nth(2)
+        .expect("please specify the collection name as second command line parameter");
+    let lease_collection_name = std::env::args()
+        .nth(3)
+        .expect("please specify the lease collection name as third command line parameter");
+
+    let authorization_token = AuthorizationToken::primary_from_base64(&primary_key)?;
+    let client = CosmosClient::new(account, authorization_token, CosmosOptions::default());
+    let database = client.database_client(database_name);
+
+    let token = CancellationToken::new();
+    let mut context = Context::new();
+    context.set_cancellation_token(token.clone());
+
+    let processor = database
+        .collection_client(collection_name)
+        .change_feed_processor(
+            database.collection_client(lease_collection_name),
+            |batch: ChangeFeedBatch<Value>| async move {
+                for document in batch.documents {
+                    println!("range {} changed: {}", batch.lease_token, document);
+                }
+                Ok(())
+            },
+        )
+        .lease_prefix("example.")
+        .start_from(ChangeFeedStartFrom::Beginning)
+        .context(context)
+        .into_future();
+
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        token.cancel();
+    });
+
+    processor.await
+}
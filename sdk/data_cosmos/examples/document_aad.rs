@@ -0,0 +1,42 @@
+use azure_data_cosmos::prelude::*;
+use azure_identity::DefaultAzureCredential;
+use serde_json::Value;
+use std::sync::Arc;
+
+#[tokio::main]
+async fn main() -> azure_core::Result<()> {
+    // This example authenticates with Azure Active Directory instead of an account key. The
+    // identity must have been assigned a Cosmos DB data plane role, such as
+    // "Cosmos DB Built-in Data Reader".
+    let account = std::env::var("COSMOS_ACCOUNT").expect("Set env variable COSMOS_ACCOUNT first!");
+
+    let database_name = std::env::args()
+        .nth(1)
+        .expect("please specify the database name as first command line parameter");
+    let collection_name = std::env::args()
+        .nth(2)
+        .expect("please specify the collection name as second command line parameter");
+    let document_name = std::env::args()
+        .nth(3)
+        .expect("please specify the document name as third command line parameter");
+    let partition_key = std::env::args()
+        .nth(4)
+        .expect("please specify the partition key as fourth command line parameter");
+
+    let authorization_token =
+        AuthorizationToken::from_token_credential(Arc::new(DefaultAzureCredential::default()));
+
+    let client = CosmosClient::new(account, authorization_token, CosmosOptions::default());
+
+    let document = client
+        .database_client(database_name)
+        .collection_client(collection_name)
+        .document_client(document_name, &partition_key)?
+        .get_document::<Value>()
+        .into_future()
+        .await?;
+
+    println!("document == {:#?}", document);
+
+    Ok(())
+}
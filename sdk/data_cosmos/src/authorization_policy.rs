@@ -2,21 +2,26 @@ use crate::headers::{HEADER_DATE, HEADER_VERSION};
 use crate::resources::permission::AuthorizationToken;
 use crate::resources::ResourceType;
 use crate::TimeNonce;
+use azure_core::auth::{TokenCredential, TokenResponse};
+use azure_core::error::{ErrorKind, ResultExt};
 use azure_core::headers::{HeaderValue, AUTHORIZATION};
 use azure_core::{Context, Policy, PolicyResult, Request};
+use chrono::{Duration, Utc};
+use futures::lock::Mutex;
 use hmac::{Hmac, Mac};
 use sha2::Sha256;
-use std::borrow::Cow;
 use std::sync::Arc;
 use url::form_urlencoded;
 
 const AZURE_VERSION: &str = "2018-12-31";
 const VERSION: &str = "1.0";
+/// How long before their expiration the AAD tokens are refreshed.
+const AAD_TOKEN_REFRESH_MINUTES: i64 = 5;
 
 /// The `AuthorizationPolicy` takes care to authenticate your calls to Azure CosmosDB.
 ///
-/// Currently it supports two type of authorization: one at service level and another at resource level (see
-/// [`AuthorizationToken`] for more info). The policy must be added just before the transport policy
+/// Currently it supports three type of authorization: one at service level, another at resource level and
+/// one with Azure Active Directory tokens (see [`AuthorizationToken`] for more info). The policy must be added just before the transport policy
 /// because it needs to inspect the values that are about to be sent to the transport and inject
 /// the proper authorization token.
 ///
@@ -26,17 +31,44 @@ const VERSION: &str = "1.0";
 ///
 /// This struct implements `Debug` but secrets are encrypted by `AuthorizationToken` so there is no risk of
 /// leaks in debug logs (secrets are stored in cleartext in memory: dumps are still leaky).
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct AuthorizationPolicy {
     authorization_token: AuthorizationToken,
+    /// The account endpoint, which the AAD tokens are requested for.
+    account_endpoint: String,
+    /// The last token of an [`AuthorizationToken::TokenCredential`].
+    aad_token: Arc<Mutex<Option<TokenResponse>>>,
 }
 
 impl AuthorizationPolicy {
-    pub(crate) fn new(authorization_token: AuthorizationToken) -> Self {
+    pub(crate) fn new(authorization_token: AuthorizationToken, account_endpoint: String) -> Self {
         Self {
             authorization_token,
+            account_endpoint,
+            aad_token: Arc::new(Mutex::new(None)),
         }
     }
+
+    /// Returns the cached token of `credential`, requesting a new one when it's about to expire.
+    async fn aad_token(
+        &self,
+        credential: &dyn TokenCredential,
+        resource: &str,
+    ) -> azure_core::Result<TokenResponse> {
+        let mut aad_token = self.aad_token.lock().await;
+        if let Some(token) = aad_token.as_ref() {
+            if token.expires_on > Utc::now() + Duration::minutes(AAD_TOKEN_REFRESH_MINUTES) {
+                return Ok(token.clone());
+            }
+        }
+
+        let token = credential
+            .get_token(resource)
+            .await
+            .context(ErrorKind::Credential, "failed to get the AAD token")?;
+        *aad_token = Some(token.clone());
+        Ok(token)
+    }
 }
 
 #[async_trait::async_trait]
@@ -56,16 +88,26 @@ impl Policy for AuthorizationPolicy {
 
         let time_nonce = TimeNonce::new();
 
-        let auth = {
-            let resource_link = generate_resource_link(request);
-            generate_authorization(
-                &self.authorization_token,
-                request.method(),
-                ctx.get()
-                    .expect("ResourceType must be in the Context at this point"),
-                &resource_link,
-                time_nonce,
-            )
+        let auth = match &self.authorization_token {
+            AuthorizationToken::TokenCredential(credential) => {
+                // the tokens are scoped to the account endpoint, even for the regional endpoints
+                let token = self
+                    .aad_token(credential.as_ref(), &self.account_endpoint)
+                    .await?;
+                format_authorization("aad", token.token.secret())
+            }
+            AuthorizationToken::Primary(key) => {
+                let resource_link = generate_resource_link(request);
+                generate_authorization(
+                    key,
+                    request.method(),
+                    ctx.get()
+                        .expect("ResourceType must be in the Context at this point"),
+                    &resource_link,
+                    time_nonce,
+                )
+            }
+            AuthorizationToken::Resource(key) => format_authorization("resource", key),
         };
 
         trace!(
@@ -135,29 +177,23 @@ fn generate_resource_link(request: &Request) -> String {
     }
 }
 
-/// The CosmosDB authorization can either be "primary" (i.e., one of the two service-level tokens) or
-/// "resource" (i.e., a single database). In the first case the signature must be constructed by
-/// signing the HTTP method, resource type, resource link (the relative URI) and the current time.
-/// In the second case, the signature is just the resource key.
+/// Generates the "master" authorization of a primary key (i.e., one of the two service-level
+/// tokens), whose signature is made by signing the HTTP method, resource type, resource link (the
+/// relative URI) and the current time. The "resource" authorization of a single database doesn't
+/// need a signature, its key is sent as is by the policy, as is the token of an "aad" one.
 fn generate_authorization(
-    auth_token: &AuthorizationToken,
+    key: &[u8],
     http_method: &azure_core::Method,
     resource_type: &ResourceType,
     resource_link: &str,
     time_nonce: TimeNonce,
 ) -> String {
-    let (authorization_type, signature) = match auth_token {
-        AuthorizationToken::Primary(key) => {
-            let string_to_sign =
-                string_to_sign(http_method, resource_type, resource_link, time_nonce);
-            (
-                "master",
-                Cow::Owned(encode_str_to_sign(&string_to_sign, key)),
-            )
-        }
-        AuthorizationToken::Resource(key) => ("resource", Cow::Borrowed(key)),
-    };
+    let string_to_sign = string_to_sign(http_method, resource_type, resource_link, time_nonce);
+    format_authorization("master", &encode_str_to_sign(&string_to_sign, key))
+}
 
+/// Builds the url encoded value of the authorization header.
+fn format_authorization(authorization_type: &str, signature: &str) -> String {
     let str_unencoded = format!(
         "type={}&ver={}&sig={}",
         authorization_type, VERSION, signature
@@ -203,6 +239,7 @@ fn string_to_sign(
             _ => "extension",
         },
         match rt {
+            ResourceType::DatabaseAccount => "",
             ResourceType::Databases => "dbs",
             ResourceType::Collections => "colls",
             ResourceType::Documents => "docs",
@@ -264,13 +301,13 @@ mon, 01 jan 1900 01:00:00 gmt
             chrono::DateTime::parse_from_rfc3339("1900-01-01T01:00:00.000000000+00:00").unwrap();
         let time = time.with_timezone(&chrono::Utc).into();
 
-        let auth_token = AuthorizationToken::primary_from_base64(
+        let key = base64::decode(
             "8F8xXXOptJxkblM1DBXW7a6NMI5oE8NnwPGYBmwxLCKfejOK7B7yhcCHMGvN3PBrlMLIOeol1Hv9RCdzAZR5sg==",
         )
         .unwrap();
 
         let ret = generate_authorization(
-            &auth_token,
+            &key,
             &azure_core::Method::Get,
             &ResourceType::Databases,
             "dbs/MyDatabase/colls/MyCollection",
@@ -288,13 +325,12 @@ mon, 01 jan 1900 01:00:00 gmt
             chrono::DateTime::parse_from_rfc3339("2017-04-27T00:51:12.000000000+00:00").unwrap();
         let time = time.with_timezone(&chrono::Utc).into();
 
-        let auth_token = AuthorizationToken::primary_from_base64(
-            "dsZQi3KtZmCv1ljt3VNWNm7sQUF1y5rJfC6kv5JiwvW0EndXdDku/dkKBp8/ufDToSxL",
-        )
-        .unwrap();
+        let key =
+            base64::decode("dsZQi3KtZmCv1ljt3VNWNm7sQUF1y5rJfC6kv5JiwvW0EndXdDku/dkKBp8/ufDToSxL")
+                .unwrap();
 
         let ret = generate_authorization(
-            &auth_token,
+            &key,
             &azure_core::Method::Get,
             &ResourceType::Databases,
             "dbs/ToDoList",
@@ -347,4 +383,57 @@ mon, 01 jan 1900 01:00:00 gmt
         );
         assert_eq!(&generate_resource_link(&request), "dbs/test_db");
     }
+
+    struct CountingCredential(std::sync::atomic::AtomicUsize);
+
+    #[async_trait::async_trait]
+    impl TokenCredential for CountingCredential {
+        async fn get_token(&self, resource: &str) -> azure_core::Result<TokenResponse> {
+            assert_eq!(resource, "https://account.documents.azure.com");
+            let count = self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(TokenResponse::new(
+                azure_core::auth::AccessToken::new(format!("token{}", count)),
+                // the second token is about to expire, so it's refreshed
+                Utc::now() + Duration::minutes(if count == 1 { 1 } else { 60 }),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn aad_token_is_cached() {
+        let credential = Arc::new(CountingCredential(Default::default()));
+        let resource = "https://account.documents.azure.com";
+        let policy = AuthorizationPolicy::new(
+            AuthorizationToken::TokenCredential(credential.clone()),
+            resource.to_owned(),
+        );
+
+        let token = policy
+            .aad_token(credential.as_ref(), resource)
+            .await
+            .unwrap();
+        assert_eq!(token.token.secret(), "token0");
+        let token = policy
+            .aad_token(credential.as_ref(), resource)
+            .await
+            .unwrap();
+        assert_eq!(token.token.secret(), "token0");
+
+        *policy.aad_token.lock().await = None;
+        let token = policy
+            .aad_token(credential.as_ref(), resource)
+            .await
+            .unwrap();
+        assert_eq!(token.token.secret(), "token1");
+        let token = policy
+            .aad_token(credential.as_ref(), resource)
+            .await
+            .unwrap();
+        assert_eq!(token.token.secret(), "token2");
+
+        assert_eq!(
+            format_authorization("aad", token.token.secret()),
+            "type%3Daad%26ver%3D1.0%26sig%3Dtoken2"
+        );
+    }
 }
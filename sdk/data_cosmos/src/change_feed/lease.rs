@@ -0,0 +1,255 @@
+use crate::headers::HEADER_SUB_STATUS;
+use crate::prelude::*;
+use crate::resources::document::{Param, Query};
+use crate::CosmosEntity;
+use azure_core::error::{ErrorKind, HttpError};
+use azure_core::prelude::*;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use std::time::Duration;
+
+/// The state of the processing of a partition key range, stored in the lease container.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Lease {
+    pub id: String,
+    /// The id of the partition key range.
+    pub lease_token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub continuation_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// When the owner last renewed the lease.
+    pub timestamp: DateTime<Utc>,
+}
+
+impl CosmosEntity for Lease {
+    type Entity = String;
+
+    fn partition_key(&self) -> Self::Entity {
+        self.id.clone()
+    }
+}
+
+impl Lease {
+    pub fn new(prefix: &str, lease_token: String, continuation_token: Option<String>) -> Self {
+        Self {
+            id: format!("{}{}", prefix, lease_token),
+            lease_token,
+            continuation_token,
+            owner: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    pub fn is_expired(&self, expiration: Duration, now: DateTime<Utc>) -> bool {
+        self.owner.is_none()
+            || (now - self.timestamp)
+                .to_std()
+                .is_ok_and(|age| age > expiration)
+    }
+}
+
+/// A lease with the etag of its document, to update it only if nobody else has.
+#[derive(Debug, Clone)]
+pub(crate) struct StoredLease {
+    pub lease: Lease,
+    pub etag: String,
+}
+
+/// Whether `error` is the `status` HTTP response.
+pub(crate) fn is_status(error: &azure_core::error::Error, status: u16) -> bool {
+    matches!(error.kind(), ErrorKind::HttpResponse { status: s, .. } if *s == status)
+}
+
+/// How a partition key range is gone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RangeGone {
+    /// The range has been split into several ranges.
+    Split,
+    /// The range has been merged with others into a single range.
+    Merged,
+}
+
+/// How a partition key range is gone if `error` is the `410 Gone` response telling that it has
+/// been split or merged into other ranges, rather than a transient one.
+pub(crate) fn range_gone(error: &azure_core::error::Error) -> Option<RangeGone> {
+    if !is_status(error, 410) {
+        return None;
+    }
+    range_gone_sub_status(
+        error
+            .downcast_ref::<HttpError>()
+            .and_then(|error| error.headers().get(HEADER_SUB_STATUS.as_str()))
+            .map(String::as_str),
+    )
+}
+
+/// How a partition key range is gone from the sub-status of a `410 Gone` response: 1002 for a
+/// split and 1007 for a merge.
+fn range_gone_sub_status(sub_status: Option<&str>) -> Option<RangeGone> {
+    match sub_status {
+        Some("1002") => Some(RangeGone::Split),
+        Some("1007") => Some(RangeGone::Merged),
+        _ => None,
+    }
+}
+
+/// Reads and updates the leases of a processor in the lease container, which must be
+/// partitioned by `/id`.
+#[derive(Debug, Clone)]
+pub(crate) struct LeaseStore {
+    client: CollectionClient,
+    prefix: String,
+}
+
+impl LeaseStore {
+    pub fn new(client: CollectionClient, prefix: String) -> Self {
+        Self { client, prefix }
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Lists the leases of the processor, leaving the other documents of the lease container
+    /// aside.
+    pub async fn list(&self) -> azure_core::Result<Vec<StoredLease>> {
+        let query = Query::with_params(
+            "SELECT * FROM c WHERE STARTSWITH(c.id, @prefix)".to_owned(),
+            vec![Param::new("@prefix".to_owned(), self.prefix.clone())],
+        );
+        let mut leases = Vec::new();
+        let mut pages = self
+            .client
+            .query_documents(query)
+            .query_cross_partition(true)
+            .into_stream::<Lease>();
+        while let Some(page) = pages.next().await {
+            leases.extend(page?.into_documents()?.results.into_iter().map(|document| {
+                StoredLease {
+                    etag: document.document_attributes.etag().to_owned(),
+                    lease: document.result,
+                }
+            }));
+        }
+        Ok(leases)
+    }
+
+    /// Creates `lease`, returning `None` if another host already created it.
+    pub async fn create(&self, lease: Lease) -> azure_core::Result<Option<StoredLease>> {
+        match self
+            .client
+            .create_document(lease.clone())
+            .into_future()
+            .await
+        {
+            Ok(response) => Ok(Some(StoredLease {
+                etag: response.document_attributes.etag().to_owned(),
+                lease,
+            })),
+            Err(error) if is_status(&error, 409) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Replaces the lease with `lease`, returning `None` if it has been updated by another host
+    /// since it was read.
+    pub async fn replace(
+        &self,
+        lease: Lease,
+        etag: &str,
+    ) -> azure_core::Result<Option<StoredLease>> {
+        let response = self
+            .client
+            .document_client(lease.id.clone(), &lease.id)?
+            .replace_document(lease.clone())
+            .if_match_condition(IfMatchCondition::Match(etag.to_owned()))
+            .into_future()
+            .await;
+        match response {
+            Ok(response) => Ok(Some(StoredLease {
+                etag: response.document_attributes.etag().to_owned(),
+                lease,
+            })),
+            Err(error) if is_status(&error, 412) || is_status(&error, 404) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub async fn delete(&self, lease: &Lease) -> azure_core::Result<()> {
+        match self
+            .client
+            .document_client(lease.id.clone(), &lease.id)?
+            .delete_document()
+            .into_future()
+            .await
+        {
+            Err(error) if !is_status(&error, 404) => Err(error),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lease_document() {
+        let mut lease = Lease::new("processor.", "3".to_owned(), Some("\"42\"".to_owned()));
+        lease.timestamp = DateTime::parse_from_rfc3339("2022-06-21T18:12:01Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            serde_json::to_value(&lease).unwrap(),
+            serde_json::json!({
+                "id": "processor.3",
+                "leaseToken": "3",
+                "continuationToken": "\"42\"",
+                "timestamp": "2022-06-21T18:12:01Z"
+            })
+        );
+        assert!(lease.is_expired(Duration::from_secs(60), lease.timestamp));
+
+        lease.owner = Some("host".to_owned());
+        assert!(!lease.is_expired(
+            Duration::from_secs(60),
+            lease.timestamp + chrono::Duration::seconds(30)
+        ));
+        assert!(lease.is_expired(
+            Duration::from_secs(60),
+            lease.timestamp + chrono::Duration::seconds(90)
+        ));
+
+        let document: Document<Lease> = serde_json::from_str(
+            r#"{
+                "id": "processor.3",
+                "leaseToken": "3",
+                "owner": "host",
+                "timestamp": "2022-06-21T18:12:01Z",
+                "_rid": "rid",
+                "_ts": 1655835121,
+                "_self": "dbs/db/colls/leases/docs/processor.3",
+                "_etag": "\"etag\"",
+                "_attachments": "attachments/"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(document.document_attributes.etag(), "\"etag\"");
+        assert_eq!(document.document.owner.as_deref(), Some("host"));
+        assert_eq!(document.document.continuation_token, None);
+    }
+
+    #[test]
+    fn range_gone_sub_statuses() {
+        assert_eq!(range_gone_sub_status(Some("1002")), Some(RangeGone::Split));
+        assert_eq!(range_gone_sub_status(Some("1007")), Some(RangeGone::Merged));
+        assert_eq!(range_gone_sub_status(Some("1000")), None);
+        assert_eq!(range_gone_sub_status(None), None);
+
+        let error = azure_core::error::Error::from(ErrorKind::http_response(410, None));
+        assert!(is_status(&error, 410));
+        assert_eq!(range_gone(&error), None);
+    }
+}
@@ -0,0 +1,78 @@
+//! Processing the change feed of a collection with several hosts.
+//!
+//! The [`ChangeFeedProcessorBuilder`] reads the change feed of every partition key range of a
+//! collection and hands the changed documents to a [`ChangeFeedHandler`] in batches. Where each
+//! range has been read up to is stored in a lease container, which must be partitioned by
+//! `/id`, so that the processing restarts where it stopped and the ranges can be shared between
+//! several hosts running the same processor.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use azure_data_cosmos::change_feed::ChangeFeedBatch;
+//! use azure_data_cosmos::prelude::*;
+//!
+//! # async fn run(collection: CollectionClient, leases: CollectionClient) -> azure_core::Result<()> {
+//! collection
+//!     .change_feed_processor(leases, |batch: ChangeFeedBatch<serde_json::Value>| async move {
+//!         println!("{} changes in range {}", batch.documents.len(), batch.lease_token);
+//!         Ok(())
+//!     })
+//!     .host_name("host-1")
+//!     .into_future()
+//!     .await
+//! # }
+//! ```
+
+mod lease;
+mod processor;
+mod reader;
+
+pub use processor::{ChangeFeedProcessor, ChangeFeedProcessorBuilder};
+
+use chrono::{DateTime, Utc};
+use futures::Future;
+
+/// Where to start reading the partition key ranges which have never been read.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ChangeFeedStartFrom {
+    /// Read all the changes since the collection was created.
+    Beginning,
+    /// Only read the changes made from now on.
+    #[default]
+    Now,
+    /// Read the changes made after the given time.
+    Time(DateTime<Utc>),
+}
+
+/// The documents changed in a partition key range.
+#[derive(Debug, Clone)]
+pub struct ChangeFeedBatch<T> {
+    /// The id of the partition key range the documents have been read from.
+    pub lease_token: String,
+    /// The latest version of the changed documents, in the order they have been changed.
+    pub documents: Vec<T>,
+}
+
+/// Handles the documents read from the change feed.
+///
+/// A batch is only checkpointed once the handler returns successfully; if it fails, the same
+/// changes are read again, so the handling should be idempotent. Any `async` closure taking a
+/// [`ChangeFeedBatch`] implements this trait.
+#[async_trait::async_trait]
+pub trait ChangeFeedHandler<T: Send + 'static>: Send + Sync + 'static {
+    /// Handles a batch of changed documents.
+    async fn handle(&self, batch: ChangeFeedBatch<T>) -> azure_core::Result<()>;
+}
+
+#[async_trait::async_trait]
+impl<T, F, Fut> ChangeFeedHandler<T> for F
+where
+    T: Send + 'static,
+    F: Fn(ChangeFeedBatch<T>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = azure_core::Result<()>> + Send,
+{
+    async fn handle(&self, batch: ChangeFeedBatch<T>) -> azure_core::Result<()> {
+        self(batch).await
+    }
+}
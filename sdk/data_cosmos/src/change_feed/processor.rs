@@ -0,0 +1,543 @@
+use super::lease::{is_status, range_gone, Lease, LeaseStore, RangeGone, StoredLease};
+use super::reader::read_change_feed_page;
+use super::{ChangeFeedBatch, ChangeFeedHandler, ChangeFeedStartFrom};
+use crate::prelude::*;
+use azure_core::error::ErrorKind;
+use azure_core::prelude::*;
+use azure_core::sleep::sleep_with_context;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Reads the change feed of a collection until the context is cancelled or times out.
+///
+/// Every host running a processor with the same lease container and lease prefix takes its
+/// share of the partition key ranges; the ranges of a host which stops renewing its leases
+/// are taken over by the others once the leases expire.
+pub struct ChangeFeedProcessorBuilder<T> {
+    client: CollectionClient,
+    lease_client: CollectionClient,
+    handler: Arc<dyn ChangeFeedHandler<T>>,
+    host_name: String,
+    lease_prefix: String,
+    start_from: ChangeFeedStartFrom,
+    max_item_count: i32,
+    poll_interval: Duration,
+    lease_acquire_interval: Duration,
+    lease_expiration_interval: Duration,
+    context: Context,
+}
+
+// the setters are not documented, like those of the other builders
+#[allow(missing_docs)]
+impl<T> ChangeFeedProcessorBuilder<T>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    pub(crate) fn new(
+        client: CollectionClient,
+        lease_client: CollectionClient,
+        handler: Arc<dyn ChangeFeedHandler<T>>,
+    ) -> Self {
+        Self {
+            client,
+            lease_client,
+            handler,
+            host_name: uuid::Uuid::new_v4().to_string(),
+            lease_prefix: String::new(),
+            start_from: ChangeFeedStartFrom::default(),
+            max_item_count: 100,
+            poll_interval: Duration::from_secs(5),
+            lease_acquire_interval: Duration::from_secs(13),
+            lease_expiration_interval: Duration::from_secs(60),
+            context: Context::new(),
+        }
+    }
+
+    setters! {
+        host_name: String => host_name,
+        lease_prefix: String => lease_prefix,
+        start_from: ChangeFeedStartFrom => start_from,
+        max_item_count: i32 => max_item_count,
+        poll_interval: Duration => poll_interval,
+        lease_acquire_interval: Duration => lease_acquire_interval,
+        lease_expiration_interval: Duration => lease_expiration_interval,
+        context: Context => context,
+    }
+
+    /// Runs the processor until the cancellation token of the context is cancelled or its
+    /// deadline passes, then releases the leases of this host.
+    pub fn into_future(self) -> ChangeFeedProcessor {
+        Box::pin(async move {
+            let processor = Processor {
+                leases: LeaseStore::new(self.lease_client.clone(), self.lease_prefix.clone()),
+                builder: self,
+            };
+            processor.run().await
+        })
+    }
+}
+
+impl<T> std::fmt::Debug for ChangeFeedProcessorBuilder<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChangeFeedProcessorBuilder")
+            .field("client", &self.client)
+            .field("lease_client", &self.lease_client)
+            .field("host_name", &self.host_name)
+            .field("lease_prefix", &self.lease_prefix)
+            .field("start_from", &self.start_from)
+            .field("max_item_count", &self.max_item_count)
+            .field("poll_interval", &self.poll_interval)
+            .field("lease_acquire_interval", &self.lease_acquire_interval)
+            .field("lease_expiration_interval", &self.lease_expiration_interval)
+            .finish()
+    }
+}
+
+/// The future returned by calling `into_future` on the builder.
+pub type ChangeFeedProcessor = futures::future::BoxFuture<'static, azure_core::Result<()>>;
+
+#[cfg(feature = "into_future")]
+impl<T> std::future::IntoFuture for ChangeFeedProcessorBuilder<T>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    type IntoFuture = ChangeFeedProcessor;
+    type Output = <ChangeFeedProcessor as std::future::Future>::Output;
+    fn into_future(self) -> Self::IntoFuture {
+        Self::into_future(self)
+    }
+}
+
+/// What happened to a lease after reading its range.
+enum Progress {
+    /// There were no new changes.
+    Idle,
+    /// Changes have been handled.
+    Read,
+    /// Another host took the lease, or the range has been split.
+    Lost,
+}
+
+struct Processor<T> {
+    builder: ChangeFeedProcessorBuilder<T>,
+    leases: LeaseStore,
+}
+
+impl<T> Processor<T>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    async fn run(&self) -> azure_core::Result<()> {
+        let context = &self.builder.context;
+        let mut owned = HashMap::new();
+
+        // a misconfigured lease container should fail right away rather than be retried forever
+        self.balance(&mut owned).await?;
+        let mut balanced_at = Instant::now();
+
+        loop {
+            if balanced_at.elapsed() >= self.builder.lease_acquire_interval {
+                match self.balance(&mut owned).await {
+                    Err(error) if self.is_stopped(&error) => break,
+                    Err(error) => warn!("cannot balance the change feed leases: {}", error),
+                    Ok(()) => {}
+                }
+                balanced_at = Instant::now();
+            }
+
+            let mut read_any = false;
+            let ids: Vec<String> = owned.keys().cloned().collect();
+            for id in ids {
+                let lease = match owned.get_mut(&id) {
+                    Some(lease) => lease,
+                    None => continue,
+                };
+                match self.process(lease).await {
+                    Ok(Progress::Idle) => {}
+                    Ok(Progress::Read) => read_any = true,
+                    Ok(Progress::Lost) => {
+                        owned.remove(&id);
+                    }
+                    Err(error) if self.is_stopped(&error) => return self.release(owned).await,
+                    Err(error) => warn!("cannot process the change feed lease {}: {}", id, error),
+                }
+            }
+
+            if !read_any
+                && sleep_with_context(self.builder.poll_interval, context)
+                    .await
+                    .is_err()
+            {
+                break;
+            }
+        }
+
+        self.release(owned).await
+    }
+
+    /// Creates the leases of the new partition key ranges, renews the leases of this host and
+    /// takes its share of the others.
+    async fn balance(&self, owned: &mut HashMap<String, StoredLease>) -> azure_core::Result<()> {
+        let host_name = &self.builder.host_name;
+        let ranges = self.partition_key_ranges().await?;
+        let mut leases = self.leases.list().await?;
+
+        let tokens: HashSet<String> = leases
+            .iter()
+            .map(|lease| lease.lease.lease_token.clone())
+            .collect();
+        for range in &ranges {
+            // the ranges split from a leased range get their leases when the split is handled
+            if tokens.contains(&range.id) || range.parents.iter().any(|p| tokens.contains(p)) {
+                continue;
+            }
+            let lease = Lease::new(self.leases.prefix(), range.id.clone(), None);
+            if let Some(lease) = self.leases.create(lease).await? {
+                leases.push(lease);
+            }
+        }
+
+        let now = Utc::now();
+        let to_take: HashSet<String> = leases_to_take(
+            &leases,
+            host_name,
+            self.builder.lease_expiration_interval,
+            now,
+        )
+        .into_iter()
+        .map(|lease| lease.lease.id.clone())
+        .collect();
+
+        owned.clear();
+        for stored in leases {
+            let renew = stored.lease.owner.as_ref() == Some(host_name)
+                && !stored
+                    .lease
+                    .is_expired(self.builder.lease_expiration_interval, now);
+            if !renew && !to_take.contains(&stored.lease.id) {
+                continue;
+            }
+            let mut lease = stored.lease;
+            if !renew {
+                debug!(
+                    "taking the change feed lease {} from {:?}",
+                    lease.id, lease.owner
+                );
+            }
+            lease.owner = Some(host_name.clone());
+            lease.timestamp = now;
+            if let Some(stored) = self.leases.replace(lease, &stored.etag).await? {
+                owned.insert(stored.lease.id.clone(), stored);
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a page of the range of `stored`, hands it to the handler and checkpoints it.
+    async fn process(&self, stored: &mut StoredLease) -> azure_core::Result<Progress> {
+        let page = read_change_feed_page::<T>(
+            &self.builder.client,
+            &stored.lease.lease_token,
+            stored.lease.continuation_token.as_deref(),
+            &self.builder.start_from,
+            self.builder.max_item_count,
+            &self.builder.context,
+        )
+        .await;
+        let page = match page {
+            Ok(page) => page,
+            Err(error) => match range_gone(&error) {
+                Some(gone) => {
+                    self.split(&stored.lease, gone).await?;
+                    return Ok(Progress::Lost);
+                }
+                None if is_status(&error, 410) => {
+                    // the range is still there, such as when a replica is moved, so it's read
+                    // again after the poll interval
+                    debug!(
+                        "the change feed of the lease {} is temporarily gone: {}",
+                        stored.lease.id, error
+                    );
+                    return Ok(Progress::Idle);
+                }
+                None => return Err(error),
+            },
+        };
+
+        if page.continuation.is_none() || page.continuation == stored.lease.continuation_token {
+            return Ok(Progress::Idle);
+        }
+
+        let progress = if page.documents.is_empty() {
+            Progress::Idle
+        } else {
+            let batch = ChangeFeedBatch {
+                lease_token: stored.lease.lease_token.clone(),
+                documents: page.documents,
+            };
+            if let Err(error) = self.builder.handler.handle(batch).await {
+                // the changes are read again after the poll interval
+                warn!(
+                    "the change feed handler failed for the lease {}: {}",
+                    stored.lease.id, error
+                );
+                return Ok(Progress::Idle);
+            }
+            Progress::Read
+        };
+
+        let mut lease = stored.lease.clone();
+        lease.continuation_token = page.continuation;
+        lease.timestamp = Utc::now();
+        match self.leases.replace(lease, &stored.etag).await? {
+            Some(checkpointed) => {
+                *stored = checkpointed;
+                Ok(progress)
+            }
+            None => Ok(Progress::Lost),
+        }
+    }
+
+    /// Replaces the lease of a range which has been split by the leases of the ranges it became,
+    /// which continue from where the range has been read up to.
+    async fn split(&self, lease: &Lease, gone: RangeGone) -> azure_core::Result<()> {
+        let ranges = self.partition_key_ranges().await?;
+        let children = children_of(&lease.lease_token, gone, ranges)?;
+
+        debug!(
+            "the partition key range {} has been split into {:?}",
+            lease.lease_token, children
+        );
+        for child in children {
+            let child = Lease::new(
+                self.leases.prefix(),
+                child,
+                lease.continuation_token.clone(),
+            );
+            self.leases.create(child).await?;
+        }
+        self.leases.delete(lease).await
+    }
+
+    /// Gives the leases of this host up so that the other hosts can take them right away.
+    async fn release(&self, owned: HashMap<String, StoredLease>) -> azure_core::Result<()> {
+        for stored in owned.into_values() {
+            let mut lease = stored.lease;
+            lease.owner = None;
+            if let Err(error) = self.leases.replace(lease, &stored.etag).await {
+                warn!("cannot release the change feed lease: {}", error);
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `error` means the processor has been asked to stop.
+    fn is_stopped(&self, error: &azure_core::error::Error) -> bool {
+        matches!(error.kind(), ErrorKind::Cancelled)
+            || self
+                .builder
+                .context
+                .deadline()
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    async fn partition_key_ranges(&self) -> azure_core::Result<Vec<PartitionKeyRange>> {
+        Ok(self
+            .builder
+            .client
+            .get_partition_key_ranges()
+            .context(self.builder.context.clone())
+            .into_future()
+            .await?
+            .partition_key_ranges)
+    }
+}
+
+/// Picks the leases `host_name` should take to own its share of `leases`: the expired ones
+/// first, then one of the host owning the most leases if it owns more than its share.
+fn leases_to_take<'a>(
+    leases: &'a [StoredLease],
+    host_name: &str,
+    expiration: Duration,
+    now: DateTime<Utc>,
+) -> Vec<&'a StoredLease> {
+    let mut expired = Vec::new();
+    let mut by_owner: HashMap<&str, Vec<&StoredLease>> = HashMap::new();
+    by_owner.insert(host_name, Vec::new());
+    for stored in leases {
+        match &stored.lease.owner {
+            Some(owner) if !stored.lease.is_expired(expiration, now) => {
+                by_owner.entry(owner).or_default().push(stored)
+            }
+            _ => expired.push(stored),
+        }
+    }
+
+    let target = leases.len().div_ceil(by_owner.len());
+    let owned = by_owner[host_name].len();
+    if owned >= target {
+        return Vec::new();
+    }
+    if !expired.is_empty() {
+        expired.truncate(target - owned);
+        return expired;
+    }
+
+    // take a single lease at a time, so that hosts starting together do not steal from each
+    // other back and forth
+    by_owner
+        .into_iter()
+        .filter(|(owner, leases)| *owner != host_name && leases.len() > target)
+        .max_by_key(|(_, leases)| leases.len())
+        .and_then(|(_, leases)| leases.into_iter().next())
+        .into_iter()
+        .collect()
+}
+
+/// The ids of the ranges the range `lease_token` has been split into.
+///
+/// Merges are not supported yet: the child of a merge must continue from where each of its
+/// parents has been read up to, so the lease of a merged range is kept and an error returned.
+fn children_of(
+    lease_token: &str,
+    gone: RangeGone,
+    ranges: Vec<PartitionKeyRange>,
+) -> azure_core::Result<Vec<String>> {
+    if gone == RangeGone::Merged {
+        return Err(azure_core::error::Error::with_message(
+            ErrorKind::Other,
+            || {
+                format!(
+                    "the partition key range {} has been merged, which is not supported",
+                    lease_token
+                )
+            },
+        ));
+    }
+
+    let children: Vec<String> = ranges
+        .into_iter()
+        .filter(|range| range.parents.iter().any(|parent| parent == lease_token))
+        .map(|range| range.id)
+        .collect();
+    if children.is_empty() {
+        return Err(azure_core::error::Error::with_message(
+            ErrorKind::Other,
+            || {
+                format!(
+                    "the partition key range {} is gone but has no children",
+                    lease_token
+                )
+            },
+        ));
+    }
+    Ok(children)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lease(token: &str, owner: Option<&str>, age: i64, now: DateTime<Utc>) -> StoredLease {
+        let mut lease = Lease::new("", token.to_owned(), None);
+        lease.owner = owner.map(ToOwned::to_owned);
+        lease.timestamp = now - chrono::Duration::seconds(age);
+        StoredLease {
+            lease,
+            etag: String::new(),
+        }
+    }
+
+    fn take(leases: &[StoredLease], host_name: &str, now: DateTime<Utc>) -> Vec<String> {
+        let mut tokens: Vec<String> =
+            leases_to_take(leases, host_name, Duration::from_secs(60), now)
+                .into_iter()
+                .map(|lease| lease.lease.lease_token.clone())
+                .collect();
+        tokens.sort();
+        tokens
+    }
+
+    #[test]
+    fn balance_leases() {
+        let now = Utc::now();
+
+        // a single host takes everything
+        let leases = vec![
+            lease("0", None, 0, now),
+            lease("1", None, 0, now),
+            lease("2", Some("gone"), 120, now),
+        ];
+        assert_eq!(take(&leases, "a", now), ["0", "1", "2"]);
+
+        // the expired leases are shared with the live hosts
+        let leases = vec![
+            lease("0", Some("a"), 10, now),
+            lease("1", Some("b"), 10, now),
+            lease("2", None, 0, now),
+            lease("3", Some("gone"), 120, now),
+        ];
+        assert_eq!(take(&leases, "a", now), ["2"]);
+
+        // a new host steals a single lease from the most loaded one
+        let leases = vec![
+            lease("0", Some("a"), 10, now),
+            lease("1", Some("a"), 10, now),
+            lease("2", Some("a"), 10, now),
+            lease("3", Some("b"), 10, now),
+        ];
+        assert_eq!(take(&leases, "c", now).len(), 1);
+        assert!(take(&leases, "c", now)[0] != "3");
+
+        // a host owning its share takes nothing
+        let leases = vec![
+            lease("0", Some("a"), 10, now),
+            lease("1", Some("a"), 10, now),
+            lease("2", Some("b"), 10, now),
+            lease("3", Some("b"), 10, now),
+        ];
+        assert!(take(&leases, "a", now).is_empty());
+        assert!(take(&leases, "b", now).is_empty());
+    }
+
+    fn range(id: &str, parents: &[&str]) -> PartitionKeyRange {
+        PartitionKeyRange {
+            rid: String::new(),
+            id: id.to_owned(),
+            etag: String::new(),
+            min_exclusive: String::new(),
+            max_exclusive: String::new(),
+            rid_prefix: 0,
+            _self: String::new(),
+            throughput_fraction: 0,
+            status: String::new(),
+            parents: parents.iter().map(|parent| (*parent).to_owned()).collect(),
+            ts: 0,
+        }
+    }
+
+    #[test]
+    fn split_ranges() {
+        let ranges = || {
+            vec![
+                range("1", &["0"]),
+                range("2", &["0"]),
+                range("3", &[]),
+                range("4", &["3", "1"]),
+            ]
+        };
+
+        assert_eq!(
+            children_of("0", RangeGone::Split, ranges()).unwrap(),
+            ["1", "2"]
+        );
+        assert!(children_of("2", RangeGone::Split, ranges()).is_err());
+
+        // the child of a merge does not take over the lease of any of its parents
+        assert!(children_of("1", RangeGone::Merged, ranges()).is_err());
+        assert!(children_of("3", RangeGone::Merged, ranges()).is_err());
+    }
+}
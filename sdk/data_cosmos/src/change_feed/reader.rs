@@ -0,0 +1,75 @@
+use super::ChangeFeedStartFrom;
+use crate::prelude::*;
+use crate::resources::ResourceType;
+use azure_core::headers::etag_from_headers_optional;
+use azure_core::prelude::*;
+use azure_core::{collect_pinned_stream, StatusCode};
+use serde::de::DeserializeOwned;
+
+/// A page of the change feed of a partition key range.
+#[derive(Debug, Clone)]
+pub(crate) struct ChangeFeedPage<T> {
+    pub documents: Vec<T>,
+    /// Where to read the next page from.
+    pub continuation: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChangeFeedBody<T> {
+    #[serde(rename = "Documents")]
+    documents: Vec<T>,
+}
+
+/// Reads the changes of the `range_id` partition key range after `continuation`, or from
+/// `start_from` for a range which has never been read.
+pub(crate) async fn read_change_feed_page<T: DeserializeOwned>(
+    client: &CollectionClient,
+    range_id: &str,
+    continuation: Option<&str>,
+    start_from: &ChangeFeedStartFrom,
+    max_item_count: i32,
+    context: &Context,
+) -> azure_core::Result<ChangeFeedPage<T>> {
+    let mut request = client.docs_request(azure_core::Method::Get);
+
+    request.insert_headers(&ChangeFeed::Incremental);
+    request.insert_headers(&PartitionRangeId::new(range_id.to_owned()));
+    request.insert_headers(&MaxItemCount::new(max_item_count));
+    match (continuation, start_from) {
+        (Some(continuation), _) => {
+            request.insert_headers(&IfMatchCondition::NotMatch(continuation.to_owned()))
+        }
+        (None, ChangeFeedStartFrom::Beginning) => {}
+        (None, ChangeFeedStartFrom::Now) => {
+            request.insert_headers(&IfMatchCondition::NotMatch("*".to_owned()))
+        }
+        (None, ChangeFeedStartFrom::Time(time)) => {
+            request.insert_headers(&IfModifiedSince::new(*time))
+        }
+    }
+
+    let response = client
+        .pipeline()
+        .send(
+            context.clone().insert(ResourceType::Documents),
+            &mut request,
+        )
+        .await?;
+
+    let (status_code, headers, pinned_stream) = response.deconstruct();
+    let continuation = etag_from_headers_optional(&headers)?;
+    // there are no changes after the continuation
+    if status_code == StatusCode::NotModified {
+        return Ok(ChangeFeedPage {
+            documents: Vec::new(),
+            continuation,
+        });
+    }
+
+    let body = collect_pinned_stream(pinned_stream).await?;
+    let body: ChangeFeedBody<T> = serde_json::from_slice(&body)?;
+    Ok(ChangeFeedPage {
+        documents: body.documents,
+        continuation,
+    })
+}
@@ -1,4 +1,5 @@
 use super::{DatabaseClient, UserDefinedFunctionClient};
+use crate::change_feed::{ChangeFeedHandler, ChangeFeedProcessorBuilder};
 use crate::clients::*;
 use crate::operations::*;
 use crate::resources::collection::PartitionKey;
@@ -6,7 +7,9 @@ use crate::resources::document::Query;
 use crate::CosmosEntity;
 use crate::ReadonlyString;
 use azure_core::{Pipeline, Request};
+use serde::de::DeserializeOwned;
 use serde::Serialize;
+use std::sync::Arc;
 
 /// A client for Cosmos collection resources.
 #[derive(Debug, Clone)]
@@ -62,6 +65,33 @@ impl CollectionClient {
         QueryDocumentsBuilder::new(self.clone(), query.into())
     }
 
+    /// Commit several document operations of the `partition_key` logical partition atomically.
+    pub fn transactional_batch<PK: Serialize>(
+        &self,
+        partition_key: &PK,
+    ) -> azure_core::Result<TransactionalBatchBuilder> {
+        Ok(TransactionalBatchBuilder::new(
+            self.clone(),
+            crate::cosmos_entity::serialize_partition_key(partition_key)?,
+        ))
+    }
+
+    /// Process the change feed of the collection with `handler`, keeping track of the progress in
+    /// the `lease_client` collection.
+    ///
+    /// See the [`change_feed`](crate::change_feed) module for more details.
+    pub fn change_feed_processor<T, H>(
+        &self,
+        lease_client: CollectionClient,
+        handler: H,
+    ) -> ChangeFeedProcessorBuilder<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+        H: ChangeFeedHandler<T>,
+    {
+        ChangeFeedProcessorBuilder::new(self.clone(), lease_client, Arc::new(handler))
+    }
+
     /// List stored procedures in a collection.
     pub fn list_stored_procedures(&self) -> ListStoredProceduresBuilder {
         ListStoredProceduresBuilder::new(self.clone())
@@ -1,4 +1,6 @@
 use crate::clients::DatabaseClient;
+use crate::headers;
+use crate::location_cache::{LocationCache, LocationPolicy};
 use crate::operations::*;
 use crate::resources::permission::AuthorizationToken;
 use crate::resources::ResourceType;
@@ -25,7 +27,7 @@ impl CosmosClient {
     /// Create a new `CosmosClient` which connects to the account's instance in the public Azure cloud.
     pub fn new(account: String, auth_token: AuthorizationToken, options: CosmosOptions) -> Self {
         let cloud_location = CloudLocation::Public(account);
-        let pipeline = new_pipeline_from_options(options, auth_token);
+        let pipeline = new_pipeline_from_options(options, auth_token, &cloud_location);
         Self {
             pipeline,
             cloud_location,
@@ -53,7 +55,7 @@ impl CosmosClient {
         options: CosmosOptions,
     ) -> Self {
         let cloud_location = CloudLocation::China(account);
-        let pipeline = new_pipeline_from_options(options, auth_token);
+        let pipeline = new_pipeline_from_options(options, auth_token, &cloud_location);
         Self {
             pipeline,
             cloud_location,
@@ -68,7 +70,7 @@ impl CosmosClient {
         options: CosmosOptions,
     ) -> Self {
         let cloud_location = CloudLocation::Custom { account, uri };
-        let pipeline = new_pipeline_from_options(options, auth_token);
+        let pipeline = new_pipeline_from_options(options, auth_token, &cloud_location);
         Self {
             pipeline,
             cloud_location,
@@ -83,7 +85,7 @@ impl CosmosClient {
             account: String::from("Custom"),
             uri,
         };
-        let pipeline = new_pipeline_from_options(options, auth_token);
+        let pipeline = new_pipeline_from_options(options, auth_token, &cloud_location);
         Self {
             pipeline,
             cloud_location,
@@ -94,8 +96,10 @@ impl CosmosClient {
     pub fn auth_token(&mut self, auth_token: AuthorizationToken) {
         // we replace the AuthorizationPolicy. This is
         // the last-1 policy by construction.
-        let auth_policy: Arc<dyn azure_core::Policy> =
-            Arc::new(crate::AuthorizationPolicy::new(auth_token));
+        let auth_policy: Arc<dyn azure_core::Policy> = Arc::new(crate::AuthorizationPolicy::new(
+            auth_token,
+            self.cloud_location.url(),
+        ));
 
         self.pipeline
             .replace_policy(auth_policy, self.pipeline.policies().len() - 2);
@@ -148,19 +152,53 @@ impl CosmosClient {
 fn new_pipeline_from_options(
     options: CosmosOptions,
     authorization_token: AuthorizationToken,
+    cloud_location: &CloudLocation,
 ) -> Pipeline {
-    let auth_policy: Arc<dyn azure_core::Policy> =
-        Arc::new(crate::AuthorizationPolicy::new(authorization_token));
+    let CosmosOptions {
+        mut options,
+        preferred_regions,
+    } = options;
+    options.logging_mut().add_allowed_header_names([
+        headers::HEADER_ACTIVITY_ID,
+        headers::HEADER_CONSISTENCY_LEVEL,
+        headers::HEADER_REQUEST_CHARGE,
+        headers::HEADER_SESSION_TOKEN,
+        azure_core::headers::CONTINUATION,
+        azure_core::headers::ITEM_COUNT,
+        azure_core::headers::MAX_ITEM_COUNT,
+    ]);
+
+    let auth_policy: Arc<dyn azure_core::Policy> = Arc::new(crate::AuthorizationPolicy::new(
+        authorization_token,
+        cloud_location.url(),
+    ));
 
     // The `AuthorizationPolicy` must be the **last** retry policy.
     // Policies can change the url and/or the headers, and the `AuthorizationPolicy`
     // must be able to inspect them or the resulting token will be invalid.
-    let per_retry_policies = vec![auth_policy];
+    let mut per_retry_policies = vec![auth_policy];
+
+    // The `LocationPolicy` goes before it, so that each attempt can be sent to another region.
+    // A custom uri is only checked when a request is made, so the requests are sent to it as is
+    // rather than failing to build the client if it isn't a valid url.
+    if !preferred_regions.is_empty() {
+        match cloud_location.url().parse() {
+            Ok(default_endpoint) => {
+                let location_cache = LocationCache::new(default_endpoint, preferred_regions);
+                per_retry_policies.insert(0, Arc::new(LocationPolicy::new(location_cache)));
+            }
+            Err(error) => warn!(
+                "ignoring the preferred regions since the account endpoint {} is not a valid url: {}",
+                cloud_location.url(),
+                error
+            ),
+        }
+    }
 
     Pipeline::new(
         option_env!("CARGO_PKG_NAME"),
         option_env!("CARGO_PKG_VERSION"),
-        options.options,
+        options,
         Vec::new(),
         per_retry_policies,
     )
@@ -170,6 +208,7 @@ fn new_pipeline_from_options(
 #[derive(Debug, Clone, Default)]
 pub struct CosmosOptions {
     options: ClientOptions,
+    preferred_regions: Vec<String>,
 }
 
 impl CosmosOptions {
@@ -178,11 +217,33 @@ impl CosmosOptions {
         Self::default()
     }
 
+    /// Send the requests to the regions of the account in this order of preference, such as
+    /// `["West US", "East US"]`.
+    ///
+    /// Setting preferred regions enables the discovery of the regions of the account. The reads
+    /// are sent to the first available preferred region and the writes to the write region of
+    /// the account, or to the first available preferred region if the account accepts writes in
+    /// multiple regions. A request fails over to the next region when a region is unavailable.
+    ///
+    /// The regions of the account are read again every five minutes and after a failover of
+    /// the write region. The requests don't wait for them: they are sent to the regions known so
+    /// far, and the regions read are used once a request sees the read complete. The preferred
+    /// regions are ignored if the uri of a custom cloud is not a valid url.
+    pub fn preferred_regions<I, S>(mut self, preferred_regions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.preferred_regions = preferred_regions.into_iter().map(Into::into).collect();
+        self
+    }
+
     #[cfg(feature = "mock_transport_framework")]
     /// Create new options with a given transaction name
     pub fn new_with_transaction_name(name: String) -> Self {
         Self {
             options: ClientOptions::new_with_transaction_name(name),
+            preferred_regions: Vec::new(),
         }
     }
 }
@@ -213,3 +274,24 @@ impl CloudLocation {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_custom_uri_ignores_preferred_regions() {
+        let options = CosmosOptions::default().preferred_regions(["West US"]);
+        let client = CosmosClient::new_custom(
+            "account".to_owned(),
+            AuthorizationToken::new_resource("resource".to_owned()),
+            "not a url".to_owned(),
+            options,
+        );
+        assert_eq!(client.cloud_location.url(), "not a url");
+
+        let options = CosmosOptions::default().preferred_regions(["West US"]);
+        let client = CosmosClient::new_emulator("localhost", 8081, options);
+        assert_eq!(client.cloud_location.url(), "https://localhost:8081");
+    }
+}
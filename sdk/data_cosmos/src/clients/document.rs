@@ -1,5 +1,6 @@
 use crate::clients::*;
 use crate::operations::*;
+use crate::resources::document::PatchOperation;
 use crate::ReadonlyString;
 use azure_core::Request;
 use serde::de::DeserializeOwned;
@@ -42,6 +43,11 @@ impl DocumentClient {
         ReplaceDocumentBuilder::new(self.clone(), document)
     }
 
+    /// Partially update the document with up to 10 operations.
+    pub fn patch_document(&self, operations: Vec<PatchOperation>) -> PatchDocumentBuilder {
+        PatchDocumentBuilder::new(self.clone(), operations)
+    }
+
     /// Delete the document.
     pub fn delete_document(&self) -> DeleteDocumentBuilder {
         DeleteDocumentBuilder::new(self.clone())
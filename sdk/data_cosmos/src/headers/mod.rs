@@ -19,6 +19,11 @@ pub(crate) const HEADER_DOCUMENTDB_PARTITIONRANGEID: HeaderName =
     HeaderName::from_static("x-ms-documentdb-partitionkeyrangeid"); // [String]
 pub(crate) const HEADER_DOCUMENTDB_PARTITIONKEY: HeaderName =
     HeaderName::from_static("x-ms-documentdb-partitionkey"); // [String]
+pub(crate) const HEADER_COSMOS_IS_BATCH_REQUEST: HeaderName =
+    HeaderName::from_static("x-ms-cosmos-is-batch-request"); // [bool]
+pub(crate) const HEADER_COSMOS_BATCH_ATOMIC: HeaderName =
+    HeaderName::from_static("x-ms-cosmos-batch-atomic"); // [bool]
+pub(crate) const HEADER_SUB_STATUS: HeaderName = HeaderName::from_static("x-ms-substatus"); // [u32]
 pub(crate) const HEADER_NUMBER_OF_READ_REGIONS: HeaderName =
     HeaderName::from_static("x-ms-number-of-read-regions");
 pub(crate) const HEADER_REQUEST_CHARGE: HeaderName = HeaderName::from_static("x-ms-request-charge"); // [f64]
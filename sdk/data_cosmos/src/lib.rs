@@ -95,6 +95,7 @@ extern crate serde;
 #[macro_use]
 extern crate azure_core;
 
+pub mod change_feed;
 pub mod clients;
 mod operations;
 pub mod prelude;
@@ -104,6 +105,7 @@ mod authorization_policy;
 mod consistency_level;
 mod cosmos_entity;
 mod headers;
+mod location_cache;
 mod resource_quota;
 mod time_nonce;
 mod to_json_vector;
@@ -0,0 +1,599 @@
+use crate::headers::{HEADER_DOCUMENTDB_ISQUERY, HEADER_SUB_STATUS};
+use crate::resources::ResourceType;
+use azure_core::error::{ErrorKind, ResultExt};
+use azure_core::headers::Headers;
+use azure_core::{collect_pinned_stream, Context, Policy, PolicyResult, Request, StatusCode, Url};
+use futures::future::{BoxFuture, Either, Shared};
+use futures::FutureExt;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// How long the locations of the account are used before being read again.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+/// How long an endpoint which failed is only used as a last resort.
+const UNAVAILABLE_DURATION: Duration = Duration::from_secs(5 * 60);
+/// The sub-status of a `403 Forbidden` sent by a region which doesn't accept writes anymore.
+const SUB_STATUS_WRITE_FORBIDDEN: &str = "3";
+
+/// The regions of a database account, as returned by a `GET` on the account endpoint.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DatabaseAccount {
+    #[serde(default)]
+    pub writable_locations: Vec<AccountRegion>,
+    #[serde(default)]
+    pub readable_locations: Vec<AccountRegion>,
+    #[serde(default)]
+    pub enable_multiple_write_locations: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AccountRegion {
+    pub name: String,
+    pub database_account_endpoint: String,
+}
+
+/// Orders the regional endpoints of an account by preference, taking the failures into account.
+#[derive(Debug)]
+pub(crate) struct LocationCache {
+    default_endpoint: Url,
+    preferred_regions: Vec<String>,
+    state: RwLock<LocationState>,
+}
+
+/// A read of the locations of the account, which every request polls until one of them sees it
+/// complete, or `None` if they could not be read.
+pub(crate) type Refresh = Shared<BoxFuture<'static, Option<DatabaseAccount>>>;
+
+#[derive(Default)]
+struct LocationState {
+    read_endpoints: Vec<Url>,
+    write_endpoints: Vec<Url>,
+    unavailable: HashMap<Url, Instant>,
+    refreshed_at: Option<Instant>,
+    refresh: Option<Refresh>,
+}
+
+impl std::fmt::Debug for LocationState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocationState")
+            .field("read_endpoints", &self.read_endpoints)
+            .field("write_endpoints", &self.write_endpoints)
+            .field("unavailable", &self.unavailable)
+            .field("refreshed_at", &self.refreshed_at)
+            .field("refreshing", &self.refresh.is_some())
+            .finish()
+    }
+}
+
+impl LocationCache {
+    pub(crate) fn new(default_endpoint: Url, preferred_regions: Vec<String>) -> Self {
+        Self {
+            default_endpoint,
+            preferred_regions,
+            state: RwLock::new(LocationState::default()),
+        }
+    }
+
+    /// Returns the endpoints to try in order, the ones which failed recently last.
+    ///
+    /// The account endpoint is always the last resort.
+    pub(crate) fn endpoints(&self, is_read: bool) -> Vec<Url> {
+        let state = self.state.read().unwrap();
+        let endpoints = if is_read {
+            &state.read_endpoints
+        } else {
+            &state.write_endpoints
+        };
+
+        let now = Instant::now();
+        let (available, mut unavailable): (Vec<_>, Vec<_>) =
+            endpoints.iter().cloned().partition(|endpoint| {
+                state
+                    .unavailable
+                    .get(endpoint)
+                    .is_none_or(|since| now.duration_since(*since) >= UNAVAILABLE_DURATION)
+            });
+
+        let mut endpoints = available;
+        endpoints.append(&mut unavailable);
+        if !endpoints.contains(&self.default_endpoint) {
+            endpoints.push(self.default_endpoint.clone());
+        }
+        endpoints
+    }
+
+    pub(crate) fn mark_unavailable(&self, endpoint: &Url) {
+        let mut state = self.state.write().unwrap();
+        state.unavailable.insert(endpoint.clone(), Instant::now());
+    }
+
+    /// Makes the next request read the locations of the account again.
+    pub(crate) fn expire(&self) {
+        self.state.write().unwrap().refreshed_at = None;
+    }
+
+    /// Returns the read of the locations of the account in progress, starting it with `read`
+    /// if the locations are stale.
+    pub(crate) fn refresh(
+        &self,
+        read: impl FnOnce() -> BoxFuture<'static, Option<DatabaseAccount>>,
+    ) -> Option<Refresh> {
+        let mut state = self.state.write().unwrap();
+        let is_stale = state
+            .refreshed_at
+            .is_none_or(|refreshed_at| refreshed_at.elapsed() >= REFRESH_INTERVAL);
+        if is_stale && state.refresh.is_none() {
+            state.refresh = Some(read().shared());
+        }
+        state.refresh.clone()
+    }
+
+    /// Uses the locations read by `refresh`, unless another request already did.
+    ///
+    /// If they could not be read, the current ones are kept until the next refresh.
+    pub(crate) fn refreshed(&self, refresh: &Refresh, account: Option<&DatabaseAccount>) {
+        let mut state = self.state.write().unwrap();
+        if !state
+            .refresh
+            .as_ref()
+            .is_some_and(|current| current.ptr_eq(refresh))
+        {
+            return;
+        }
+        state.refresh = None;
+        state.refreshed_at = Some(Instant::now());
+
+        let account = match account {
+            Some(account) => account,
+            None => return,
+        };
+        state.read_endpoints = self.order_by_preference(&account.readable_locations);
+        state.write_endpoints = if account.enable_multiple_write_locations {
+            self.order_by_preference(&account.writable_locations)
+        } else {
+            // the first writable location is the write region of the account, the others only
+            // accept writes after a failover
+            account
+                .writable_locations
+                .first()
+                .and_then(|region| parse_endpoint(&region.database_account_endpoint))
+                .into_iter()
+                .collect()
+        };
+    }
+
+    /// Orders the regions following the preferred regions, then the order of the account.
+    fn order_by_preference(&self, regions: &[AccountRegion]) -> Vec<Url> {
+        let mut regions = regions.iter().collect::<Vec<_>>();
+        regions.sort_by_key(|region| {
+            self.preferred_regions
+                .iter()
+                .position(|preferred| region_eq(preferred, &region.name))
+                .unwrap_or(self.preferred_regions.len())
+        });
+        regions
+            .into_iter()
+            .filter_map(|region| parse_endpoint(&region.database_account_endpoint))
+            .collect()
+    }
+}
+
+/// Compares region names ignoring case and spaces, so `West US` matches `westus`.
+fn region_eq(a: &str, b: &str) -> bool {
+    let normalize = |s: &str| {
+        s.chars()
+            .filter(|c| !c.is_whitespace())
+            .flat_map(char::to_lowercase)
+            .collect::<String>()
+    };
+    normalize(a) == normalize(b)
+}
+
+fn parse_endpoint(endpoint: &str) -> Option<Url> {
+    match Url::parse(endpoint) {
+        Ok(url) => Some(url),
+        Err(error) => {
+            warn!("ignoring invalid regional endpoint {}: {}", endpoint, error);
+            None
+        }
+    }
+}
+
+/// Whether the request only reads data, and so can be sent to any readable region.
+fn is_read_request(request: &Request) -> bool {
+    matches!(
+        request.method(),
+        azure_core::Method::Get | azure_core::Method::Head
+    ) || request
+        .headers()
+        .get_optional_str(&HEADER_DOCUMENTDB_ISQUERY)
+        .is_some()
+}
+
+/// Whether the response asks to send the request to another region.
+fn is_failover_response(status: StatusCode, headers: &Headers) -> bool {
+    match status {
+        StatusCode::ServiceUnavailable => true,
+        StatusCode::Forbidden => {
+            headers.get_optional_str(&HEADER_SUB_STATUS) == Some(SUB_STATUS_WRITE_FORBIDDEN)
+        }
+        _ => false,
+    }
+}
+
+/// Sends the requests to the preferred regions of the account, failing over to the other regions
+/// when a region is unavailable.
+///
+/// The policy must be placed before the [`AuthorizationPolicy`](crate::AuthorizationPolicy) since
+/// it uses it to read the locations of the account.
+#[derive(Debug, Clone)]
+pub(crate) struct LocationPolicy {
+    cache: Arc<LocationCache>,
+}
+
+impl LocationPolicy {
+    pub(crate) fn new(cache: LocationCache) -> Self {
+        Self {
+            cache: Arc::new(cache),
+        }
+    }
+
+    /// Sends the request to the next policies, polling the read of the locations of the account
+    /// in progress meanwhile so that it's not held up by it.
+    async fn send_polling(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+        refresh: &mut Option<Refresh>,
+    ) -> PolicyResult {
+        let send = next[0].send(ctx, request, &next[1..]);
+        let pending = match refresh.take() {
+            Some(pending) => pending,
+            None => return send.await,
+        };
+        match futures::future::select(send, pending.clone()).await {
+            Either::Left((result, _)) => {
+                // the next request polls it further
+                *refresh = Some(pending);
+                result
+            }
+            Either::Right((account, send)) => {
+                self.cache.refreshed(&pending, account.as_ref());
+                send.await
+            }
+        }
+    }
+}
+
+async fn read_database_account(
+    endpoint: Url,
+    mut ctx: Context,
+    next: Vec<Arc<dyn Policy>>,
+) -> azure_core::Result<DatabaseAccount> {
+    let mut request = Request::new(endpoint, azure_core::Method::Get);
+    ctx.insert(ResourceType::DatabaseAccount);
+
+    let response = next[0].send(&ctx, &mut request, &next[1..]).await?;
+    let (status, _headers, body) = response.deconstruct();
+    let body = collect_pinned_stream(body).await?;
+    if !status.is_success() {
+        return Err(ErrorKind::http_response_from_body(status as u16, &body).into_error());
+    }
+    serde_json::from_slice(&body).context(
+        ErrorKind::DataConversion,
+        "could not parse the locations of the database account",
+    )
+}
+
+#[async_trait::async_trait]
+impl Policy for LocationPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        assert!(
+            !next.is_empty(),
+            "Location policies cannot be the last policy of a pipeline"
+        );
+
+        // the request is sent to the current endpoints while the locations are read again
+        let mut refresh = self.cache.refresh(|| {
+            let read = read_database_account(
+                self.cache.default_endpoint.clone(),
+                ctx.clone(),
+                next.to_vec(),
+            );
+            async move {
+                read.await
+                    .map_err(|error| {
+                        warn!("could not read the locations of the account: {}", error)
+                    })
+                    .ok()
+            }
+            .boxed()
+        });
+
+        let endpoints = self.cache.endpoints(is_read_request(request));
+        let mut endpoints = endpoints.iter().peekable();
+        loop {
+            // there is always at least the account endpoint
+            let endpoint = endpoints.next().unwrap();
+            set_endpoint(request.url_mut(), endpoint);
+
+            let result = self.send_polling(ctx, request, next, &mut refresh).await;
+            let failover = match &result {
+                Ok(response) => is_failover_response(response.status(), response.headers()),
+                Err(error) => matches!(error.kind(), ErrorKind::Io),
+            };
+            if !failover {
+                return result;
+            }
+
+            debug!("failing over from endpoint {}", endpoint);
+            self.cache.mark_unavailable(endpoint);
+            if let Ok(response) = &result {
+                if response.status() == StatusCode::Forbidden {
+                    // the write region has changed
+                    self.cache.expire();
+                }
+            }
+            if endpoints.peek().is_none() {
+                return result;
+            }
+        }
+    }
+}
+
+/// Replaces the scheme, host and port of `url` with the ones of `endpoint`.
+fn set_endpoint(url: &mut Url, endpoint: &Url) {
+    let mut regional = endpoint.clone();
+    regional.set_path(url.path());
+    regional.set_query(url.query());
+    *url = regional;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(name: &str, endpoint: &str) -> AccountRegion {
+        AccountRegion {
+            name: name.to_owned(),
+            database_account_endpoint: endpoint.to_owned(),
+        }
+    }
+
+    fn account(enable_multiple_write_locations: bool) -> DatabaseAccount {
+        let regions = vec![
+            region("West US", "https://account-westus.documents.azure.com:443/"),
+            region("East US", "https://account-eastus.documents.azure.com:443/"),
+            region(
+                "North Europe",
+                "https://account-northeurope.documents.azure.com:443/",
+            ),
+        ];
+        DatabaseAccount {
+            writable_locations: regions.clone(),
+            readable_locations: regions,
+            enable_multiple_write_locations,
+        }
+    }
+
+    /// Starts a read of the locations of the account returning `account`.
+    fn read(cache: &LocationCache, account: Option<DatabaseAccount>) -> Refresh {
+        cache
+            .refresh(|| futures::future::ready(account).boxed())
+            .unwrap()
+    }
+
+    fn complete(cache: &LocationCache, refresh: Refresh) {
+        let account = refresh.clone().now_or_never().unwrap();
+        cache.refreshed(&refresh, account.as_ref());
+    }
+
+    fn hosts(endpoints: Vec<Url>) -> Vec<String> {
+        endpoints
+            .into_iter()
+            .map(|endpoint| endpoint.host_str().unwrap().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn endpoints_follow_preferred_regions() {
+        let cache = LocationCache::new(
+            Url::parse("https://account.documents.azure.com").unwrap(),
+            vec!["northeurope".to_owned(), "East US".to_owned()],
+        );
+        assert_eq!(
+            hosts(cache.endpoints(true)),
+            ["account.documents.azure.com"]
+        );
+
+        let refresh = read(&cache, Some(account(false)));
+        // the read in progress is shared with the other requests
+        assert!(cache.refresh(|| unreachable!()).unwrap().ptr_eq(&refresh));
+        complete(&cache, refresh);
+        assert!(cache.refresh(|| unreachable!()).is_none());
+
+        assert_eq!(
+            hosts(cache.endpoints(true)),
+            [
+                "account-northeurope.documents.azure.com",
+                "account-eastus.documents.azure.com",
+                "account-westus.documents.azure.com",
+                "account.documents.azure.com",
+            ]
+        );
+        assert_eq!(
+            hosts(cache.endpoints(false)),
+            [
+                "account-westus.documents.azure.com",
+                "account.documents.azure.com"
+            ]
+        );
+
+        cache.expire();
+        complete(&cache, read(&cache, Some(account(true))));
+        assert_eq!(
+            hosts(cache.endpoints(false))[0],
+            "account-northeurope.documents.azure.com"
+        );
+
+        cache.mark_unavailable(
+            &Url::parse("https://account-northeurope.documents.azure.com").unwrap(),
+        );
+        assert_eq!(
+            hosts(cache.endpoints(true)),
+            [
+                "account-eastus.documents.azure.com",
+                "account-westus.documents.azure.com",
+                "account-northeurope.documents.azure.com",
+                "account.documents.azure.com",
+            ]
+        );
+
+        cache.expire();
+        assert!(cache
+            .refresh(|| futures::future::pending().boxed())
+            .is_some());
+    }
+
+    #[test]
+    fn failed_refresh_keeps_the_endpoints() {
+        let cache = LocationCache::new(
+            Url::parse("https://account.documents.azure.com").unwrap(),
+            vec!["East US".to_owned()],
+        );
+        complete(&cache, read(&cache, Some(account(false))));
+
+        cache.expire();
+        let failed = read(&cache, None);
+        complete(&cache, failed.clone());
+        // another request which saw the read complete too
+        complete(&cache, failed);
+        assert!(cache.refresh(|| unreachable!()).is_none());
+        assert_eq!(
+            hosts(cache.endpoints(true)),
+            [
+                "account-eastus.documents.azure.com",
+                "account-westus.documents.azure.com",
+                "account-northeurope.documents.azure.com",
+                "account.documents.azure.com",
+            ]
+        );
+    }
+
+    #[derive(Debug, Default)]
+    struct Transport {
+        sent: std::sync::Mutex<Vec<String>>,
+        account: futures::lock::Mutex<()>,
+    }
+
+    #[async_trait::async_trait]
+    impl Policy for Transport {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            let path = request.url().path().to_owned();
+            self.sent.lock().unwrap().push(path.clone());
+            if path == "/" {
+                // the locations are read once the test releases the lock
+                let _released = self.account.lock().await;
+            } else {
+                // the request waits for the service meanwhile
+                tokio::task::yield_now().await;
+            }
+            Err(azure_core::error::Error::message(ErrorKind::Other, path))
+        }
+    }
+
+    #[tokio::test]
+    async fn requests_are_not_held_up_by_a_refresh() {
+        let policy = LocationPolicy::new(LocationCache::new(
+            Url::parse("https://account.documents.azure.com").unwrap(),
+            vec!["East US".to_owned()],
+        ));
+        let transport = Arc::new(Transport::default());
+        let next: Vec<Arc<dyn Policy>> = vec![transport.clone()];
+        let send = || async {
+            let mut request = Request::new(
+                Url::parse("https://account.documents.azure.com/dbs/db").unwrap(),
+                azure_core::Method::Get,
+            );
+            assert!(policy
+                .send(&Context::new(), &mut request, &next)
+                .await
+                .is_err());
+        };
+
+        // the requests are sent while the locations are being read
+        let account = transport.account.lock().await;
+        send().await;
+        send().await;
+        assert!(policy.cache.refresh(|| unreachable!()).is_some());
+
+        // a later request sees the read complete
+        drop(account);
+        send().await;
+        assert!(policy.cache.refresh(|| unreachable!()).is_none());
+        assert_eq!(
+            *transport.sent.lock().unwrap(),
+            ["/dbs/db", "/", "/dbs/db", "/dbs/db"]
+        );
+    }
+
+    #[test]
+    fn failover_responses() {
+        let mut headers = Headers::from(HashMap::new());
+        assert!(is_failover_response(
+            StatusCode::ServiceUnavailable,
+            &headers
+        ));
+        assert!(!is_failover_response(StatusCode::Forbidden, &headers));
+        headers.insert(HEADER_SUB_STATUS, "3");
+        assert!(is_failover_response(StatusCode::Forbidden, &headers));
+        assert!(!is_failover_response(StatusCode::NotFound, &headers));
+
+        let mut url =
+            Url::parse("https://account.documents.azure.com/dbs/db/colls/coll?a=b").unwrap();
+        set_endpoint(
+            &mut url,
+            &Url::parse("https://account-westus.documents.azure.com:443/").unwrap(),
+        );
+        assert_eq!(
+            url.as_str(),
+            "https://account-westus.documents.azure.com/dbs/db/colls/coll?a=b"
+        );
+    }
+
+    #[test]
+    fn parse_database_account() {
+        let account: DatabaseAccount = serde_json::from_str(
+            r#"{
+                "_self": "",
+                "id": "account",
+                "writableLocations": [
+                    { "name": "West US", "databaseAccountEndpoint": "https://account-westus.documents.azure.com:443/" }
+                ],
+                "readableLocations": [
+                    { "name": "West US", "databaseAccountEndpoint": "https://account-westus.documents.azure.com:443/" },
+                    { "name": "East US", "databaseAccountEndpoint": "https://account-eastus.documents.azure.com:443/" }
+                ],
+                "enableMultipleWriteLocations": false
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(account.writable_locations.len(), 1);
+        assert_eq!(account.readable_locations[1].name, "East US");
+        assert!(!account.enable_multiple_write_locations);
+    }
+}
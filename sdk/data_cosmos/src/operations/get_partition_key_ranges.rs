@@ -159,7 +159,9 @@ pub struct PartitionKeyRange {
     #[serde(rename = "throughputFraction")]
     pub throughput_fraction: u64,
     pub status: String,
-    // TODO: parents
+    /// The ranges this range has been split from, the closest last.
+    #[serde(default)]
+    pub parents: Vec<String>,
     #[serde(rename = "_ts")]
     pub ts: u64,
 }
@@ -38,12 +38,14 @@ mod list_stored_procedures;
 mod list_triggers;
 mod list_user_defined_functions;
 mod list_users;
+mod patch_document;
 mod query_documents;
 mod replace_collection;
 mod replace_document;
 mod replace_permission;
 mod replace_stored_procedure;
 mod replace_user;
+mod transactional_batch;
 
 pub use create_collection::*;
 pub use create_database::*;
@@ -81,9 +83,11 @@ pub use list_stored_procedures::*;
 pub use list_triggers::*;
 pub use list_user_defined_functions::*;
 pub use list_users::*;
+pub use patch_document::*;
 pub use query_documents::*;
 pub use replace_collection::*;
 pub use replace_document::*;
 pub use replace_permission::*;
 pub use replace_stored_procedure::*;
 pub use replace_user::*;
+pub use transactional_batch::*;
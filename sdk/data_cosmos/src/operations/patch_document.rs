@@ -0,0 +1,242 @@
+use crate::cosmos_entity::{add_as_partition_key_header_serialized, serialize_partition_key};
+use crate::headers::from_headers::*;
+use crate::prelude::*;
+use crate::resources::document::DocumentAttributes;
+use crate::ResourceQuota;
+
+use azure_core::error::{Error, ErrorKind};
+use azure_core::headers::{self, session_token_from_headers, HeaderValue};
+use azure_core::prelude::*;
+use azure_core::SessionToken;
+use azure_core::{collect_pinned_stream, Response as HttpResponse};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// The maximum number of operations of a single patch request.
+const MAX_PATCH_OPERATIONS: usize = 10;
+
+#[derive(Debug, Clone)]
+pub struct PatchDocumentBuilder {
+    client: DocumentClient,
+    operations: Vec<PatchOperation>,
+    condition: Option<String>,
+    partition_key: Option<String>,
+    if_match_condition: Option<IfMatchCondition>,
+    consistency_level: Option<ConsistencyLevel>,
+    allow_tentative_writes: TentativeWritesAllowance,
+    context: Context,
+}
+
+impl PatchDocumentBuilder {
+    pub(crate) fn new(client: DocumentClient, operations: Vec<PatchOperation>) -> Self {
+        Self {
+            client,
+            operations,
+            condition: None,
+            partition_key: None,
+            if_match_condition: None,
+            consistency_level: None,
+            allow_tentative_writes: TentativeWritesAllowance::Deny,
+            context: Context::new(),
+        }
+    }
+
+    setters! {
+        consistency_level: ConsistencyLevel => Some(consistency_level),
+        if_match_condition: IfMatchCondition => Some(if_match_condition),
+        allow_tentative_writes: TentativeWritesAllowance,
+        context: Context => context,
+    }
+
+    /// Only apply the operations if the document matches the filter predicate, such as
+    /// `from c where c.status = 'active'`.
+    ///
+    /// The request fails with a `412 Precondition Failed` otherwise.
+    pub fn condition<C: Into<String>>(mut self, condition: C) -> Self {
+        self.condition = Some(condition.into());
+        self
+    }
+
+    pub fn partition_key<T: Serialize>(&mut self, partition_key: &T) -> azure_core::Result<()> {
+        self.partition_key = Some(serialize_partition_key(partition_key)?);
+        Ok(())
+    }
+
+    pub fn into_future(self) -> PatchDocument {
+        Box::pin(async move {
+            if self.operations.is_empty() || self.operations.len() > MAX_PATCH_OPERATIONS {
+                return Err(Error::with_message(ErrorKind::Other, || {
+                    format!(
+                        "a patch request must have between 1 and {} operations, got {}",
+                        MAX_PATCH_OPERATIONS,
+                        self.operations.len()
+                    )
+                }));
+            }
+
+            let mut request = self.client.document_request(azure_core::Method::Patch);
+
+            let partition_key = self
+                .partition_key
+                .as_deref()
+                .unwrap_or_else(|| self.client.partition_key_serialized());
+            add_as_partition_key_header_serialized(partition_key, &mut request);
+
+            request.insert_header(
+                headers::CONTENT_TYPE,
+                HeaderValue::from_static("application/json_patch+json"),
+            );
+            request.insert_headers(&self.if_match_condition);
+            if let Some(cl) = &self.consistency_level {
+                request.insert_headers(cl);
+            }
+            request.insert_headers(&self.allow_tentative_writes);
+
+            let body = PatchRequestBody {
+                condition: self.condition.as_deref(),
+                operations: &self.operations,
+            };
+            request.set_body(azure_core::to_json(&body)?);
+
+            let response = self
+                .client
+                .cosmos_client()
+                .pipeline()
+                .send(
+                    self.context.clone().insert(ResourceType::Documents),
+                    &mut request,
+                )
+                .await?;
+
+            PatchDocumentResponse::try_from(response).await
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct PatchRequestBody<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    condition: Option<&'a str>,
+    operations: &'a [PatchOperation],
+}
+
+/// The future returned by calling `into_future` on the builder.
+pub type PatchDocument =
+    futures::future::BoxFuture<'static, azure_core::Result<PatchDocumentResponse>>;
+
+#[cfg(feature = "into_future")]
+impl std::future::IntoFuture for PatchDocumentBuilder {
+    type IntoFuture = PatchDocument;
+    type Output = <PatchDocument as std::future::Future>::Output;
+    fn into_future(self) -> Self::IntoFuture {
+        Self::into_future(self)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PatchDocumentResponse {
+    pub document_attributes: DocumentAttributes,
+    pub content_location: String,
+    pub last_state_change: DateTime<Utc>,
+    pub resource_quota: Vec<ResourceQuota>,
+    pub resource_usage: Vec<ResourceQuota>,
+    pub lsn: u64,
+    pub schema_version: String,
+    pub alt_content_path: String,
+    pub content_path: String,
+    pub quorum_acked_lsn: Option<u64>,
+    pub current_write_quorum: Option<u64>,
+    pub current_replica_set_size: Option<u64>,
+    pub role: u32,
+    pub global_committed_lsn: u64,
+    pub number_of_read_regions: u32,
+    pub transport_request_id: u64,
+    pub cosmos_llsn: u64,
+    pub cosmos_quorum_acked_llsn: Option<u64>,
+    pub session_token: SessionToken,
+    pub charge: f64,
+    pub service_version: String,
+    pub activity_id: uuid::Uuid,
+    pub gateway_version: String,
+    pub date: DateTime<Utc>,
+}
+
+impl PatchDocumentResponse {
+    pub async fn try_from(response: HttpResponse) -> azure_core::Result<Self> {
+        let (_status_code, headers, pinned_stream) = response.deconstruct();
+        let body = collect_pinned_stream(pinned_stream).await?;
+        let document_attributes = serde_json::from_slice(&body)?;
+
+        Ok(Self {
+            content_location: content_location_from_headers(&headers)?.to_owned(),
+            last_state_change: last_state_change_from_headers(&headers)?,
+            resource_quota: resource_quota_from_headers(&headers)?,
+            resource_usage: resource_usage_from_headers(&headers)?,
+            lsn: lsn_from_headers(&headers)?,
+            schema_version: schema_version_from_headers(&headers)?.to_owned(),
+            alt_content_path: alt_content_path_from_headers(&headers)?.to_owned(),
+            content_path: content_path_from_headers(&headers)?.to_owned(),
+            quorum_acked_lsn: quorum_acked_lsn_from_headers_optional(&headers)?,
+            current_write_quorum: current_write_quorum_from_headers_optional(&headers)?,
+            current_replica_set_size: current_replica_set_size_from_headers_optional(&headers)?,
+            role: role_from_headers(&headers)?,
+            global_committed_lsn: global_committed_lsn_from_headers(&headers)?,
+            number_of_read_regions: number_of_read_regions_from_headers(&headers)?,
+            transport_request_id: transport_request_id_from_headers(&headers)?,
+            cosmos_llsn: cosmos_llsn_from_headers(&headers)?,
+            cosmos_quorum_acked_llsn: cosmos_quorum_acked_llsn_from_headers_optional(&headers)?,
+            session_token: session_token_from_headers(&headers)?,
+            charge: request_charge_from_headers(&headers)?,
+            service_version: service_version_from_headers(&headers)?.to_owned(),
+            activity_id: activity_id_from_headers(&headers)?,
+            gateway_version: gateway_version_from_headers(&headers)?.to_owned(),
+            date: date_from_headers(&headers)?,
+            document_attributes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_patch_request() {
+        let operations = vec![
+            PatchOperation::add("/tags/-", "new"),
+            PatchOperation::set("/status", "active"),
+            PatchOperation::replace("/owner", serde_json::json!({ "name": "contoso" })),
+            PatchOperation::remove("/obsolete"),
+            PatchOperation::increment("/counter", 1),
+            PatchOperation::move_from("/old", "/new"),
+        ];
+        let body = PatchRequestBody {
+            condition: Some("from c where c.status = 'pending'"),
+            operations: &operations,
+        };
+
+        assert_eq!(
+            serde_json::to_value(&body).unwrap(),
+            serde_json::json!({
+                "condition": "from c where c.status = 'pending'",
+                "operations": [
+                    { "op": "add", "path": "/tags/-", "value": "new" },
+                    { "op": "set", "path": "/status", "value": "active" },
+                    { "op": "replace", "path": "/owner", "value": { "name": "contoso" } },
+                    { "op": "remove", "path": "/obsolete" },
+                    { "op": "incr", "path": "/counter", "value": 1 },
+                    { "op": "move", "from": "/old", "path": "/new" },
+                ]
+            })
+        );
+
+        let body = PatchRequestBody {
+            condition: None,
+            operations: &operations[4..5],
+        };
+        assert_eq!(
+            serde_json::to_string(&body).unwrap(),
+            r#"{"operations":[{"op":"incr","path":"/counter","value":1}]}"#
+        );
+    }
+}
@@ -0,0 +1,399 @@
+use crate::cosmos_entity::add_as_partition_key_header_serialized;
+use crate::headers::from_headers::*;
+use crate::headers::{HEADER_COSMOS_BATCH_ATOMIC, HEADER_COSMOS_IS_BATCH_REQUEST};
+use crate::prelude::*;
+
+use azure_core::error::{Error, ErrorKind, ResultExt};
+use azure_core::headers::{session_token_from_headers, HeaderValue};
+use azure_core::prelude::*;
+use azure_core::{collect_pinned_stream, Response as HttpResponse, SessionToken, StatusCode};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+/// The maximum number of operations of a single transactional batch.
+const MAX_BATCH_OPERATIONS: usize = 100;
+
+/// Commits several document operations of a logical partition atomically.
+///
+/// Either all the operations succeed, or none of them is applied and the response holds the
+/// status of the operation that failed.
+#[derive(Debug, Clone)]
+pub struct TransactionalBatchBuilder {
+    client: CollectionClient,
+    partition_key: String,
+    operations: Vec<BatchOperation>,
+    consistency_level: Option<ConsistencyLevel>,
+    context: Context,
+}
+
+impl TransactionalBatchBuilder {
+    pub(crate) fn new(client: CollectionClient, partition_key: String) -> Self {
+        Self {
+            client,
+            partition_key,
+            operations: Vec::new(),
+            consistency_level: None,
+            context: Context::new(),
+        }
+    }
+
+    setters! {
+        consistency_level: ConsistencyLevel => Some(consistency_level),
+        context: Context => context,
+    }
+
+    /// Queue the creation of `document`.
+    pub fn create_document<D: Serialize>(self, document: &D) -> azure_core::Result<Self> {
+        let body = serde_json::to_value(document)?;
+        Ok(self.push(BatchOperation::new(BatchOperationType::Create).body(body)))
+    }
+
+    /// Queue the creation of `document`, replacing it if it already exists.
+    pub fn upsert_document<D: Serialize>(self, document: &D) -> azure_core::Result<Self> {
+        let body = serde_json::to_value(document)?;
+        Ok(self.push(BatchOperation::new(BatchOperationType::Upsert).body(body)))
+    }
+
+    /// Queue the replacement of the document `id` with `document`.
+    pub fn replace_document<S: Into<String>, D: Serialize>(
+        self,
+        id: S,
+        document: &D,
+    ) -> azure_core::Result<Self> {
+        let body = serde_json::to_value(document)?;
+        Ok(self.push(
+            BatchOperation::new(BatchOperationType::Replace)
+                .id(id.into())
+                .body(body),
+        ))
+    }
+
+    /// Queue the read of the document `id`.
+    pub fn read_document<S: Into<String>>(self, id: S) -> Self {
+        self.push(BatchOperation::new(BatchOperationType::Read).id(id.into()))
+    }
+
+    /// Queue the deletion of the document `id`.
+    pub fn delete_document<S: Into<String>>(self, id: S) -> Self {
+        self.push(BatchOperation::new(BatchOperationType::Delete).id(id.into()))
+    }
+
+    /// Queue a partial update of the document `id`.
+    pub fn patch_document<S: Into<String>>(self, id: S, operations: Vec<PatchOperation>) -> Self {
+        let body = serde_json::json!({ "operations": operations });
+        self.push(
+            BatchOperation::new(BatchOperationType::Patch)
+                .id(id.into())
+                .body(body),
+        )
+    }
+
+    /// Only apply the last queued operation if the document's etag matches `etag`.
+    ///
+    /// A mismatch fails the whole batch.
+    pub fn if_match<S: Into<String>>(mut self, etag: S) -> Self {
+        if let Some(operation) = self.operations.last_mut() {
+            operation.if_match = Some(etag.into());
+        }
+        self
+    }
+
+    fn push(mut self, operation: BatchOperation) -> Self {
+        self.operations.push(operation);
+        self
+    }
+
+    pub fn into_future(self) -> TransactionalBatch {
+        Box::pin(async move {
+            if self.operations.is_empty() || self.operations.len() > MAX_BATCH_OPERATIONS {
+                return Err(Error::with_message(ErrorKind::Other, || {
+                    format!(
+                        "a transactional batch must have between 1 and {} operations, got {}",
+                        MAX_BATCH_OPERATIONS,
+                        self.operations.len()
+                    )
+                }));
+            }
+
+            let mut request = self.client.docs_request(azure_core::Method::Post);
+
+            add_as_partition_key_header_serialized(&self.partition_key, &mut request);
+            request.insert_header(
+                HEADER_COSMOS_IS_BATCH_REQUEST,
+                HeaderValue::from_static("True"),
+            );
+            request.insert_header(HEADER_COSMOS_BATCH_ATOMIC, HeaderValue::from_static("True"));
+            if let Some(cl) = &self.consistency_level {
+                request.insert_headers(cl);
+            }
+
+            request.set_body(azure_core::to_json(&self.operations)?);
+
+            let response = self
+                .client
+                .pipeline()
+                .send(
+                    self.context.clone().insert(ResourceType::Documents),
+                    &mut request,
+                )
+                .await?;
+
+            TransactionalBatchResponse::try_from(response).await
+        })
+    }
+}
+
+/// The future returned by calling `into_future` on the builder.
+pub type TransactionalBatch =
+    futures::future::BoxFuture<'static, azure_core::Result<TransactionalBatchResponse>>;
+
+#[cfg(feature = "into_future")]
+impl std::future::IntoFuture for TransactionalBatchBuilder {
+    type IntoFuture = TransactionalBatch;
+    type Output = <TransactionalBatch as std::future::Future>::Output;
+    fn into_future(self) -> Self::IntoFuture {
+        Self::into_future(self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+enum BatchOperationType {
+    Create,
+    Upsert,
+    Replace,
+    Read,
+    Delete,
+    Patch,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchOperation {
+    operation_type: BatchOperationType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resource_body: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    if_match: Option<String>,
+}
+
+impl BatchOperation {
+    fn new(operation_type: BatchOperationType) -> Self {
+        Self {
+            operation_type,
+            id: None,
+            resource_body: None,
+            if_match: None,
+        }
+    }
+
+    fn id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    fn body(mut self, body: Value) -> Self {
+        self.resource_body = Some(body);
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TransactionalBatchResponse {
+    /// The results, in the order the operations were queued.
+    pub results: Vec<TransactionalBatchOperationResult>,
+    pub status_code: StatusCode,
+    pub session_token: SessionToken,
+    /// The total charge of the batch.
+    pub charge: f64,
+    pub activity_id: uuid::Uuid,
+    pub date: DateTime<Utc>,
+}
+
+impl TransactionalBatchResponse {
+    pub async fn try_from(response: HttpResponse) -> azure_core::Result<Self> {
+        let (status_code, headers, pinned_stream) = response.deconstruct();
+        let body = collect_pinned_stream(pinned_stream).await?;
+
+        Ok(Self {
+            results: parse_results(&body)?,
+            status_code,
+            session_token: session_token_from_headers(&headers)?,
+            charge: request_charge_from_headers(&headers)?,
+            activity_id: activity_id_from_headers(&headers)?,
+            date: date_from_headers(&headers)?,
+        })
+    }
+
+    /// Whether the operations have been committed.
+    ///
+    /// The service answers `207 Multi-Status` when an operation failed, in which case the failed
+    /// operation has its own status and the others `424 Failed Dependency`.
+    pub fn is_success(&self) -> bool {
+        self.status_code == StatusCode::Ok
+    }
+}
+
+/// The result of an operation of a transactional batch.
+#[derive(Debug, Clone)]
+pub struct TransactionalBatchOperationResult {
+    pub status_code: StatusCode,
+    pub sub_status_code: Option<u32>,
+    pub etag: Option<String>,
+    pub resource_body: Option<Value>,
+    pub request_charge: f64,
+    pub retry_after_milliseconds: Option<u64>,
+}
+
+impl TransactionalBatchOperationResult {
+    /// Whether the operation succeeded.
+    pub fn is_success(&self) -> bool {
+        self.status_code.is_success()
+    }
+
+    /// Deserialize the document returned by the operation, if any.
+    pub fn resource<T: DeserializeOwned>(&self) -> azure_core::Result<Option<T>> {
+        self.resource_body
+            .clone()
+            .map(serde_json::from_value)
+            .transpose()
+            .context(
+                ErrorKind::DataConversion,
+                "invalid transactional batch resource",
+            )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawOperationResult {
+    status_code: u16,
+    sub_status_code: Option<u32>,
+    e_tag: Option<String>,
+    resource_body: Option<Value>,
+    #[serde(default)]
+    request_charge: f64,
+    retry_after_milliseconds: Option<u64>,
+}
+
+fn parse_results(body: &[u8]) -> azure_core::Result<Vec<TransactionalBatchOperationResult>> {
+    let results: Vec<RawOperationResult> = serde_json::from_slice(body)?;
+    results
+        .into_iter()
+        .map(|result| {
+            Ok(TransactionalBatchOperationResult {
+                status_code: StatusCode::try_from(result.status_code).map_err(|_| {
+                    Error::with_message(ErrorKind::DataConversion, || {
+                        format!("invalid status code {}", result.status_code)
+                    })
+                })?,
+                sub_status_code: result.sub_status_code,
+                etag: result.e_tag,
+                resource_body: result.resource_body,
+                request_charge: result.request_charge,
+                retry_after_milliseconds: result.retry_after_milliseconds,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Counter {
+        id: String,
+        count: u64,
+    }
+
+    fn batch() -> TransactionalBatchBuilder {
+        let client = CosmosClient::new(
+            "account".to_owned(),
+            AuthorizationToken::primary_from_base64("c2VjcmV0").unwrap(),
+            CosmosOptions::default(),
+        );
+        client
+            .database_client("database")
+            .collection_client("collection")
+            .transactional_batch(&"partition")
+            .unwrap()
+    }
+
+    #[test]
+    fn serialize_batch_operations() {
+        let counter = Counter {
+            id: "a".to_owned(),
+            count: 1,
+        };
+        let batch = batch()
+            .create_document(&counter)
+            .unwrap()
+            .replace_document("b", &counter)
+            .unwrap()
+            .if_match("\"etag\"")
+            .read_document("c")
+            .patch_document("d", vec![PatchOperation::increment("/count", 1)])
+            .delete_document("e");
+
+        assert_eq!(batch.partition_key, "[\"partition\"]");
+        assert_eq!(
+            serde_json::to_value(&batch.operations).unwrap(),
+            serde_json::json!([
+                { "operationType": "Create", "resourceBody": { "id": "a", "count": 1 } },
+                {
+                    "operationType": "Replace",
+                    "id": "b",
+                    "resourceBody": { "id": "a", "count": 1 },
+                    "ifMatch": "\"etag\""
+                },
+                { "operationType": "Read", "id": "c" },
+                {
+                    "operationType": "Patch",
+                    "id": "d",
+                    "resourceBody": { "operations": [{ "op": "incr", "path": "/count", "value": 1 }] }
+                },
+                { "operationType": "Delete", "id": "e" },
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_batch_results() {
+        let body = br#"[
+            {
+                "statusCode": 409,
+                "requestCharge": 1.24,
+                "subStatusCode": 0
+            },
+            {
+                "statusCode": 424,
+                "requestCharge": 0
+            },
+            {
+                "statusCode": 200,
+                "requestCharge": 1,
+                "eTag": "\"00000000-0000-0000-0000-000000000000\"",
+                "resourceBody": { "id": "c", "count": 3 }
+            }
+        ]"#;
+
+        let results = parse_results(body).unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].status_code, StatusCode::Conflict);
+        assert!(!results[0].is_success());
+        assert_eq!(results[1].status_code, StatusCode::FailedDependency);
+        assert!(results[2].is_success());
+        assert_eq!(
+            results[2].resource::<Counter>().unwrap(),
+            Some(Counter {
+                id: "c".to_owned(),
+                count: 3
+            })
+        );
+        assert_eq!(results[1].resource::<Counter>().unwrap(), None);
+    }
+}
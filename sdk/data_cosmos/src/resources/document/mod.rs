@@ -2,10 +2,12 @@
 
 mod document_attributes;
 mod indexing_directive;
+mod patch;
 mod query;
 
 pub use document_attributes::DocumentAttributes;
 pub use indexing_directive::IndexingDirective;
+pub use patch::PatchOperation;
 pub use query::{Param, Query};
 
 use super::Resource;
@@ -0,0 +1,76 @@
+use serde_json::Value;
+
+/// An operation of a partial document update.
+///
+/// Paths are JSON Pointers into the document, such as `/tags/0` or `/address/city`. You can
+/// learn more about the semantics of each operation [here](https://docs.microsoft.com/azure/cosmos-db/partial-document-update).
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    /// Add a property or insert an element in an array.
+    #[allow(missing_docs)]
+    Add { path: String, value: Value },
+    /// Set a property, creating it if it doesn't exist.
+    #[allow(missing_docs)]
+    Set { path: String, value: Value },
+    /// Replace an existing property.
+    #[allow(missing_docs)]
+    Replace { path: String, value: Value },
+    /// Remove an existing property or array element.
+    #[allow(missing_docs)]
+    Remove { path: String },
+    /// Increment a numeric property, creating it if it doesn't exist.
+    #[allow(missing_docs)]
+    #[serde(rename = "incr")]
+    Increment { path: String, value: Value },
+    /// Move a property to another path.
+    #[allow(missing_docs)]
+    Move { from: String, path: String },
+}
+
+impl PatchOperation {
+    /// Add `value` at `path`
+    pub fn add<P: Into<String>, V: Into<Value>>(path: P, value: V) -> Self {
+        Self::Add {
+            path: path.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Set `path` to `value`
+    pub fn set<P: Into<String>, V: Into<Value>>(path: P, value: V) -> Self {
+        Self::Set {
+            path: path.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Replace the value at `path` with `value`
+    pub fn replace<P: Into<String>, V: Into<Value>>(path: P, value: V) -> Self {
+        Self::Replace {
+            path: path.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Remove the value at `path`
+    pub fn remove<P: Into<String>>(path: P) -> Self {
+        Self::Remove { path: path.into() }
+    }
+
+    /// Increment the number at `path` by `value`, which can be negative
+    pub fn increment<P: Into<String>, V: Into<Value>>(path: P, value: V) -> Self {
+        Self::Increment {
+            path: path.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Move the value at `from` to `path`
+    pub fn move_from<F: Into<String>, P: Into<String>>(from: F, path: P) -> Self {
+        Self::Move {
+            from: from.into(),
+            path: path.into(),
+        }
+    }
+}
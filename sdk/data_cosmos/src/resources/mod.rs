@@ -52,6 +52,7 @@ pub trait Resource {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum ResourceType {
+    DatabaseAccount,
     Databases,
     Collections,
     Documents,
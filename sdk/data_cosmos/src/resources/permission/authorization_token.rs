@@ -1,15 +1,20 @@
-use super::PermissionToken;
+use azure_core::auth::TokenCredential;
 use std::fmt;
+use std::sync::Arc;
 
 /// Authorization tokens for accessing Cosmos.
 ///
 /// Learn more about the different types of tokens [here](https://docs.microsoft.com/azure/cosmos-db/secure-access-to-data).
-#[derive(PartialEq, Clone, Eq)]
+#[derive(Clone)]
 pub enum AuthorizationToken {
     /// Used for administrative resources: database accounts, databases, users, and permissions
     Primary(Vec<u8>),
     /// Used for application resources: containers, documents, attachments, stored procedures, triggers, and UDFs
     Resource(String),
+    /// Used to authenticate with Azure Active Directory, following the role assignments of the identity
+    ///
+    /// The tokens are requested for the account endpoint and cached until they are about to expire.
+    TokenCredential(Arc<dyn TokenCredential>),
 }
 
 impl AuthorizationToken {
@@ -28,8 +33,27 @@ impl AuthorizationToken {
     pub fn new_resource(resource: String) -> AuthorizationToken {
         AuthorizationToken::Resource(resource)
     }
+
+    /// Create an `AuthorizationToken` authenticating with the Azure Active Directory tokens of
+    /// `credential`, such as the credentials of `azure_identity`.
+    pub fn from_token_credential(credential: Arc<dyn TokenCredential>) -> AuthorizationToken {
+        AuthorizationToken::TokenCredential(credential)
+    }
+}
+
+impl PartialEq for AuthorizationToken {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Primary(a), Self::Primary(b)) => a == b,
+            (Self::Resource(a), Self::Resource(b)) => a == b,
+            (Self::TokenCredential(a), Self::TokenCredential(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
 }
 
+impl Eq for AuthorizationToken {}
+
 impl fmt::Debug for AuthorizationToken {
     // We provide a custom implementation to hide the key value.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -39,17 +63,8 @@ impl fmt::Debug for AuthorizationToken {
             match self {
                 AuthorizationToken::Primary(_) => "Master",
                 AuthorizationToken::Resource(_) => "Resource",
+                AuthorizationToken::TokenCredential(_) => "TokenCredential",
             }
         )
     }
 }
-
-impl std::convert::From<PermissionToken> for AuthorizationToken {
-    fn from(permission_token: PermissionToken) -> Self {
-        trace!(
-            "Converting permission_token into AuthorizationToken: {:#?}",
-            permission_token
-        );
-        permission_token.token
-    }
-}
@@ -7,11 +7,32 @@ const SIGNATURE_PREFIX: &str = "sig=";
 /// The token field of a [`Permission`](super::Permission) object.
 ///
 /// This field is a url encoded string with the type of permission, the signature, and the version (currently only 1.0)
-/// This type is a wrapper around AuthorizationToken.
+/// This type is a wrapper around the primary or resource variants of AuthorizationToken.
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 #[serde(try_from = "String")]
 pub struct PermissionToken {
-    pub(crate) token: AuthorizationToken,
+    signature: Signature,
+}
+
+/// The signature of a permission, which is never backed by a token credential.
+#[derive(Clone, PartialEq)]
+enum Signature {
+    Primary(Vec<u8>),
+    Resource(String),
+}
+
+impl std::fmt::Debug for Signature {
+    // We provide a custom implementation to hide the key value.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Signature::{}(***hidden***)",
+            match self {
+                Signature::Primary(_) => "Master",
+                Signature::Resource(_) => "Resource",
+            }
+        )
+    }
 }
 
 impl serde::Serialize for PermissionToken {
@@ -26,9 +47,9 @@ impl serde::Serialize for PermissionToken {
 impl std::fmt::Display for PermissionToken {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         use std::borrow::Cow;
-        let (permission_type, signature) = match &self.token {
-            AuthorizationToken::Resource(s) => ("resource", Cow::Borrowed(s)),
-            AuthorizationToken::Primary(s) => ("master", Cow::Owned(base64::encode(s))),
+        let (permission_type, signature) = match &self.signature {
+            Signature::Resource(s) => ("resource", Cow::Borrowed(s)),
+            Signature::Primary(s) => ("master", Cow::Owned(base64::encode(s))),
         };
         write!(
             f,
@@ -69,16 +90,16 @@ impl std::convert::TryFrom<&str> for PermissionToken {
 
         let permission_type = try_get_item(s, &parts, PERMISSION_TYPE_PREFIX)?;
         let signature = try_get_item(s, &parts, SIGNATURE_PREFIX)?.to_owned();
-        let token = match permission_type {
-            "master" => AuthorizationToken::Primary(base64::decode(signature)?),
-            "resource" => AuthorizationToken::Resource(signature),
+        let signature = match permission_type {
+            "master" => Signature::Primary(base64::decode(signature)?),
+            "resource" => Signature::Resource(signature),
             _ => {
                 return Err(PermissionTokenParseError::UnrecognizedPermissionType {
                     provided_type: permission_type.to_owned(),
                 })
             }
         };
-        Ok(Self { token })
+        Ok(Self { signature })
     }
 }
 
@@ -108,8 +129,32 @@ fn try_get_item<'a>(
 }
 
 impl std::convert::From<AuthorizationToken> for PermissionToken {
+    /// # Panics
+    ///
+    /// Panics if the token is backed by a token credential: its AAD tokens are not permissions,
+    /// and are not to be shared.
     fn from(token: AuthorizationToken) -> Self {
-        Self { token }
+        let signature = match token {
+            AuthorizationToken::Primary(key) => Signature::Primary(key),
+            AuthorizationToken::Resource(resource) => Signature::Resource(resource),
+            AuthorizationToken::TokenCredential(_) => {
+                panic!("a token credential cannot be turned into a permission token")
+            }
+        };
+        Self { signature }
+    }
+}
+
+impl std::convert::From<PermissionToken> for AuthorizationToken {
+    fn from(permission_token: PermissionToken) -> Self {
+        trace!(
+            "Converting permission_token into AuthorizationToken: {:#?}",
+            permission_token
+        );
+        match permission_token.signature {
+            Signature::Primary(key) => AuthorizationToken::Primary(key),
+            Signature::Resource(resource) => AuthorizationToken::Resource(resource),
+        }
     }
 }
 
@@ -168,9 +213,36 @@ mod tests {
     #[test]
     fn parse_permission_token() {
         let permission_token: PermissionToken = PERMISSION.try_into().unwrap();
-        assert!(matches!(
-            permission_token.token,
-            AuthorizationToken::Resource(_)
-        ));
+        assert!(matches!(permission_token.signature, Signature::Resource(_)));
+    }
+
+    #[test]
+    fn convert_authorization_token() {
+        let token = AuthorizationToken::new_resource("resource".to_owned());
+        let permission_token: PermissionToken = token.clone().into();
+        assert_eq!(
+            permission_token.to_string(),
+            "type=resource&ver=1.0&sig=resource"
+        );
+        assert_eq!(AuthorizationToken::from(permission_token), token);
+    }
+
+    struct Credential;
+
+    #[async_trait::async_trait]
+    impl azure_core::auth::TokenCredential for Credential {
+        async fn get_token(
+            &self,
+            _resource: &str,
+        ) -> azure_core::Result<azure_core::auth::TokenResponse> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "a token credential cannot be turned into a permission token")]
+    fn token_credentials_are_not_permission_tokens() {
+        let token = AuthorizationToken::from_token_credential(std::sync::Arc::new(Credential));
+        let _: PermissionToken = token.into();
     }
 }
@@ -33,49 +33,58 @@ impl Policy for AuthorizationPolicy {
             !next.is_empty(),
             "Authorization policies cannot be the last policy of a pipeline"
         );
-        let request = match &self.credentials {
-            StorageCredentials::Key(account, key) => {
-                if !request.url().query_pairs().any(|(k, _)| &*k == "sig") {
-                    let auth = generate_authorization(
-                        request.headers(),
-                        request.url(),
-                        request.method(),
-                        account,
-                        key,
-                        ctx.get()
-                            .expect("ServiceType must be in the Context at this point"),
-                    );
-                    request.insert_header(AUTHORIZATION, auth)
-                }
-                request
-            }
-            StorageCredentials::SASToken(query_pairs) => {
-                request
-                    .url_mut()
-                    .query_pairs_mut()
-                    .extend_pairs(query_pairs);
+        authorize(
+            request,
+            &self.credentials,
+            ctx.get()
+                .expect("ServiceType must be in the Context at this point"),
+        )?;
 
-                request
-            }
-            StorageCredentials::BearerToken(token) => {
-                request.insert_header(AUTHORIZATION, format!("Bearer {}", token));
-                request
-            }
-            StorageCredentials::TokenCredential(token_credential) => {
-                let bearer_token_future = token_credential.get_token(STORAGE_TOKEN_SCOPE);
-                let bearer_token = futures::executor::block_on(bearer_token_future)
-                    .context(ErrorKind::Credential, "failed to get bearer token")?;
-
-                request.insert_header(
-                    AUTHORIZATION,
-                    format!("Bearer {}", bearer_token.token.secret()),
+        next[0].send(ctx, request, &next[1..]).await
+    }
+}
+
+/// Adds the authorization of `credentials` to `request`.
+pub(crate) fn authorize(
+    request: &mut Request,
+    credentials: &StorageCredentials,
+    service_type: &ServiceType,
+) -> azure_core::Result<()> {
+    match credentials {
+        StorageCredentials::Key(account, key) => {
+            if !request.url().query_pairs().any(|(k, _)| &*k == "sig") {
+                let auth = generate_authorization(
+                    request.headers(),
+                    request.url(),
+                    request.method(),
+                    account,
+                    key,
+                    service_type,
                 );
-                request
+                request.insert_header(AUTHORIZATION, auth)
             }
-        };
-
-        next[0].send(ctx, request, &next[1..]).await
+        }
+        StorageCredentials::SASToken(query_pairs) => {
+            request
+                .url_mut()
+                .query_pairs_mut()
+                .extend_pairs(query_pairs);
+        }
+        StorageCredentials::BearerToken(token) => {
+            request.insert_header(AUTHORIZATION, format!("Bearer {}", token));
+        }
+        StorageCredentials::TokenCredential(token_credential) => {
+            let bearer_token_future = token_credential.get_token(STORAGE_TOKEN_SCOPE);
+            let bearer_token = futures::executor::block_on(bearer_token_future)
+                .context(ErrorKind::Credential, "failed to get bearer token")?;
+
+            request.insert_header(
+                AUTHORIZATION,
+                format!("Bearer {}", bearer_token.token.secret()),
+            );
+        }
     }
+    Ok(())
 }
 
 fn generate_authorization(
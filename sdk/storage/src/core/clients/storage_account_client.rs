@@ -1,4 +1,4 @@
-use crate::authorization_policy::AuthorizationPolicy;
+use crate::authorization_policy::{authorize, AuthorizationPolicy};
 use crate::ConnectionString;
 use crate::{
     hmac::sign, shared_access_signature::account_sas::AccountSharedAccessSignatureBuilder,
@@ -63,6 +63,7 @@ pub struct StorageAccountClient {
     table_storage_url: Url,
     queue_storage_url: Url,
     queue_storage_secondary_url: Url,
+    blob_storage_secondary_url: Url,
     filesystem_url: Url,
     account: String,
     pipeline: Pipeline,
@@ -114,6 +115,12 @@ impl StorageAccountClient {
                 "queue",
             )
             .unwrap(),
+            blob_storage_secondary_url: get_endpoint_uri(
+                None,
+                &format!("{account}-secondary"),
+                "blob",
+            )
+            .unwrap(),
             filesystem_url: get_endpoint_uri(None, &account, "dfs").unwrap(),
             storage_credentials,
             http_client,
@@ -180,7 +187,8 @@ impl StorageAccountClient {
         let filesystem_url = Url::parse(&format!("{}{}", filesystem_url, account)).unwrap();
 
         Arc::new(Self {
-            blob_storage_url,
+            blob_storage_url: blob_storage_url.clone(),
+            blob_storage_secondary_url: blob_storage_url,
             table_storage_url,
             queue_storage_url: queue_storage_url.clone(),
             queue_storage_secondary_url: queue_storage_url,
@@ -217,6 +225,11 @@ impl StorageAccountClient {
                 &format!("{account}-secondary"),
                 "queue",
             )?,
+            blob_storage_secondary_url: get_endpoint_uri(
+                None,
+                &format!("{account}-secondary"),
+                "blob",
+            )?,
             filesystem_url: get_endpoint_uri(None, &account, "dfs")?,
             storage_credentials,
             http_client,
@@ -250,6 +263,12 @@ impl StorageAccountClient {
                 "queue",
             )
             .unwrap(),
+            blob_storage_secondary_url: get_endpoint_uri(
+                None,
+                &format!("{}-secondary", account),
+                "blob",
+            )
+            .unwrap(),
             filesystem_url: get_endpoint_uri(None, &account, "dfs").unwrap(),
             storage_credentials,
             http_client,
@@ -281,6 +300,12 @@ impl StorageAccountClient {
                 "queue",
             )
             .unwrap(),
+            blob_storage_secondary_url: get_endpoint_uri(
+                None,
+                &format!("{}-secondary", account),
+                "blob",
+            )
+            .unwrap(),
             filesystem_url: get_endpoint_uri(None, &account, "dfs").unwrap(),
             storage_credentials,
             http_client,
@@ -317,6 +342,7 @@ impl StorageAccountClient {
                     table_storage_url: get_endpoint_uri(table_endpoint, account, "table")?,
                     queue_storage_url: get_endpoint_uri(queue_endpoint, account, "queue")?,
                     queue_storage_secondary_url: get_endpoint_uri(queue_endpoint, &format!("{}-secondary", account), "queue")?,
+                    blob_storage_secondary_url: get_endpoint_uri(blob_endpoint, &format!("{}-secondary", account), "blob")?,
                     filesystem_url: get_endpoint_uri(file_endpoint, account, "dfs")?,
                     http_client,
                     account: account.to_string(),
@@ -341,6 +367,7 @@ impl StorageAccountClient {
                     table_storage_url: get_endpoint_uri(table_endpoint, account, "table")?,
                     queue_storage_url: get_endpoint_uri(queue_endpoint, account, "queue")?,
                     queue_storage_secondary_url: get_endpoint_uri(queue_endpoint, &format!("{}-secondary", account), "queue")?,
+                    blob_storage_secondary_url: get_endpoint_uri(blob_endpoint, &format!("{}-secondary", account), "blob")?,
                     filesystem_url: get_endpoint_uri(file_endpoint, account, "dfs")?,
                     http_client,
                     account: account.to_string(),
@@ -364,6 +391,7 @@ impl StorageAccountClient {
                 table_storage_url: get_endpoint_uri(table_endpoint, account, "table")?,
                 queue_storage_url: get_endpoint_uri(queue_endpoint, account, "queue")?,
                 queue_storage_secondary_url: get_endpoint_uri(queue_endpoint, &format!("{}-secondary", account), "queue")?,
+                blob_storage_secondary_url: get_endpoint_uri(blob_endpoint, &format!("{}-secondary", account), "blob")?,
                 filesystem_url: get_endpoint_uri(file_endpoint, account, "dfs")?,
                 http_client,
                 account: account.to_string(),
@@ -386,6 +414,10 @@ impl StorageAccountClient {
         &self.blob_storage_url
     }
 
+    pub fn blob_storage_secondary_url(&self) -> &Url {
+        &self.blob_storage_secondary_url
+    }
+
     pub fn table_storage_url(&self) -> &Url {
         &self.table_storage_url
     }
@@ -496,6 +528,21 @@ impl StorageAccountClient {
             .await
     }
 
+    /// Authorizes a request sent in the body of a batch request, as the pipeline authorizes the
+    /// requests it sends.
+    ///
+    /// Batch sub-requests have no `x-ms-version` header: the version of the batch request applies.
+    pub fn authorize_batch_subrequest(
+        &self,
+        request: &mut Request,
+        service_type: ServiceType,
+    ) -> azure_core::Result<()> {
+        let dt = chrono::Utc::now();
+        request.insert_header(MS_DATE, format!("{}", dt.format("%a, %d %h %Y %T GMT")));
+        request.insert_header(CONTENT_LENGTH, "0");
+        authorize(request, &self.storage_credentials, &service_type)
+    }
+
     /// Prepares' an `azure_core::Request`.
     pub(crate) fn blob_storage_request(
         &self,
@@ -709,6 +756,27 @@ fn get_endpoint_uri(
 
 /// Create a Pipeline from CosmosOptions
 fn new_pipeline_from_options(options: StorageOptions, credentials: StorageCredentials) -> Pipeline {
+    let mut options = options.options;
+    options.logging_mut().add_allowed_header_names([
+        BLOB_ACCESS_TIER,
+        BLOB_CONTENT_LENGTH,
+        BLOB_SEQUENCE_NUMBER,
+        BLOB_TYPE,
+        COPY_STATUS,
+        DELETE_SNAPSHOTS,
+        LEASE_STATE,
+        LEASE_STATUS,
+        MS_RANGE,
+        RANGE,
+        SERVER_ENCRYPTED,
+    ]);
+    options.logging_mut().add_allowed_query_parameters([
+        "blockid",
+        "blocklisttype",
+        "snapshot",
+        "versionid",
+    ]);
+
     let auth_policy: Arc<dyn azure_core::Policy> = Arc::new(AuthorizationPolicy::new(credentials));
 
     // The `AuthorizationPolicy` must be the **last** retry policy.
@@ -719,7 +787,7 @@ fn new_pipeline_from_options(options: StorageOptions, credentials: StorageCreden
     Pipeline::new(
         option_env!("CARGO_PKG_NAME"),
         option_env!("CARGO_PKG_VERSION"),
-        options.options,
+        options,
         Vec::new(),
         per_retry_policies,
     )
@@ -34,6 +34,9 @@ impl TryFrom<&Headers> for CommonStorageResponseHeaders {
 pub const CONTENT_CRC64: HeaderName = HeaderName::from_static("x-ms-content-crc64");
 pub const COPY_ID: HeaderName = HeaderName::from_static("x-ms-copy-id");
 pub const RENAME_SOURCE: HeaderName = HeaderName::from_static("x-ms-rename-source");
+pub const IF_TAGS: HeaderName = HeaderName::from_static("x-ms-if-tags");
+pub const TAG_COUNT: HeaderName = HeaderName::from_static("x-ms-tag-count");
+pub const TAGS: HeaderName = HeaderName::from_static("x-ms-tags");
 
 pub fn content_crc64_from_headers(headers: &Headers) -> azure_core::Result<ConsistencyCRC64> {
     headers.get_as(&CONTENT_CRC64)
@@ -90,8 +90,22 @@ impl fmt::Display for BlobSasPermissions {
     }
 }
 
+/// A key obtained with Azure AD credentials, used to sign user delegation SAS tokens instead of
+/// the account key ([Azure documentation](https://docs.microsoft.com/rest/api/storageservices/create-user-delegation-sas)).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserDelegationKey {
+    pub signed_oid: String,           // skoid
+    pub signed_tid: String,           // sktid
+    pub signed_start: DateTime<Utc>,  // skt
+    pub signed_expiry: DateTime<Utc>, // ske
+    pub signed_service: String,       // sks
+    pub signed_version: String,       // skv
+    pub value: String,
+}
+
 pub struct BlobSharedAccessSignature {
     key: String,
+    user_delegation_key: Option<UserDelegationKey>,
 
     canonicalized_resource: String,
     signed_permissions: BlobSasPermissions, // sp
@@ -101,10 +115,16 @@ pub struct BlobSharedAccessSignature {
     signed_ip: Option<String>,
     signed_protocol: Option<SasProtocol>,
     signed_resource: BlobSignedResource,
+    signed_authorized_user_object_id: Option<String>, // saoid
+    signed_unauthorized_user_object_id: Option<String>, // suoid
 }
 
 impl BlobSharedAccessSignature {
     fn sign(&self) -> String {
+        if let Some(user_delegation_key) = &self.user_delegation_key {
+            return self.sign_user_delegation(user_delegation_key);
+        }
+
         let content = vec![
             self.signed_permissions.to_string(),
             self.signed_start.map_or("".to_string(), format_date),
@@ -133,6 +153,42 @@ impl BlobSharedAccessSignature {
 
         hmac::sign(&content.join("\n"), &self.key).unwrap()
     }
+
+    fn sign_user_delegation(&self, user_delegation_key: &UserDelegationKey) -> String {
+        let content = vec![
+            self.signed_permissions.to_string(),
+            self.signed_start.map_or("".to_string(), format_date),
+            format_date(self.signed_expiry),
+            self.canonicalized_resource.clone(),
+            user_delegation_key.signed_oid.clone(),
+            user_delegation_key.signed_tid.clone(),
+            format_date(user_delegation_key.signed_start),
+            format_date(user_delegation_key.signed_expiry),
+            user_delegation_key.signed_service.clone(),
+            user_delegation_key.signed_version.clone(),
+            self.signed_authorized_user_object_id
+                .clone()
+                .unwrap_or_default(),
+            self.signed_unauthorized_user_object_id
+                .clone()
+                .unwrap_or_default(),
+            "".to_string(), // signed correlation id
+            self.signed_ip.clone().unwrap_or_default(),
+            self.signed_protocol
+                .map(|x| x.to_string())
+                .unwrap_or_default(),
+            SERVICE_SAS_VERSION.to_string(),
+            self.signed_resource.to_string(),
+            "".to_string(), // snapshot time
+            "".to_string(), // rscc
+            "".to_string(), // rscd
+            "".to_string(), // rsce
+            "".to_string(), // rscl
+            "".to_string(), // rsct
+        ];
+
+        hmac::sign(&content.join("\n"), &user_delegation_key.value).unwrap()
+    }
 }
 
 impl SasToken for BlobSharedAccessSignature {
@@ -156,6 +212,27 @@ impl SasToken for BlobSharedAccessSignature {
             elements.push(format!("spr={}", protocol))
         }
 
+        if let Some(user_delegation_key) = &self.user_delegation_key {
+            elements.push(format!("skoid={}", user_delegation_key.signed_oid));
+            elements.push(format!("sktid={}", user_delegation_key.signed_tid));
+            elements.push(format!(
+                "skt={}",
+                format_form(format_date(user_delegation_key.signed_start))
+            ));
+            elements.push(format!(
+                "ske={}",
+                format_form(format_date(user_delegation_key.signed_expiry))
+            ));
+            elements.push(format!("sks={}", user_delegation_key.signed_service));
+            elements.push(format!("skv={}", user_delegation_key.signed_version));
+            if let Some(oid) = &self.signed_authorized_user_object_id {
+                elements.push(format!("saoid={}", oid))
+            }
+            if let Some(oid) = &self.signed_unauthorized_user_object_id {
+                elements.push(format!("suoid={}", oid))
+            }
+        }
+
         let sig = self.sign();
         elements.push(format!("sig={}", format_form(sig)));
 
@@ -171,6 +248,7 @@ pub struct SetExpiry {}
 pub struct BlobSharedAccessSignatureBuilder<T1, T2, T3> {
     _phantom: PhantomData<(T1, T2, T3)>,
     key: String,
+    user_delegation_key: Option<UserDelegationKey>,
     canonicalized_resource: String,
 
     // required
@@ -183,6 +261,8 @@ pub struct BlobSharedAccessSignatureBuilder<T1, T2, T3> {
     signed_identifier: Option<String>,
     signed_ip: Option<String>,
     signed_protocol: Option<SasProtocol>,
+    signed_authorized_user_object_id: Option<String>,
+    signed_unauthorized_user_object_id: Option<String>,
 }
 
 impl BlobSharedAccessSignatureBuilder<(), (), ()> {
@@ -193,6 +273,7 @@ impl BlobSharedAccessSignatureBuilder<(), (), ()> {
         BlobSharedAccessSignatureBuilder {
             _phantom: PhantomData,
             key,
+            user_delegation_key: None,
             canonicalized_resource,
             signed_permissions: None,
             signed_expiry: None,
@@ -201,6 +282,24 @@ impl BlobSharedAccessSignatureBuilder<(), (), ()> {
             signed_identifier: None,
             signed_ip: None,
             signed_protocol: None,
+            signed_authorized_user_object_id: None,
+            signed_unauthorized_user_object_id: None,
+        }
+    }
+
+    /// Creates a builder for a user delegation SAS, signed with a key obtained with Azure AD
+    /// credentials rather than with the account key.
+    ///
+    /// Stored access policies cannot be used with a user delegation SAS, so the identifier set
+    /// with `with_identifier` is ignored.
+    pub fn new_user_delegation(
+        user_delegation_key: UserDelegationKey,
+        canonicalized_resource: String,
+    ) -> BlobSharedAccessSignatureBuilder<(), (), ()> {
+        BlobSharedAccessSignatureBuilder {
+            key: user_delegation_key.value.clone(),
+            user_delegation_key: Some(user_delegation_key),
+            ..Self::new(String::new(), canonicalized_resource)
         }
     }
 }
@@ -213,6 +312,7 @@ impl<T1, T2, T3> BlobSharedAccessSignatureBuilder<T1, T2, T3> {
         BlobSharedAccessSignatureBuilder {
             _phantom: PhantomData,
             key: self.key,
+            user_delegation_key: self.user_delegation_key,
             canonicalized_resource: self.canonicalized_resource,
             signed_permissions: self.signed_permissions,
             signed_resource: self.signed_resource,
@@ -221,12 +321,15 @@ impl<T1, T2, T3> BlobSharedAccessSignatureBuilder<T1, T2, T3> {
             signed_identifier: self.signed_identifier,
             signed_protocol: self.signed_protocol,
             signed_ip: self.signed_ip,
+            signed_authorized_user_object_id: self.signed_authorized_user_object_id,
+            signed_unauthorized_user_object_id: self.signed_unauthorized_user_object_id,
         }
     }
     pub fn with_ip(self, signed_ip: String) -> BlobSharedAccessSignatureBuilder<T1, T2, T3> {
         BlobSharedAccessSignatureBuilder {
             _phantom: PhantomData,
             key: self.key,
+            user_delegation_key: self.user_delegation_key,
             canonicalized_resource: self.canonicalized_resource,
             signed_permissions: self.signed_permissions,
             signed_resource: self.signed_resource,
@@ -235,6 +338,8 @@ impl<T1, T2, T3> BlobSharedAccessSignatureBuilder<T1, T2, T3> {
             signed_identifier: self.signed_identifier,
             signed_protocol: self.signed_protocol,
             signed_ip: Some(signed_ip),
+            signed_authorized_user_object_id: self.signed_authorized_user_object_id,
+            signed_unauthorized_user_object_id: self.signed_unauthorized_user_object_id,
         }
     }
     pub fn with_identifier(
@@ -244,6 +349,7 @@ impl<T1, T2, T3> BlobSharedAccessSignatureBuilder<T1, T2, T3> {
         BlobSharedAccessSignatureBuilder {
             _phantom: PhantomData,
             key: self.key,
+            user_delegation_key: self.user_delegation_key,
             canonicalized_resource: self.canonicalized_resource,
             signed_permissions: self.signed_permissions,
             signed_resource: self.signed_resource,
@@ -252,6 +358,8 @@ impl<T1, T2, T3> BlobSharedAccessSignatureBuilder<T1, T2, T3> {
             signed_identifier: Some(signed_identifier),
             signed_protocol: self.signed_protocol,
             signed_ip: self.signed_ip,
+            signed_authorized_user_object_id: self.signed_authorized_user_object_id,
+            signed_unauthorized_user_object_id: self.signed_unauthorized_user_object_id,
         }
     }
     pub fn with_protocol(
@@ -261,6 +369,7 @@ impl<T1, T2, T3> BlobSharedAccessSignatureBuilder<T1, T2, T3> {
         BlobSharedAccessSignatureBuilder {
             _phantom: PhantomData,
             key: self.key,
+            user_delegation_key: self.user_delegation_key,
             canonicalized_resource: self.canonicalized_resource,
             signed_permissions: self.signed_permissions,
             signed_resource: self.signed_resource,
@@ -269,6 +378,30 @@ impl<T1, T2, T3> BlobSharedAccessSignatureBuilder<T1, T2, T3> {
             signed_identifier: self.signed_identifier,
             signed_protocol: Some(signed_protocol),
             signed_ip: self.signed_ip,
+            signed_authorized_user_object_id: self.signed_authorized_user_object_id,
+            signed_unauthorized_user_object_id: self.signed_unauthorized_user_object_id,
+        }
+    }
+    /// Sets the object ID of the user, authorized by the owner of the user delegation key, who
+    /// may use the SAS. Only used by user delegation SAS tokens.
+    pub fn with_authorized_user_object_id(
+        self,
+        object_id: String,
+    ) -> BlobSharedAccessSignatureBuilder<T1, T2, T3> {
+        BlobSharedAccessSignatureBuilder {
+            signed_authorized_user_object_id: Some(object_id),
+            ..self
+        }
+    }
+    /// Sets the object ID of the user, not authorized by the owner of the user delegation key,
+    /// who may use the SAS after a POSIX ACL check. Only used by user delegation SAS tokens.
+    pub fn with_unauthorized_user_object_id(
+        self,
+        object_id: String,
+    ) -> BlobSharedAccessSignatureBuilder<T1, T2, T3> {
+        BlobSharedAccessSignatureBuilder {
+            signed_unauthorized_user_object_id: Some(object_id),
+            ..self
         }
     }
 }
@@ -277,6 +410,7 @@ impl BlobSharedAccessSignatureBuilder<SetPerms, SetResources, SetExpiry> {
     pub fn finalize(self) -> BlobSharedAccessSignature {
         BlobSharedAccessSignature {
             key: self.key.clone(),
+            user_delegation_key: self.user_delegation_key,
             canonicalized_resource: self.canonicalized_resource.clone(),
             signed_permissions: self.signed_permissions.unwrap(),
             signed_resource: self.signed_resource.unwrap(),
@@ -285,6 +419,8 @@ impl BlobSharedAccessSignatureBuilder<SetPerms, SetResources, SetExpiry> {
             signed_identifier: self.signed_identifier,
             signed_protocol: self.signed_protocol,
             signed_ip: self.signed_ip,
+            signed_authorized_user_object_id: self.signed_authorized_user_object_id,
+            signed_unauthorized_user_object_id: self.signed_unauthorized_user_object_id,
         }
     }
 }
@@ -297,6 +433,7 @@ impl<T1, T2> BlobSharedAccessSignatureBuilder<(), T1, T2> {
         BlobSharedAccessSignatureBuilder {
             _phantom: PhantomData,
             key: self.key,
+            user_delegation_key: self.user_delegation_key,
             canonicalized_resource: self.canonicalized_resource,
             signed_permissions: Some(permissions),
             signed_resource: self.signed_resource,
@@ -305,6 +442,8 @@ impl<T1, T2> BlobSharedAccessSignatureBuilder<(), T1, T2> {
             signed_identifier: self.signed_identifier,
             signed_protocol: self.signed_protocol,
             signed_ip: self.signed_ip,
+            signed_authorized_user_object_id: self.signed_authorized_user_object_id,
+            signed_unauthorized_user_object_id: self.signed_unauthorized_user_object_id,
         }
     }
 }
@@ -317,6 +456,7 @@ impl<T1, T2> BlobSharedAccessSignatureBuilder<T1, (), T2> {
         BlobSharedAccessSignatureBuilder {
             _phantom: PhantomData,
             key: self.key,
+            user_delegation_key: self.user_delegation_key,
             canonicalized_resource: self.canonicalized_resource,
             signed_permissions: self.signed_permissions,
             signed_resource: Some(signed_resource),
@@ -325,6 +465,8 @@ impl<T1, T2> BlobSharedAccessSignatureBuilder<T1, (), T2> {
             signed_identifier: self.signed_identifier,
             signed_protocol: self.signed_protocol,
             signed_ip: self.signed_ip,
+            signed_authorized_user_object_id: self.signed_authorized_user_object_id,
+            signed_unauthorized_user_object_id: self.signed_unauthorized_user_object_id,
         }
     }
 }
@@ -337,6 +479,7 @@ impl<T1, T2> BlobSharedAccessSignatureBuilder<T1, T2, ()> {
         BlobSharedAccessSignatureBuilder {
             _phantom: PhantomData,
             key: self.key,
+            user_delegation_key: self.user_delegation_key,
             canonicalized_resource: self.canonicalized_resource,
             signed_permissions: self.signed_permissions,
             signed_resource: self.signed_resource,
@@ -345,6 +488,46 @@ impl<T1, T2> BlobSharedAccessSignatureBuilder<T1, T2, ()> {
             signed_identifier: self.signed_identifier,
             signed_protocol: self.signed_protocol,
             signed_ip: self.signed_ip,
+            signed_authorized_user_object_id: self.signed_authorized_user_object_id,
+            signed_unauthorized_user_object_id: self.signed_unauthorized_user_object_id,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn user_delegation_sas() {
+        let user_delegation_key = UserDelegationKey {
+            signed_oid: "oid".to_owned(),
+            signed_tid: "tid".to_owned(),
+            signed_start: Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap(),
+            signed_expiry: Utc.with_ymd_and_hms(2022, 6, 3, 0, 0, 0).unwrap(),
+            signed_service: "b".to_owned(),
+            signed_version: "2020-06-12".to_owned(),
+            value: base64::encode("key"),
+        };
+        let sas = BlobSharedAccessSignatureBuilder::new_user_delegation(
+            user_delegation_key,
+            "/blob/account/container/blob".to_owned(),
+        )
+        .with_resources(BlobSignedResource::Blob)
+        .with_permissions(BlobSasPermissions {
+            read: true,
+            ..Default::default()
+        })
+        .with_expiry(Utc.with_ymd_and_hms(2022, 6, 2, 0, 0, 0).unwrap())
+        .with_protocol(SasProtocol::Https)
+        .finalize();
+
+        assert_eq!(
+            sas.token(),
+            "sv=2020-06-12&sp=r&sr=b&se=2022-06-02T00%3A00%3A00Z&spr=https&skoid=oid&sktid=tid\
+            &skt=2022-06-01T00%3A00%3A00Z&ske=2022-06-03T00%3A00%3A00Z&sks=b&skv=2020-06-12\
+            &sig=iDu3vkKYk4hHBoS8PxU1J3kxV63ZcFDG7Fl7irhb6bw%3D"
+        );
+    }
+}
@@ -9,6 +9,22 @@ pub fn read_xml<'de, T: serde::de::Deserialize<'de>>(body: &[u8]) -> azure_core:
         .context(ErrorKind::DataConversion, "failed to deserialize xml")
 }
 
+/// Escapes the characters that cannot appear as is in XML text or attribute values.
+pub fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 /// Returns bytes without the UTF-8 BOM.
 fn slice_bom(bytes: &[u8]) -> &[u8] {
     if bytes.len() > 3 && bytes[0..3] == UTF8_BOM {
@@ -30,4 +46,13 @@ mod test {
         let bytes = &[8];
         assert_eq!(&[8], slice_bom(bytes));
     }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            "a&amp;b &lt;c&gt; &quot;d&apos;",
+            escape_xml("a&b <c> \"d'")
+        );
+        assert_eq!("plain", escape_xml("plain"));
+    }
 }
@@ -0,0 +1,62 @@
+use azure_storage::core::prelude::*;
+use azure_storage_blobs::{
+    prelude::*,
+    service::{BlobBatch, MAX_BATCH_OPERATIONS},
+};
+use futures::StreamExt;
+use std::num::NonZeroU32;
+
+#[tokio::main]
+async fn main() -> azure_core::Result<()> {
+    // First we retrieve the account name and access key from environment variables.
+    let account =
+        std::env::var("STORAGE_ACCOUNT").expect("Set env variable STORAGE_ACCOUNT first!");
+    let access_key =
+        std::env::var("STORAGE_ACCESS_KEY").expect("Set env variable STORAGE_ACCESS_KEY first!");
+
+    let container = std::env::args()
+        .nth(1)
+        .expect("please specify container name as command line parameter");
+    let prefix = std::env::args()
+        .nth(2)
+        .expect("please specify the prefix of the blobs to delete as command line parameter");
+
+    let http_client = azure_core::new_http_client();
+    let container_client =
+        StorageAccountClient::new_access_key(http_client.clone(), &account, &access_key)
+            .storage_client()
+            .container_client(&container);
+
+    // Each page of the listing is deleted with a single batch request.
+    let mut pages = container_client
+        .list_blobs()
+        .prefix(prefix)
+        .max_results(NonZeroU32::new(MAX_BATCH_OPERATIONS as u32).unwrap())
+        .into_stream();
+    while let Some(page) = pages.next().await {
+        let page = page?;
+        if page.blobs.blobs.is_empty() {
+            continue;
+        }
+
+        let mut batch = BlobBatch::new();
+        for blob in page.blobs.blobs.iter() {
+            batch.add(
+                container_client
+                    .blob_client(&blob.name)
+                    .delete()
+                    .to_batch_operation()?,
+            );
+        }
+
+        let response = container_client.submit_batch(batch).into_future().await?;
+        for (blob, item) in page.blobs.blobs.iter().zip(response.items) {
+            match item.error_code {
+                None => println!("deleted {}", blob.name),
+                Some(error_code) => println!("failed to delete {}: {}", blob.name, error_code),
+            }
+        }
+    }
+
+    Ok(())
+}
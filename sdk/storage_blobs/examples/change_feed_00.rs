@@ -0,0 +1,49 @@
+use azure_storage::core::prelude::*;
+use azure_storage_blobs::{prelude::*, service::ChangeFeedCursor};
+use chrono::{Duration, Utc};
+use futures::StreamExt;
+
+#[tokio::main]
+async fn main() -> azure_core::Result<()> {
+    // First we retrieve the account name and access key from environment variables.
+    let account =
+        std::env::var("STORAGE_ACCOUNT").expect("Set env variable STORAGE_ACCOUNT first!");
+    let access_key =
+        std::env::var("STORAGE_ACCESS_KEY").expect("Set env variable STORAGE_ACCESS_KEY first!");
+
+    // The cursor printed by a previous run can be passed to resume from there.
+    let cursor = std::env::args()
+        .nth(1)
+        .map(|cursor| serde_json::from_str::<ChangeFeedCursor>(&cursor).expect("invalid cursor"));
+
+    let http_client = azure_core::new_http_client();
+    let blob_service_client =
+        StorageAccountClient::new_access_key(http_client.clone(), &account, &access_key)
+            .blob_service_client();
+
+    let mut change_feed = blob_service_client
+        .get_change_feed()
+        .start_time(Utc::now() - Duration::days(1));
+    if let Some(cursor) = cursor {
+        change_feed = change_feed.cursor(cursor);
+    }
+
+    let mut pages = change_feed.into_stream();
+    let mut cursor = None;
+    while let Some(page) = pages.next().await {
+        let page = page?;
+        for event in page.events {
+            println!(
+                "{} {:?} {}",
+                event.event_time, event.event_type, event.subject
+            );
+        }
+        cursor = Some(page.cursor);
+    }
+
+    if let Some(cursor) = cursor {
+        println!("cursor: {}", serde_json::to_string(&cursor).unwrap());
+    }
+
+    Ok(())
+}
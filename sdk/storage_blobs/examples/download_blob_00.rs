@@ -0,0 +1,39 @@
+use azure_storage::core::prelude::*;
+use azure_storage_blobs::prelude::*;
+
+#[tokio::main]
+async fn main() -> azure_core::Result<()> {
+    // First we retrieve the account name and access key from environment variables.
+    let account =
+        std::env::var("STORAGE_ACCOUNT").expect("Set env variable STORAGE_ACCOUNT first!");
+    let access_key =
+        std::env::var("STORAGE_ACCESS_KEY").expect("Set env variable STORAGE_ACCESS_KEY first!");
+
+    let container = std::env::args()
+        .nth(1)
+        .expect("please specify container name as command line parameter");
+    let blob = std::env::args()
+        .nth(2)
+        .expect("please specify blob name as command line parameter");
+    let path = std::env::args()
+        .nth(3)
+        .expect("please specify the destination file as command line parameter");
+
+    let http_client = azure_core::new_http_client();
+    let blob_client =
+        StorageAccountClient::new_access_key(http_client.clone(), &account, &access_key)
+            .storage_client()
+            .container_client(&container)
+            .blob_client(&blob);
+
+    // Fetch 16 ranges of 4MB at a time, checking the CRC64 of each one.
+    let response = blob_client
+        .download()
+        .parallelism(16usize)
+        .validation(RangeValidation::CRC64)
+        .into_file(&path)
+        .await?;
+    println!("downloaded {} bytes to {}", response.content_length, path);
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use azure_storage::core::prelude::*;
+use azure_storage_blobs::prelude::*;
+
+#[tokio::main]
+async fn main() -> azure_core::Result<()> {
+    // First we retrieve the account name and access key from environment variables.
+    let account =
+        std::env::var("STORAGE_ACCOUNT").expect("Set env variable STORAGE_ACCOUNT first!");
+    let access_key =
+        std::env::var("STORAGE_ACCESS_KEY").expect("Set env variable STORAGE_ACCESS_KEY first!");
+
+    let container = std::env::args()
+        .nth(1)
+        .expect("please specify container name as command line parameter");
+    let blob = std::env::args()
+        .nth(2)
+        .expect("please specify blob name as command line parameter");
+    let path = std::env::args()
+        .nth(3)
+        .expect("please specify the file to upload as command line parameter");
+
+    let http_client = azure_core::new_http_client();
+    let blob_client =
+        StorageAccountClient::new_access_key(http_client.clone(), &account, &access_key)
+            .storage_client()
+            .container_client(&container)
+            .blob_client(&blob);
+
+    let file = std::fs::File::open(&path).expect("cannot open the file to upload");
+    let content_length = file.metadata().expect("cannot read the file size").len();
+
+    // The block size is picked from the content length, and blocks are staged 16 at a time.
+    let upload = blob_client
+        .upload()
+        .content_length(content_length)
+        .parallelism(16usize)
+        .content_type("application/octet-stream")
+        .progress(move |uploaded| println!("uploaded {}/{} bytes", uploaded, content_length))
+        .from_reader(futures::io::AllowStdIo::new(file));
+    let response = tokio::spawn(upload).await.unwrap()?;
+    println!("uploaded {} with etag {}", path, response.etag);
+
+    Ok(())
+}
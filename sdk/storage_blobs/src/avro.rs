@@ -0,0 +1,857 @@
+//! A reader of [Avro object container files](https://avro.apache.org/docs/1.10.2/spec.html#Object+Container+Files),
+//! the format of the responses of Query Blob Contents and of the change feed segments.
+//!
+//! Only the `null` codec is supported, which is the one the service uses. The records are decoded
+//! with the writer schema into [`AvroValue`]s.
+
+use azure_core::error::{Error, ErrorKind, ResultExt};
+use bytes::{Buf, Bytes, BytesMut};
+use futures::{Stream, StreamExt};
+use std::collections::{HashMap, VecDeque};
+
+const MAGIC: &[u8] = b"Obj\x01";
+const SYNC_MARKER_LEN: usize = 16;
+
+/// A decoded Avro datum. Unions decode to the value of the branch they hold.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum AvroValue {
+    Null,
+    Boolean(bool),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    Bytes(Vec<u8>),
+    String(String),
+    Record(AvroRecord),
+    Enum(String),
+    Array(Vec<AvroValue>),
+    Map(HashMap<String, AvroValue>),
+    Fixed(Vec<u8>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct AvroRecord {
+    /// The full name of the record schema, including its namespace.
+    pub name: String,
+    pub fields: HashMap<String, AvroValue>,
+}
+
+impl AvroRecord {
+    pub fn get(&self, field: &str) -> azure_core::Result<&AvroValue> {
+        self.fields.get(field).ok_or_else(|| {
+            Error::with_message(ErrorKind::DataConversion, || {
+                format!("avro record {} has no field {}", self.name, field)
+            })
+        })
+    }
+
+    /// Returns `None` when the field is missing or `null`.
+    pub fn get_optional(&self, field: &str) -> Option<&AvroValue> {
+        match self.fields.get(field) {
+            None | Some(AvroValue::Null) => None,
+            Some(value) => Some(value),
+        }
+    }
+
+    pub fn get_long(&self, field: &str) -> azure_core::Result<i64> {
+        self.get(field).and_then(|value| self.as_long(field, value))
+    }
+
+    pub fn get_optional_long(&self, field: &str) -> azure_core::Result<Option<i64>> {
+        self.get_optional(field)
+            .map(|value| self.as_long(field, value))
+            .transpose()
+    }
+
+    fn as_long(&self, field: &str, value: &AvroValue) -> azure_core::Result<i64> {
+        match value {
+            AvroValue::Long(value) => Ok(*value),
+            AvroValue::Int(value) => Ok(*value as i64),
+            value => Err(unexpected_value(&self.name, field, "long", value)),
+        }
+    }
+
+    pub fn get_bool(&self, field: &str) -> azure_core::Result<bool> {
+        self.get(field).and_then(|value| self.as_bool(field, value))
+    }
+
+    pub fn get_optional_bool(&self, field: &str) -> azure_core::Result<Option<bool>> {
+        self.get_optional(field)
+            .map(|value| self.as_bool(field, value))
+            .transpose()
+    }
+
+    fn as_bool(&self, field: &str, value: &AvroValue) -> azure_core::Result<bool> {
+        match value {
+            AvroValue::Boolean(value) => Ok(*value),
+            value => Err(unexpected_value(&self.name, field, "boolean", value)),
+        }
+    }
+
+    /// Enum symbols are returned as strings.
+    pub fn get_str(&self, field: &str) -> azure_core::Result<&str> {
+        self.get(field).and_then(|value| self.as_str(field, value))
+    }
+
+    pub fn get_optional_str(&self, field: &str) -> azure_core::Result<Option<&str>> {
+        self.get_optional(field)
+            .map(|value| self.as_str(field, value))
+            .transpose()
+    }
+
+    fn as_str<'a>(&self, field: &str, value: &'a AvroValue) -> azure_core::Result<&'a str> {
+        match value {
+            AvroValue::String(value) | AvroValue::Enum(value) => Ok(value),
+            value => Err(unexpected_value(&self.name, field, "string", value)),
+        }
+    }
+
+    pub fn get_bytes(&self, field: &str) -> azure_core::Result<&[u8]> {
+        match self.get(field)? {
+            AvroValue::Bytes(value) => Ok(value),
+            value => Err(unexpected_value(&self.name, field, "bytes", value)),
+        }
+    }
+
+    pub fn get_record(&self, field: &str) -> azure_core::Result<&AvroRecord> {
+        match self.get(field)? {
+            AvroValue::Record(value) => Ok(value),
+            value => Err(unexpected_value(&self.name, field, "record", value)),
+        }
+    }
+}
+
+fn unexpected_value(record: &str, field: &str, expected: &str, value: &AvroValue) -> Error {
+    Error::with_message(ErrorKind::DataConversion, || {
+        format!(
+            "avro field {}.{} should be a {}, but it is {:?}",
+            record, field, expected, value
+        )
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Schema {
+    Null,
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    Bytes,
+    String,
+    Record {
+        name: String,
+        fields: Vec<(String, Schema)>,
+    },
+    Enum {
+        symbols: Vec<String>,
+    },
+    Array(Box<Schema>),
+    Map(Box<Schema>),
+    Union(Vec<Schema>),
+    Fixed {
+        size: usize,
+    },
+    /// A reference to a named type defined earlier in the schema.
+    Named(String),
+}
+
+/// The writer schema with its named types.
+#[derive(Debug, Clone)]
+struct Schemata {
+    root: Schema,
+    named: HashMap<String, Schema>,
+}
+
+impl Schemata {
+    fn parse(json: &[u8]) -> azure_core::Result<Self> {
+        let json: serde_json::Value = serde_json::from_slice(json)?;
+        let mut named = HashMap::new();
+        let root = parse_schema(&json, None, &mut named)?;
+        Ok(Self { root, named })
+    }
+}
+
+fn invalid_schema(json: &serde_json::Value) -> Error {
+    Error::with_message(ErrorKind::DataConversion, || {
+        format!("invalid avro schema {}", json)
+    })
+}
+
+fn full_name(name: &str, namespace: Option<&str>) -> String {
+    match namespace {
+        Some(namespace) if !name.contains('.') && !namespace.is_empty() => {
+            format!("{}.{}", namespace, name)
+        }
+        _ => name.to_owned(),
+    }
+}
+
+fn parse_schema(
+    json: &serde_json::Value,
+    namespace: Option<&str>,
+    named: &mut HashMap<String, Schema>,
+) -> azure_core::Result<Schema> {
+    match json {
+        serde_json::Value::String(name) => Ok(match name.as_str() {
+            "null" => Schema::Null,
+            "boolean" => Schema::Boolean,
+            "int" => Schema::Int,
+            "long" => Schema::Long,
+            "float" => Schema::Float,
+            "double" => Schema::Double,
+            "bytes" => Schema::Bytes,
+            "string" => Schema::String,
+            name => {
+                let name = if named.contains_key(name) {
+                    name.to_owned()
+                } else {
+                    full_name(name, namespace)
+                };
+                if !named.contains_key(&name) {
+                    return Err(Error::with_message(ErrorKind::DataConversion, || {
+                        format!("unknown avro type {}", name)
+                    }));
+                }
+                Schema::Named(name)
+            }
+        }),
+        serde_json::Value::Array(branches) => Ok(Schema::Union(
+            branches
+                .iter()
+                .map(|branch| parse_schema(branch, namespace, named))
+                .collect::<azure_core::Result<_>>()?,
+        )),
+        serde_json::Value::Object(object) => {
+            let type_ = object.get("type").ok_or_else(|| invalid_schema(json))?;
+            let type_name = match type_ {
+                serde_json::Value::String(type_name) => type_name.as_str(),
+                // a nested definition such as `{"type": {"type": "array", ...}}`
+                _ => return parse_schema(type_, namespace, named),
+            };
+
+            let name = || -> azure_core::Result<String> {
+                let name = object
+                    .get("name")
+                    .and_then(|name| name.as_str())
+                    .ok_or_else(|| invalid_schema(json))?;
+                let namespace = object
+                    .get("namespace")
+                    .and_then(|namespace| namespace.as_str())
+                    .or(namespace);
+                Ok(full_name(name, namespace))
+            };
+
+            let schema = match type_name {
+                "record" | "error" => {
+                    let name = name()?;
+                    let namespace = name.rsplit_once('.').map(|(namespace, _)| namespace);
+                    // a placeholder makes recursive references resolvable
+                    named.insert(
+                        name.clone(),
+                        Schema::Record {
+                            name: name.clone(),
+                            fields: Vec::new(),
+                        },
+                    );
+                    let fields = object
+                        .get("fields")
+                        .and_then(|fields| fields.as_array())
+                        .ok_or_else(|| invalid_schema(json))?
+                        .iter()
+                        .map(|field| {
+                            let field_name = field
+                                .get("name")
+                                .and_then(|name| name.as_str())
+                                .ok_or_else(|| invalid_schema(field))?;
+                            let field_type =
+                                field.get("type").ok_or_else(|| invalid_schema(field))?;
+                            Ok((
+                                field_name.to_owned(),
+                                parse_schema(field_type, namespace, named)?,
+                            ))
+                        })
+                        .collect::<azure_core::Result<_>>()?;
+                    let schema = Schema::Record {
+                        name: name.clone(),
+                        fields,
+                    };
+                    named.insert(name, schema.clone());
+                    schema
+                }
+                "enum" => {
+                    let name = name()?;
+                    let symbols = object
+                        .get("symbols")
+                        .and_then(|symbols| symbols.as_array())
+                        .ok_or_else(|| invalid_schema(json))?
+                        .iter()
+                        .map(|symbol| {
+                            symbol
+                                .as_str()
+                                .map(ToOwned::to_owned)
+                                .ok_or_else(|| invalid_schema(json))
+                        })
+                        .collect::<azure_core::Result<_>>()?;
+                    let schema = Schema::Enum { symbols };
+                    named.insert(name, schema.clone());
+                    schema
+                }
+                "fixed" => {
+                    let name = name()?;
+                    let size = object
+                        .get("size")
+                        .and_then(|size| size.as_u64())
+                        .ok_or_else(|| invalid_schema(json))?;
+                    let schema = Schema::Fixed {
+                        size: size as usize,
+                    };
+                    named.insert(name, schema.clone());
+                    schema
+                }
+                "array" => Schema::Array(Box::new(parse_schema(
+                    object.get("items").ok_or_else(|| invalid_schema(json))?,
+                    namespace,
+                    named,
+                )?)),
+                "map" => Schema::Map(Box::new(parse_schema(
+                    object.get("values").ok_or_else(|| invalid_schema(json))?,
+                    namespace,
+                    named,
+                )?)),
+                // a primitive type, possibly annotated with a logical type
+                _ => parse_schema(type_, namespace, named)?,
+            };
+            Ok(schema)
+        }
+        _ => Err(invalid_schema(json)),
+    }
+}
+
+fn unexpected_end() -> Error {
+    Error::message(ErrorKind::DataConversion, "unexpected end of avro data")
+}
+
+fn read_exact<'a>(buf: &mut &'a [u8], len: usize) -> azure_core::Result<&'a [u8]> {
+    if buf.len() < len {
+        return Err(unexpected_end());
+    }
+    let (value, rest) = buf.split_at(len);
+    *buf = rest;
+    Ok(value)
+}
+
+/// Reads a zigzag encoded variable length integer.
+fn read_long(buf: &mut &[u8]) -> azure_core::Result<i64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = read_exact(buf, 1)?[0];
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok((value >> 1) as i64 ^ -((value & 1) as i64));
+        }
+    }
+    Err(Error::message(
+        ErrorKind::DataConversion,
+        "avro variable length integer is too long",
+    ))
+}
+
+fn read_len(buf: &mut &[u8]) -> azure_core::Result<usize> {
+    let len = read_long(buf)?;
+    if len < 0 {
+        return Err(Error::with_message(ErrorKind::DataConversion, || {
+            format!("negative avro length {}", len)
+        }));
+    }
+    Ok(len as usize)
+}
+
+fn read_bytes(buf: &mut &[u8]) -> azure_core::Result<Vec<u8>> {
+    let len = read_len(buf)?;
+    Ok(read_exact(buf, len)?.to_vec())
+}
+
+fn read_string(buf: &mut &[u8]) -> azure_core::Result<String> {
+    String::from_utf8(read_bytes(buf)?)
+        .context(ErrorKind::DataConversion, "avro string is not valid UTF-8")
+}
+
+/// Reads the item count of a block of an array or a map. A negative count is followed by the
+/// size of the block in bytes, which is not needed here.
+fn read_block_count(buf: &mut &[u8]) -> azure_core::Result<usize> {
+    let count = read_long(buf)?;
+    if count < 0 {
+        read_long(buf)?;
+    }
+    Ok(count.unsigned_abs() as usize)
+}
+
+fn read_value(
+    buf: &mut &[u8],
+    schema: &Schema,
+    schemata: &Schemata,
+) -> azure_core::Result<AvroValue> {
+    Ok(match schema {
+        Schema::Null => AvroValue::Null,
+        Schema::Boolean => AvroValue::Boolean(read_exact(buf, 1)?[0] != 0),
+        Schema::Int => AvroValue::Int(read_long(buf)? as i32),
+        Schema::Long => AvroValue::Long(read_long(buf)?),
+        Schema::Float => AvroValue::Float(read_exact(buf, 4)?.get_f32_le()),
+        Schema::Double => AvroValue::Double(read_exact(buf, 8)?.get_f64_le()),
+        Schema::Bytes => AvroValue::Bytes(read_bytes(buf)?),
+        Schema::String => AvroValue::String(read_string(buf)?),
+        Schema::Record { name, fields } => AvroValue::Record(AvroRecord {
+            name: name.clone(),
+            fields: fields
+                .iter()
+                .map(|(field_name, field_schema)| {
+                    Ok((field_name.clone(), read_value(buf, field_schema, schemata)?))
+                })
+                .collect::<azure_core::Result<_>>()?,
+        }),
+        Schema::Enum { symbols } => {
+            let index = read_long(buf)?;
+            let symbol = usize::try_from(index)
+                .ok()
+                .and_then(|index| symbols.get(index))
+                .ok_or_else(|| {
+                    Error::with_message(ErrorKind::DataConversion, || {
+                        format!("avro enum index {} out of range", index)
+                    })
+                })?;
+            AvroValue::Enum(symbol.clone())
+        }
+        Schema::Array(items) => {
+            let mut values = Vec::new();
+            loop {
+                let count = read_block_count(buf)?;
+                if count == 0 {
+                    break;
+                }
+                for _ in 0..count {
+                    values.push(read_value(buf, items, schemata)?);
+                }
+            }
+            AvroValue::Array(values)
+        }
+        Schema::Map(values) => {
+            let mut map = HashMap::new();
+            loop {
+                let count = read_block_count(buf)?;
+                if count == 0 {
+                    break;
+                }
+                for _ in 0..count {
+                    let key = read_string(buf)?;
+                    map.insert(key, read_value(buf, values, schemata)?);
+                }
+            }
+            AvroValue::Map(map)
+        }
+        Schema::Union(branches) => {
+            let index = read_long(buf)?;
+            let branch = usize::try_from(index)
+                .ok()
+                .and_then(|index| branches.get(index))
+                .ok_or_else(|| {
+                    Error::with_message(ErrorKind::DataConversion, || {
+                        format!("avro union index {} out of range", index)
+                    })
+                })?;
+            read_value(buf, branch, schemata)?
+        }
+        Schema::Fixed { size } => AvroValue::Fixed(read_exact(buf, *size)?.to_vec()),
+        Schema::Named(name) => {
+            // `parse_schema` only creates references to types it has registered
+            let schema = &schemata.named[name];
+            read_value(buf, schema, schemata)?
+        }
+    })
+}
+
+/// Reads a variable length integer without decoding it, returning `None` if `buf` ends first.
+fn skip_long(buf: &mut &[u8]) -> Option<i64> {
+    let mut value = 0u64;
+    for (i, byte) in buf.iter().enumerate().take(10) {
+        value |= ((byte & 0x7f) as u64) << (7 * i);
+        if byte & 0x80 == 0 {
+            *buf = &buf[i + 1..];
+            return Some((value >> 1) as i64 ^ -((value & 1) as i64));
+        }
+    }
+    None
+}
+
+fn skip(buf: &mut &[u8], len: i64) -> Option<()> {
+    let len = usize::try_from(len).ok()?;
+    *buf = buf.get(len..)?;
+    Some(())
+}
+
+/// The length of the file header at the start of `buf`, or `None` if `buf` doesn't hold all of
+/// it yet.
+fn header_len(buf: &[u8]) -> Option<usize> {
+    let mut rest = buf.get(MAGIC.len()..)?;
+    loop {
+        let count = skip_long(&mut rest)?;
+        if count == 0 {
+            break;
+        }
+        if count < 0 {
+            skip_long(&mut rest)?;
+        }
+        for _ in 0..count.unsigned_abs() {
+            let key_len = skip_long(&mut rest)?;
+            skip(&mut rest, key_len)?;
+            let value_len = skip_long(&mut rest)?;
+            skip(&mut rest, value_len)?;
+        }
+    }
+    skip(&mut rest, SYNC_MARKER_LEN as i64)?;
+    Some(buf.len() - rest.len())
+}
+
+/// The length of the data block at the start of `buf`, including its sync marker, or `None` if
+/// `buf` doesn't hold all of it yet.
+fn block_len(buf: &[u8]) -> Option<usize> {
+    let mut rest = buf;
+    skip_long(&mut rest)?;
+    let size = skip_long(&mut rest)?;
+    skip(&mut rest, size)?;
+    skip(&mut rest, SYNC_MARKER_LEN as i64)?;
+    Some(buf.len() - rest.len())
+}
+
+#[derive(Debug, Clone)]
+struct Header {
+    schemata: Schemata,
+    sync_marker: Vec<u8>,
+}
+
+fn read_header(mut buf: &[u8]) -> azure_core::Result<Header> {
+    if read_exact(&mut buf, MAGIC.len())? != MAGIC {
+        return Err(Error::message(
+            ErrorKind::DataConversion,
+            "the data is not an avro object container file",
+        ));
+    }
+
+    let mut metadata = HashMap::new();
+    loop {
+        let count = read_block_count(&mut buf)?;
+        if count == 0 {
+            break;
+        }
+        for _ in 0..count {
+            let key = read_string(&mut buf)?;
+            metadata.insert(key, read_bytes(&mut buf)?);
+        }
+    }
+
+    match metadata.get("avro.codec").map(Vec::as_slice) {
+        None | Some(b"null") => {}
+        Some(codec) => {
+            return Err(Error::with_message(ErrorKind::DataConversion, || {
+                format!("unsupported avro codec {}", String::from_utf8_lossy(codec))
+            }))
+        }
+    }
+    let schema = metadata.get("avro.schema").ok_or_else(|| {
+        Error::message(ErrorKind::DataConversion, "avro file without avro.schema")
+    })?;
+
+    Ok(Header {
+        schemata: Schemata::parse(schema)?,
+        sync_marker: read_exact(&mut buf, SYNC_MARKER_LEN)?.to_vec(),
+    })
+}
+
+/// Decodes the records of an object container file as its bytes arrive.
+///
+/// A data block is only decoded once it has been entirely received.
+pub(crate) struct AvroReader<S> {
+    stream: S,
+    buffer: BytesMut,
+    header: Option<Header>,
+    values: VecDeque<AvroValue>,
+    stream_ended: bool,
+}
+
+impl<S> AvroReader<S>
+where
+    S: Stream<Item = azure_core::Result<Bytes>> + Unpin,
+{
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            buffer: BytesMut::new(),
+            header: None,
+            values: VecDeque::new(),
+            stream_ended: false,
+        }
+    }
+
+    /// Returns the next record, or `None` at the end of the file.
+    pub async fn next(&mut self) -> azure_core::Result<Option<AvroValue>> {
+        loop {
+            if let Some(value) = self.values.pop_front() {
+                return Ok(Some(value));
+            }
+
+            match &self.header {
+                None => {
+                    if let Some(len) = header_len(&self.buffer) {
+                        self.header = Some(read_header(&self.buffer[..len])?);
+                        self.buffer.advance(len);
+                        continue;
+                    }
+                }
+                Some(header) => {
+                    if let Some(len) = block_len(&self.buffer) {
+                        let block = self.buffer.split_to(len).freeze();
+                        self.values = read_block(&block, header)?;
+                        continue;
+                    }
+                }
+            }
+
+            if self.stream_ended {
+                return if self.header.is_some() && self.buffer.is_empty() {
+                    Ok(None)
+                } else {
+                    Err(unexpected_end())
+                };
+            }
+            match self.stream.next().await {
+                Some(bytes) => self.buffer.extend_from_slice(&bytes?),
+                None => self.stream_ended = true,
+            }
+        }
+    }
+
+    /// Turns the reader into a stream of records.
+    pub fn into_stream(self) -> impl Stream<Item = azure_core::Result<AvroValue>> {
+        futures::stream::try_unfold(self, |mut reader| async move {
+            Ok(reader.next().await?.map(|value| (value, reader)))
+        })
+    }
+}
+
+fn read_block(mut buf: &[u8], header: &Header) -> azure_core::Result<VecDeque<AvroValue>> {
+    let count = read_len(&mut buf)?;
+    let size = read_len(&mut buf)?;
+    let mut data = read_exact(&mut buf, size)?;
+    if buf != header.sync_marker.as_slice() {
+        return Err(Error::message(
+            ErrorKind::DataConversion,
+            "avro block does not end with the sync marker of the file",
+        ));
+    }
+
+    // the count is untrusted, while each value takes at least a byte unless it's null
+    let mut values = VecDeque::with_capacity(count.min(size));
+    for _ in 0..count {
+        values.push_back(read_value(
+            &mut data,
+            &header.schemata.root,
+            &header.schemata,
+        )?);
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn write_long(buf: &mut Vec<u8>, value: i64) {
+        let mut value = ((value << 1) ^ (value >> 63)) as u64;
+        while value >= 0x80 {
+            buf.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        buf.push(value as u8);
+    }
+
+    pub(crate) fn write_bytes(buf: &mut Vec<u8>, value: &[u8]) {
+        write_long(buf, value.len() as i64);
+        buf.extend_from_slice(value);
+    }
+
+    /// Writes an object container file with the given schema and a block per item of `blocks`,
+    /// each holding already encoded records.
+    pub(crate) fn write_file(schema: &str, blocks: &[(i64, Vec<u8>)]) -> Vec<u8> {
+        let sync_marker = b"0123456789abcdef";
+        let mut file = MAGIC.to_vec();
+        write_long(&mut file, 2);
+        write_bytes(&mut file, b"avro.schema");
+        write_bytes(&mut file, schema.as_bytes());
+        write_bytes(&mut file, b"avro.codec");
+        write_bytes(&mut file, b"null");
+        write_long(&mut file, 0);
+        file.extend_from_slice(sync_marker);
+        for (count, data) in blocks {
+            write_long(&mut file, *count);
+            write_bytes(&mut file, data);
+            file.extend_from_slice(sync_marker);
+        }
+        file
+    }
+
+    /// Reads `file` from a stream of chunks of `chunk_size` bytes.
+    pub(crate) async fn read_file(
+        file: &[u8],
+        chunk_size: usize,
+    ) -> azure_core::Result<Vec<AvroValue>> {
+        let chunks: Vec<azure_core::Result<Bytes>> = file
+            .chunks(chunk_size)
+            .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+            .collect();
+        let mut reader = AvroReader::new(futures::stream::iter(chunks));
+        let mut values = Vec::new();
+        while let Some(value) = reader.next().await? {
+            values.push(value);
+        }
+        Ok(values)
+    }
+
+    #[tokio::test]
+    async fn test_huge_block_count() {
+        let mut data = Vec::new();
+        write_long(&mut data, 1);
+        let file = write_file(r#""long""#, &[(1, data.clone())]);
+        assert_eq!(
+            read_file(&file, file.len()).await.unwrap(),
+            vec![AvroValue::Long(1)]
+        );
+
+        let file = write_file(r#""long""#, &[(i64::MAX, data)]);
+        assert!(read_file(&file, file.len()).await.is_err());
+    }
+
+    #[test]
+    fn test_read_long() {
+        for value in [0, -1, 1, -64, 64, i32::MAX as i64, i64::MIN, i64::MAX] {
+            let mut buf = Vec::new();
+            write_long(&mut buf, value);
+            assert_eq!(read_long(&mut buf.as_slice()).unwrap(), value);
+            assert_eq!(skip_long(&mut buf.as_slice()), Some(value));
+        }
+        assert_eq!(skip_long(&mut [0x80u8].as_slice()), None);
+    }
+
+    #[tokio::test]
+    async fn test_read_file() {
+        let schema = r#"{
+            "type": "record",
+            "name": "Event",
+            "namespace": "com.contoso",
+            "fields": [
+                {"name": "id", "type": "long"},
+                {"name": "subject", "type": ["null", "string"]},
+                {"name": "kind", "type": {"type": "enum", "name": "Kind", "symbols": ["Created", "Deleted"]}},
+                {"name": "tags", "type": {"type": "map", "values": "string"}},
+                {"name": "sizes", "type": {"type": "array", "items": "int"}},
+                {"name": "previous", "type": ["null", "Kind"]},
+                {"name": "ratio", "type": "double"},
+                {"name": "processed", "type": "boolean"}
+            ]
+        }"#;
+
+        let mut first = Vec::new();
+        write_long(&mut first, 1);
+        write_long(&mut first, 1);
+        write_bytes(&mut first, b"blob.txt");
+        write_long(&mut first, 0);
+        write_long(&mut first, 1);
+        write_bytes(&mut first, b"owner");
+        write_bytes(&mut first, b"contoso");
+        write_long(&mut first, 0);
+        write_long(&mut first, -2);
+        write_long(&mut first, 2);
+        write_long(&mut first, 10);
+        write_long(&mut first, 20);
+        write_long(&mut first, 0);
+        write_long(&mut first, 0);
+        first.extend_from_slice(&0.5f64.to_le_bytes());
+        first.push(1);
+
+        let mut second = Vec::new();
+        write_long(&mut second, 2);
+        write_long(&mut second, 0);
+        write_long(&mut second, 1);
+        write_long(&mut second, 0);
+        write_long(&mut second, 0);
+        write_long(&mut second, 1);
+        write_long(&mut second, 0);
+        second.extend_from_slice(&1.5f64.to_le_bytes());
+        second.push(0);
+
+        let file = write_file(schema, &[(1, first), (1, second)]);
+
+        for chunk_size in [1, 7, file.len()] {
+            let values = read_file(&file, chunk_size).await.unwrap();
+            assert_eq!(values.len(), 2);
+
+            let first = match &values[0] {
+                AvroValue::Record(record) => record,
+                value => panic!("unexpected value {:?}", value),
+            };
+            assert_eq!(first.name, "com.contoso.Event");
+            assert_eq!(first.get_long("id").unwrap(), 1);
+            assert_eq!(
+                first.get("subject").unwrap(),
+                &AvroValue::String("blob.txt".to_owned())
+            );
+            assert_eq!(
+                first.get("kind").unwrap(),
+                &AvroValue::Enum("Created".to_owned())
+            );
+            assert_eq!(
+                first.get("tags").unwrap(),
+                &AvroValue::Map(HashMap::from([(
+                    "owner".to_owned(),
+                    AvroValue::String("contoso".to_owned())
+                )]))
+            );
+            assert_eq!(
+                first.get("sizes").unwrap(),
+                &AvroValue::Array(vec![AvroValue::Int(10), AvroValue::Int(20)])
+            );
+            assert_eq!(first.get("previous").unwrap(), &AvroValue::Null);
+            assert_eq!(first.get("ratio").unwrap(), &AvroValue::Double(0.5));
+            assert!(first.get_bool("processed").unwrap());
+
+            let second = match &values[1] {
+                AvroValue::Record(record) => record,
+                value => panic!("unexpected value {:?}", value),
+            };
+            assert_eq!(second.get("subject").unwrap(), &AvroValue::Null);
+            assert_eq!(
+                second.get("kind").unwrap(),
+                &AvroValue::Enum("Deleted".to_owned())
+            );
+            assert_eq!(
+                second.get("previous").unwrap(),
+                &AvroValue::Enum("Created".to_owned())
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_truncated_file() {
+        let mut record = Vec::new();
+        write_long(&mut record, 42);
+        let file = write_file(r#""long""#, &[(1, record)]);
+
+        assert_eq!(
+            read_file(&file, 3).await.unwrap(),
+            vec![AvroValue::Long(42)]
+        );
+        assert!(read_file(&file[..file.len() - 1], 3).await.is_err());
+        assert!(read_file(b"Obj\x02", 3).await.is_err());
+    }
+}
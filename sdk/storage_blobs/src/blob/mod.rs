@@ -21,11 +21,11 @@ use crate::AccessTier;
 use azure_core::{
     content_type,
     error::{ErrorKind, ResultExt},
-    headers::{self, Headers},
+    headers::{self, Header, Headers},
     parsing::from_azure_time,
     Etag, LeaseDuration, LeaseState, LeaseStatus,
 };
-use azure_storage::{ConsistencyCRC64, ConsistencyMD5, CopyId, CopyProgress};
+use azure_storage::{xml::escape_xml, ConsistencyCRC64, ConsistencyMD5, CopyId, CopyProgress};
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 
@@ -106,9 +106,92 @@ pub struct Tags {
     pub tag_set: Option<TagSet>,
 }
 
+impl Tags {
+    pub fn new() -> Self {
+        Self {
+            tag_set: Some(TagSet { tag: Vec::new() }),
+        }
+    }
+
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.tag_set
+            .get_or_insert_with(|| TagSet { tag: Vec::new() })
+            .tag
+            .push(Tag {
+                key: key.into(),
+                value: value.into(),
+            });
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Tag> {
+        self.tag_set.iter().flat_map(|tag_set| tag_set.tag.iter())
+    }
+
+    pub fn to_xml(&self) -> String {
+        let mut s = String::new();
+        s.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<Tags><TagSet>");
+        for tag in self.iter() {
+            s.push_str(&format!(
+                "<Tag><Key>{}</Key><Value>{}</Value></Tag>",
+                escape_xml(&tag.key),
+                escape_xml(&tag.value)
+            ));
+        }
+        s.push_str("</TagSet></Tags>");
+        s
+    }
+}
+
+impl Default for Tags {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for Tags
+where
+    K: Into<String>,
+    V: Into<String>,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut tags = Self::new();
+        for (key, value) in iter {
+            tags.insert(key, value);
+        }
+        tags
+    }
+}
+
+impl From<HashMap<String, String>> for Tags {
+    fn from(tags: HashMap<String, String>) -> Self {
+        tags.into_iter().collect()
+    }
+}
+
+/// Sets the tags of a blob when it is created, as an url encoded query string.
+impl Header for Tags {
+    fn name(&self) -> headers::HeaderName {
+        azure_storage::headers::TAGS
+    }
+
+    fn value(&self) -> headers::HeaderValue {
+        let encode = |s: &str| {
+            url::form_urlencoded::byte_serialize(s.as_bytes())
+                .collect::<String>()
+                .replace('+', "%20")
+        };
+        self.iter()
+            .map(|tag| format!("{}={}", encode(&tag.key), encode(&tag.value)))
+            .collect::<Vec<_>>()
+            .join("&")
+            .into()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct TagSet {
+    #[serde(default)]
     pub tag: Vec<Tag>,
 }
 
@@ -186,6 +269,8 @@ pub struct BlobProperties {
     pub remaining_retention_days: Option<u32>,
     pub tag_count: Option<u32>,
     pub rehydrate_priority: Option<RehydratePriority>,
+    /// Whether an append blob is sealed, and so read only.
+    pub sealed: Option<bool>,
     #[serde(flatten)]
     extra: HashMap<String, String>, // For debug purposes, should be compiled out in the future
 }
@@ -239,6 +324,9 @@ impl Blob {
             });
         let copy_status_description = h.get_optional_string(&headers::COPY_STATUS_DESCRIPTION);
         let server_encrypted = h.get_as(&headers::SERVER_ENCRYPTED)?;
+        let tag_count = h.get_optional_as(&azure_storage::headers::TAG_COUNT)?;
+        let incremental_copy = h.get_optional_as(&crate::headers::INCREMENTAL_COPY)?;
+        let sealed = h.get_optional_as(&crate::headers::BLOB_SEALED)?;
 
         let mut metadata = HashMap::new();
         for (name, value) in h.iter() {
@@ -288,7 +376,7 @@ impl Blob {
                 copy_progress,
                 copy_completion_time,
                 copy_status_description,
-                incremental_copy: None, // TODO: Not present or documentation bug?
+                incremental_copy,
                 server_encrypted,
                 customer_provided_key_sha256: None, // TODO
                 encryption_scope: None,             // TODO
@@ -296,8 +384,9 @@ impl Blob {
                 access_tier_change_time: None,      // TODO: Not present
                 deleted_time: None,                 // TODO
                 remaining_retention_days: None,     // TODO: Not present or documentation bug?
-                tag_count: None,                    // TODO
-                rehydrate_priority: None,           // TODO
+                tag_count,
+                rehydrate_priority: None, // TODO
+                sealed,
                 extra: HashMap::new(),
             },
             metadata,
@@ -15,6 +15,7 @@ pub struct AcquireLeaseBuilder {
     lease_duration: LeaseDuration,
     lease_id: Option<LeaseId>,
     proposed_lease_id: Option<ProposedLeaseId>,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     context: Context,
 }
@@ -26,6 +27,7 @@ impl AcquireLeaseBuilder {
             lease_duration,
             lease_id: None,
             proposed_lease_id: None,
+            if_tags: None,
             timeout: None,
             context: Context::new(),
         }
@@ -34,6 +36,7 @@ impl AcquireLeaseBuilder {
     setters! {
         lease_id: LeaseId => Some(lease_id),
         proposed_lease_id: ProposedLeaseId => Some(proposed_lease_id),
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
     }
 
@@ -47,6 +50,7 @@ impl AcquireLeaseBuilder {
             let mut request =
                 self.blob_client
                     .prepare_request(url, azure_core::Method::Put, None)?;
+            request.add_optional_header(&self.if_tags);
             request.insert_header(LEASE_ACTION, "acquire");
             request.add_mandatory_header(&self.lease_duration);
             request.add_optional_header(&self.proposed_lease_id);
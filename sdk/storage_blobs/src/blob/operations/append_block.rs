@@ -10,6 +10,7 @@ pub struct AppendBlockBuilder {
     condition_max_size: Option<ConditionMaxSize>,
     condition_append_position: Option<ConditionAppendPosition>,
     lease_id: Option<LeaseId>,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     context: Context,
 }
@@ -23,6 +24,7 @@ impl AppendBlockBuilder {
             condition_max_size: None,
             condition_append_position: None,
             lease_id: None,
+            if_tags: None,
             timeout: None,
             context: Context::new(),
         }
@@ -33,6 +35,7 @@ impl AppendBlockBuilder {
         condition_max_size: ConditionMaxSize => Some(condition_max_size),
         condition_append_position: ConditionAppendPosition => Some(condition_append_position),
         lease_id: LeaseId => Some(lease_id),
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
     }
 
@@ -48,6 +51,7 @@ impl AppendBlockBuilder {
                 azure_core::Method::Put,
                 Some(self.body.clone()),
             )?;
+            request.add_optional_header(&self.if_tags);
             request.add_optional_header(&self.hash);
             request.add_optional_header(&self.condition_max_size);
             request.add_optional_header(&self.condition_append_position);
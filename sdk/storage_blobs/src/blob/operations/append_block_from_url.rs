@@ -0,0 +1,146 @@
+use crate::{
+    blob::SourceContentMD5,
+    headers::{BLOB_APPEND_OFFSET, BLOB_COMMITTED_BLOCK_COUNT, SOURCE_RANGE},
+    prelude::*,
+};
+use azure_core::{headers::*, prelude::*, RequestId};
+use azure_storage::{headers::consistency_from_headers, ConsistencyCRC64, ConsistencyMD5};
+use chrono::{DateTime, Utc};
+use std::convert::{TryFrom, TryInto};
+use url::Url;
+
+/// Appends a block read from the blob or file at `source_url`.
+///
+/// The source must be public or authorized with a SAS token in the url.
+#[derive(Debug, Clone)]
+pub struct AppendBlockFromUrlBuilder {
+    blob_client: BlobClient,
+    source_url: Url,
+    source_range: Option<Range>,
+    source_content_md5: Option<SourceContentMD5>,
+    condition_max_size: Option<ConditionMaxSize>,
+    condition_append_position: Option<ConditionAppendPosition>,
+    if_modified_since_condition: Option<IfModifiedSinceCondition>,
+    if_match_condition: Option<IfMatchCondition>,
+    if_source_since_condition: Option<IfSourceModifiedSinceCondition>,
+    if_source_match_condition: Option<IfSourceMatchCondition>,
+    lease_id: Option<LeaseId>,
+    if_tags: Option<IfTags>,
+    timeout: Option<Timeout>,
+    context: Context,
+}
+
+impl AppendBlockFromUrlBuilder {
+    pub(crate) fn new(blob_client: BlobClient, source_url: Url) -> Self {
+        Self {
+            blob_client,
+            source_url,
+            source_range: None,
+            source_content_md5: None,
+            condition_max_size: None,
+            condition_append_position: None,
+            if_modified_since_condition: None,
+            if_match_condition: None,
+            if_source_since_condition: None,
+            if_source_match_condition: None,
+            lease_id: None,
+            if_tags: None,
+            timeout: None,
+            context: Context::new(),
+        }
+    }
+
+    setters! {
+        source_range: Range => Some(source_range),
+        source_content_md5: SourceContentMD5 => Some(source_content_md5),
+        condition_max_size: ConditionMaxSize => Some(condition_max_size),
+        condition_append_position: ConditionAppendPosition => Some(condition_append_position),
+        if_modified_since_condition: IfModifiedSinceCondition => Some(if_modified_since_condition),
+        if_match_condition: IfMatchCondition => Some(if_match_condition),
+        if_source_since_condition: IfSourceModifiedSinceCondition => Some(if_source_since_condition),
+        if_source_match_condition: IfSourceMatchCondition => Some(if_source_match_condition),
+        lease_id: LeaseId => Some(lease_id),
+        if_tags: IfTags => Some(if_tags),
+        timeout: Timeout => Some(timeout),
+        context: Context => context,
+    }
+
+    pub fn into_future(mut self) -> Response {
+        Box::pin(async move {
+            let mut url = self.blob_client.url_with_segments(None)?;
+
+            url.query_pairs_mut().append_pair("comp", "appendblock");
+            self.timeout.append_to_url_query(&mut url);
+
+            let mut request =
+                self.blob_client
+                    .prepare_request(url, azure_core::Method::Put, None)?;
+            request.insert_header(COPY_SOURCE, self.source_url.to_string());
+            if let Some(source_range) = &self.source_range {
+                request.insert_header(SOURCE_RANGE, source_range.to_string());
+            }
+            request.add_optional_header(&self.source_content_md5);
+            request.add_optional_header(&self.condition_max_size);
+            request.add_optional_header(&self.condition_append_position);
+            request.add_optional_header(&self.if_modified_since_condition);
+            request.add_optional_header(&self.if_match_condition);
+            request.add_optional_header(&self.if_source_since_condition);
+            request.add_optional_header(&self.if_source_match_condition);
+            request.add_optional_header(&self.lease_id);
+            request.add_optional_header(&self.if_tags);
+
+            let response = self
+                .blob_client
+                .send(&mut self.context, &mut request)
+                .await?;
+
+            response.headers().try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppendBlockFromUrlResponse {
+    pub etag: String,
+    pub last_modified: DateTime<Utc>,
+    pub content_md5: Option<ConsistencyMD5>,
+    pub content_crc64: Option<ConsistencyCRC64>,
+    /// The offset at which the block was appended.
+    pub append_offset: u64,
+    pub committed_block_count: u32,
+    pub request_id: RequestId,
+    pub date: DateTime<Utc>,
+    pub request_server_encrypted: bool,
+}
+
+impl TryFrom<&Headers> for AppendBlockFromUrlResponse {
+    type Error = crate::Error;
+
+    fn try_from(headers: &Headers) -> azure_core::Result<Self> {
+        let (content_md5, content_crc64) = consistency_from_headers(headers)?;
+
+        Ok(Self {
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+            content_md5,
+            content_crc64,
+            append_offset: headers.get_as(&BLOB_APPEND_OFFSET)?,
+            committed_block_count: headers.get_as(&BLOB_COMMITTED_BLOCK_COUNT)?,
+            request_id: request_id_from_headers(headers)?,
+            date: date_from_headers(headers)?,
+            request_server_encrypted: request_server_encrypted_from_headers(headers)?,
+        })
+    }
+}
+
+pub type Response =
+    futures::future::BoxFuture<'static, azure_core::Result<AppendBlockFromUrlResponse>>;
+
+#[cfg(feature = "into_future")]
+impl std::future::IntoFuture for AppendBlockFromUrlBuilder {
+    type IntoFuture = Response;
+    type Output = <Response as std::future::Future>::Output;
+    fn into_future(self) -> Self::IntoFuture {
+        Self::into_future(self)
+    }
+}
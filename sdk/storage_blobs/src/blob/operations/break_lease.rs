@@ -7,6 +7,7 @@ pub struct BreakLeaseBuilder {
     blob_client: BlobClient,
     lease_break_period: Option<LeaseBreakPeriod>,
     lease_id: Option<LeaseId>,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     context: Context,
 }
@@ -17,6 +18,7 @@ impl BreakLeaseBuilder {
             blob_client,
             lease_break_period: None,
             lease_id: None,
+            if_tags: None,
             timeout: None,
             context: Context::new(),
         }
@@ -25,6 +27,7 @@ impl BreakLeaseBuilder {
     setters! {
         lease_break_period: LeaseBreakPeriod => Some(lease_break_period),
         lease_id: LeaseId => Some(lease_id),
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
     }
 
@@ -38,6 +41,7 @@ impl BreakLeaseBuilder {
             let mut request =
                 self.blob_client
                     .prepare_request(url, azure_core::Method::Put, None)?;
+            request.add_optional_header(&self.if_tags);
             request.insert_header(LEASE_ACTION, "break");
             request.add_optional_header(&self.lease_break_period);
             request.add_optional_header(&self.lease_id);
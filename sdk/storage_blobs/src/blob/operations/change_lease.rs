@@ -6,6 +6,7 @@ use chrono::{DateTime, Utc};
 pub struct ChangeLeaseBuilder {
     blob_lease_client: BlobLeaseClient,
     proposed_lease_id: ProposedLeaseId,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     context: Context,
 }
@@ -18,12 +19,14 @@ impl ChangeLeaseBuilder {
         Self {
             blob_lease_client,
             proposed_lease_id,
+            if_tags: None,
             timeout: None,
             context: Context::new(),
         }
     }
 
     setters! {
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
     }
 
@@ -37,6 +40,7 @@ impl ChangeLeaseBuilder {
             let mut request =
                 self.blob_lease_client
                     .prepare_request(url, azure_core::Method::Put, None)?;
+            request.add_optional_header(&self.if_tags);
             request.insert_header(LEASE_ACTION, "change");
             request.add_mandatory_header(self.blob_lease_client.lease_id());
             request.add_mandatory_header(&self.proposed_lease_id);
@@ -13,6 +13,7 @@ pub struct ClearPageBuilder {
     sequence_number_condition: Option<SequenceNumberCondition>,
     if_modified_since_condition: Option<IfModifiedSinceCondition>,
     if_match_condition: Option<IfMatchCondition>,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     lease_id: Option<LeaseId>,
     context: Context,
@@ -26,6 +27,7 @@ impl ClearPageBuilder {
             sequence_number_condition: None,
             if_modified_since_condition: None,
             if_match_condition: None,
+            if_tags: None,
             timeout: None,
             lease_id: None,
             context: Context::new(),
@@ -36,6 +38,7 @@ impl ClearPageBuilder {
         sequence_number_condition: SequenceNumberCondition => Some(sequence_number_condition),
         if_modified_since_condition: IfModifiedSinceCondition => Some(if_modified_since_condition),
         if_match_condition: IfMatchCondition => Some(if_match_condition),
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
         lease_id: LeaseId => Some(lease_id),
     }
@@ -50,6 +53,7 @@ impl ClearPageBuilder {
             let mut request =
                 self.blob_client
                     .prepare_request(url, azure_core::Method::Put, None)?;
+            request.add_optional_header(&self.if_tags);
 
             request.insert_header(PAGE_WRITE, "clear");
             request.insert_header(BLOB_TYPE, "PageBlob");
@@ -18,6 +18,7 @@ pub struct CopyBlobBuilder {
     if_modified_since_condition: Option<IfModifiedSinceCondition>,
     if_match_condition: Option<IfMatchCondition>,
     access_tier: Option<AccessTier>,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     lease_id: Option<LeaseId>,
     if_source_since_condition: Option<IfSourceModifiedSinceCondition>,
@@ -37,6 +38,7 @@ impl CopyBlobBuilder {
             if_modified_since_condition: None,
             if_match_condition: None,
             access_tier: None,
+            if_tags: None,
             timeout: None,
             lease_id: None,
             if_source_since_condition: None,
@@ -53,6 +55,7 @@ impl CopyBlobBuilder {
         if_modified_since_condition: IfModifiedSinceCondition => Some(if_modified_since_condition),
         if_match_condition: IfMatchCondition => Some(if_match_condition),
         access_tier: AccessTier => Some(access_tier),
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
         lease_id: LeaseId => Some(lease_id),
         if_source_since_condition: IfSourceModifiedSinceCondition => Some(if_source_since_condition),
@@ -69,6 +72,7 @@ impl CopyBlobBuilder {
             let mut request =
                 self.blob_client
                     .prepare_request(url, azure_core::Method::Put, None)?;
+            request.add_optional_header(&self.if_tags);
             request.insert_header(COPY_SOURCE, self.source_url.as_str().to_owned());
             if let Some(metadata) = &self.metadata {
                 for m in metadata.iter() {
@@ -26,6 +26,7 @@ pub struct CopyBlobFromUrlBuilder {
     metadata: Option<Metadata>,
     if_modified_since_condition: Option<IfModifiedSinceCondition>,
     if_match_condition: Option<IfMatchCondition>,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     lease_id: Option<LeaseId>,
     if_source_since_condition: Option<IfSourceModifiedSinceCondition>,
@@ -43,6 +44,7 @@ impl CopyBlobFromUrlBuilder {
             metadata: None,
             if_modified_since_condition: None,
             if_match_condition: None,
+            if_tags: None,
             timeout: None,
             lease_id: None,
             if_source_since_condition: None,
@@ -57,6 +59,7 @@ impl CopyBlobFromUrlBuilder {
         metadata: Metadata => Some(metadata),
         if_modified_since_condition: IfModifiedSinceCondition => Some(if_modified_since_condition),
         if_match_condition: IfMatchCondition => Some(if_match_condition),
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
         lease_id: LeaseId => Some(lease_id),
         if_source_since_condition: IfSourceModifiedSinceCondition => Some(if_source_since_condition),
@@ -73,6 +76,7 @@ impl CopyBlobFromUrlBuilder {
             let mut request =
                 self.blob_client
                     .prepare_request(url, azure_core::Method::Put, None)?;
+            request.add_optional_header(&self.if_tags);
             request.insert_header(COPY_SOURCE, self.source_url.to_string());
             request.insert_header(REQUIRES_SYNC, format!("{}", self.is_synchronous));
             if let Some(metadata) = &self.metadata {
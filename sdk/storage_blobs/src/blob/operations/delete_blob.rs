@@ -1,11 +1,12 @@
-use crate::prelude::*;
-use azure_core::{headers::*, prelude::*, RequestId};
+use crate::{prelude::*, service::BatchOperation};
+use azure_core::{headers::*, prelude::*, Request, RequestId};
 use chrono::{DateTime, Utc};
 
 #[derive(Debug, Clone)]
 pub struct DeleteBlobBuilder {
     blob_client: BlobClient,
     delete_snapshots_method: DeleteSnapshotsMethod,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     lease_id: Option<LeaseId>,
     context: Context,
@@ -16,6 +17,7 @@ impl DeleteBlobBuilder {
         Self {
             blob_client,
             delete_snapshots_method: DeleteSnapshotsMethod::Include,
+            if_tags: None,
             timeout: None,
             lease_id: None,
             context: Context::new(),
@@ -24,6 +26,7 @@ impl DeleteBlobBuilder {
 
     setters! {
         delete_snapshots_method: DeleteSnapshotsMethod => delete_snapshots_method,
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
         lease_id: LeaseId => Some(lease_id),
     }
@@ -37,6 +40,7 @@ impl DeleteBlobBuilder {
             let mut request =
                 self.blob_client
                     .prepare_request(url, azure_core::Method::Delete, None)?;
+            request.add_optional_header(&self.if_tags);
             request.add_optional_header(&self.lease_id);
             request.add_mandatory_header(&self.delete_snapshots_method);
 
@@ -47,6 +51,18 @@ impl DeleteBlobBuilder {
             DeleteBlobResponse::from_headers(response.headers())
         })
     }
+
+    /// Creates the operation to delete the blob as part of a [`BlobBatch`](crate::service::BlobBatch).
+    pub fn to_batch_operation(&self) -> azure_core::Result<BatchOperation> {
+        let url = self.blob_client.url_with_segments(None)?;
+
+        let mut request = Request::new(url, azure_core::Method::Delete);
+        request.add_optional_header(&self.if_tags);
+        request.add_optional_header(&self.lease_id);
+        request.add_mandatory_header(&self.delete_snapshots_method);
+
+        Ok(BatchOperation::new(request))
+    }
 }
 
 #[cfg(not(feature = "azurite_workaround"))]
@@ -6,6 +6,7 @@ pub struct DeleteBlobVersionBuilder {
     blob_client: BlobClient,
     version_id: VersionId,
     permanent: bool,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     lease_id: Option<LeaseId>,
     context: Context,
@@ -17,6 +18,7 @@ impl DeleteBlobVersionBuilder {
             blob_client,
             version_id,
             permanent: false,
+            if_tags: None,
             timeout: None,
             lease_id: None,
             context: Context::new(),
@@ -25,6 +27,7 @@ impl DeleteBlobVersionBuilder {
 
     setters! {
         permanent: bool => permanent,
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
         lease_id: LeaseId => Some(lease_id),
     }
@@ -42,6 +45,7 @@ impl DeleteBlobVersionBuilder {
             let mut request =
                 self.blob_client
                     .prepare_request(url, azure_core::Method::Delete, None)?;
+            request.add_optional_header(&self.if_tags);
             request.add_optional_header(&self.lease_id);
 
             let response = self
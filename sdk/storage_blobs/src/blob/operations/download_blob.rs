@@ -0,0 +1,358 @@
+use crate::{blob::Blob, hash::crc64, prelude::*};
+use azure_core::{
+    collect_pinned_stream,
+    error::{Error, ErrorKind, ResultExt},
+    headers::*,
+    prelude::*,
+    Method, RequestId, StatusCode,
+};
+use azure_storage::{headers::CONTENT_CRC64, ConsistencyCRC64, ConsistencyMD5};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::{AsyncWrite, AsyncWriteExt, StreamExt, TryStreamExt};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::Path;
+
+const DEFAULT_CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+const DEFAULT_PARALLELISM: usize = 8;
+/// The largest range the service returns a checksum for.
+const MAX_VALIDATED_RANGE: u64 = 4 * 1024 * 1024;
+
+/// The checksum the service computes for each downloaded range, checked against the received data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeValidation {
+    MD5,
+    CRC64,
+}
+
+/// Downloads a blob by fetching ranges of `chunk_size` bytes concurrently.
+///
+/// The first range is fetched alone. Its ETag is then sent as `If-Match` with the other ranges, so
+/// the download fails instead of mixing two versions if the blob is overwritten meanwhile.
+#[derive(Debug, Clone)]
+pub struct DownloadBlobBuilder {
+    blob_client: BlobClient,
+    range: Option<Range>,
+    blob_versioning: Option<BlobVersioning>,
+    timeout: Option<Timeout>,
+    lease_id: Option<LeaseId>,
+    if_tags: Option<IfTags>,
+    chunk_size: u64,
+    parallelism: usize,
+    validation: Option<RangeValidation>,
+    context: Context,
+}
+
+impl DownloadBlobBuilder {
+    pub(crate) fn new(blob_client: BlobClient) -> Self {
+        Self {
+            blob_client,
+            range: None,
+            blob_versioning: None,
+            timeout: None,
+            lease_id: None,
+            if_tags: None,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            parallelism: DEFAULT_PARALLELISM,
+            validation: None,
+            context: Context::new(),
+        }
+    }
+
+    setters! {
+        range: Range => Some(range),
+        blob_versioning: BlobVersioning => Some(blob_versioning),
+        timeout: Timeout => Some(timeout),
+        lease_id: LeaseId => Some(lease_id),
+        if_tags: IfTags => Some(if_tags),
+        chunk_size: u64 => chunk_size,
+        parallelism: usize => parallelism,
+        validation: RangeValidation => Some(validation),
+        context: Context => context,
+    }
+
+    /// Downloads the blob into `writer`, writing the ranges in order.
+    ///
+    /// At most `parallelism` ranges are buffered in memory while waiting for an earlier one.
+    pub async fn into_writer<W>(self, writer: &mut W) -> azure_core::Result<DownloadBlobResponse>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let (first, ranges) = self.first_chunk().await?;
+        writer
+            .write_all(&first.data)
+            .await
+            .map_kind(ErrorKind::Io)?;
+
+        let etag = first.etag.clone();
+        let mut chunks = futures::stream::iter(ranges)
+            .map(|range| self.fetch(Some(range), Some(&etag)))
+            .buffered(self.parallelism.max(1));
+        let mut content_length = first.data.len() as u64;
+        while let Some(chunk) = chunks.try_next().await? {
+            writer
+                .write_all(&chunk.data)
+                .await
+                .map_kind(ErrorKind::Io)?;
+            content_length += chunk.data.len() as u64;
+        }
+        writer.flush().await.map_kind(ErrorKind::Io)?;
+
+        Ok(first.into_response(content_length))
+    }
+
+    /// Downloads the blob into the file at `path`, creating or truncating it.
+    ///
+    /// Ranges are written at their position as soon as they are received.
+    ///
+    /// The file is created and written with blocking `std::fs` calls, which block the executor
+    /// thread while they run. When that is not acceptable, use [`Self::into_writer`] with the
+    /// asynchronous file type of your runtime instead.
+    pub async fn into_file(
+        self,
+        path: impl AsRef<Path>,
+    ) -> azure_core::Result<DownloadBlobResponse> {
+        let path = path.as_ref();
+        let mut file = std::fs::File::create(path).with_context(ErrorKind::Io, || {
+            format!("failed to create {}", path.display())
+        })?;
+
+        let (first, ranges) = self.first_chunk().await?;
+        let start = first.range.map_or(0, |range| range.start);
+        file.write_all(&first.data).map_kind(ErrorKind::Io)?;
+
+        let etag = first.etag.clone();
+        let mut chunks = futures::stream::iter(ranges)
+            .map(|range| self.fetch(Some(range), Some(&etag)))
+            .buffer_unordered(self.parallelism.max(1));
+        let mut content_length = first.data.len() as u64;
+        while let Some(chunk) = chunks.try_next().await? {
+            // chunks fetched after the first one always have a range
+            let offset = chunk.range.map_or(0, |range| range.start) - start;
+            file.seek(SeekFrom::Start(offset)).map_kind(ErrorKind::Io)?;
+            file.write_all(&chunk.data).map_kind(ErrorKind::Io)?;
+            content_length += chunk.data.len() as u64;
+        }
+        file.flush().map_kind(ErrorKind::Io)?;
+
+        Ok(first.into_response(content_length))
+    }
+
+    /// Fetches the first range, returning it with the ranges left to fetch.
+    async fn first_chunk(&self) -> azure_core::Result<(Chunk, Vec<Range>)> {
+        if self.validation.is_some() && self.chunk_size > MAX_VALIDATED_RANGE {
+            return Err(Error::with_message(ErrorKind::Other, || {
+                format!(
+                    "ranges are validated only up to {} bytes, but the chunk size is {}",
+                    MAX_VALIDATED_RANGE, self.chunk_size
+                )
+            }));
+        }
+
+        let first_range = match self.range {
+            Some(range) => Range::new(range.start, range.end.min(range.start + self.chunk_size)),
+            None => Range::new(0, self.chunk_size),
+        };
+        let first = match self.fetch(Some(first_range), None).await {
+            Ok(first) => first,
+            // the service rejects any range of an empty blob, so fetch it whole
+            Err(err) if self.range.is_none() && is_invalid_range(&err) => {
+                self.fetch(None, None).await?
+            }
+            Err(err) => return Err(err),
+        };
+
+        let ranges = match first.content_range {
+            Some(content_range) => {
+                let end = self.range.map_or(content_range.total_length(), |range| {
+                    range.end.min(content_range.total_length())
+                });
+                split_range(content_range.end() + 1, end, self.chunk_size)
+            }
+            // the response had the entire blob
+            None => Vec::new(),
+        };
+        Ok((first, ranges))
+    }
+
+    async fn fetch(&self, range: Option<Range>, etag: Option<&str>) -> azure_core::Result<Chunk> {
+        let mut url = self.blob_client.url_with_segments(None)?;
+        self.blob_versioning.append_to_url_query(&mut url);
+        self.timeout.append_to_url_query(&mut url);
+
+        let mut request = self.blob_client.prepare_request(url, Method::Get, None)?;
+        if let Some(range) = range {
+            request.insert_header(MS_RANGE, range.to_string());
+            match self.validation {
+                Some(RangeValidation::MD5) => request.insert_header(RANGE_GET_CONTENT_MD5, "true"),
+                Some(RangeValidation::CRC64) => {
+                    request.insert_header(RANGE_GET_CONTENT_CRC64, "true")
+                }
+                None => {}
+            }
+        }
+        request.add_optional_header(&self.lease_id);
+        request.add_optional_header(&self.if_tags);
+        request.add_optional_header(&etag.map(|etag| IfMatchCondition::Match(etag.to_owned())));
+
+        let mut ctx = self.context.clone();
+        let response = self.blob_client.send(&mut ctx, &mut request).await?;
+        let (_, headers, body) = response.deconstruct();
+        let data = collect_pinned_stream(body).await?;
+
+        if let Some(range) = range {
+            // only the first range, fetched without an etag, may extend past the end of the blob
+            check_length(data.len(), range, etag.is_some())?;
+            if let Some(validation) = self.validation {
+                validate(validation, &headers, &data, range)?;
+            }
+        }
+
+        Ok(Chunk {
+            range,
+            content_range: headers.get_optional_as(&CONTENT_RANGE)?,
+            etag: headers.get_string(&ETAG)?,
+            request_id: request_id_from_headers(&headers)?,
+            date: date_from_headers(&headers)?,
+            blob: Blob::from_headers(self.blob_client.blob_name(), &headers)?,
+            data,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DownloadBlobResponse {
+    pub request_id: RequestId,
+    pub date: DateTime<Utc>,
+    /// The properties returned with the first range.
+    pub blob: Blob,
+    /// The number of bytes downloaded.
+    pub content_length: u64,
+}
+
+struct Chunk {
+    range: Option<Range>,
+    content_range: Option<ContentRange>,
+    etag: String,
+    request_id: RequestId,
+    date: DateTime<Utc>,
+    blob: Blob,
+    data: Bytes,
+}
+
+impl Chunk {
+    fn into_response(self, content_length: u64) -> DownloadBlobResponse {
+        DownloadBlobResponse {
+            request_id: self.request_id,
+            date: self.date,
+            blob: self.blob,
+            content_length,
+        }
+    }
+}
+
+fn is_invalid_range(err: &Error) -> bool {
+    matches!(
+        err.kind(),
+        ErrorKind::HttpResponse { status, .. } if *status == StatusCode::RequestedRangeNotSatisfiable as u16
+    )
+}
+
+fn validate(
+    validation: RangeValidation,
+    headers: &Headers,
+    data: &[u8],
+    range: Range,
+) -> azure_core::Result<()> {
+    let valid = match validation {
+        RangeValidation::MD5 => {
+            let expected: Option<ConsistencyMD5> = headers.get_optional_as(&CONTENT_MD5)?;
+            expected.map(|expected| expected.as_slice() == &md5::compute(data).0)
+        }
+        RangeValidation::CRC64 => {
+            let expected: Option<ConsistencyCRC64> = headers.get_optional_as(&CONTENT_CRC64)?;
+            expected.map(|expected| expected.as_slice() == &crc64(data).to_le_bytes())
+        }
+    };
+    match valid {
+        Some(true) => Ok(()),
+        Some(false) => Err(Error::with_message(ErrorKind::Other, || {
+            format!("{:?} mismatch for the range {}", validation, range)
+        })),
+        None => Err(Error::with_message(ErrorKind::Other, || {
+            format!("no {:?} returned for the range {}", validation, range)
+        })),
+    }
+}
+
+/// Checks that no more than `range` was received, and all of it when it lies within the blob, as
+/// a shorter body means the transfer was truncated.
+fn check_length(received: usize, range: Range, whole: bool) -> azure_core::Result<()> {
+    let received = received as u64;
+    if received > range.len() || (whole && received != range.len()) {
+        return Err(Error::with_message(ErrorKind::Other, || {
+            format!("received {} bytes for the range {}", received, range)
+        }));
+    }
+    Ok(())
+}
+
+/// Splits `start..end` into ranges of at most `chunk_size` bytes.
+fn split_range(start: u64, end: u64, chunk_size: u64) -> Vec<Range> {
+    let chunk_size = chunk_size.max(1);
+    (start..end)
+        .step_by(chunk_size as usize)
+        .map(|chunk_start| Range::new(chunk_start, end.min(chunk_start + chunk_size)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_range() {
+        assert_eq!(
+            split_range(4, 14, 4),
+            vec![Range::new(4, 8), Range::new(8, 12), Range::new(12, 14)]
+        );
+        assert_eq!(
+            split_range(4, 12, 4),
+            vec![Range::new(4, 8), Range::new(8, 12)]
+        );
+        assert!(split_range(10, 10, 4).is_empty());
+    }
+
+    #[test]
+    fn test_check_length() {
+        let range = Range::new(8, 12);
+        assert!(check_length(4, range, true).is_ok());
+        assert!(check_length(3, range, true).is_err());
+        assert!(check_length(3, range, false).is_ok());
+        assert!(check_length(5, range, false).is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let range = Range::new(0, 5);
+        let headers = Headers::from(std::collections::HashMap::from([
+            (CONTENT_MD5, base64::encode(md5::compute("hello").0).into()),
+            (
+                CONTENT_CRC64,
+                base64::encode(crc64(b"hello").to_le_bytes()).into(),
+            ),
+        ]));
+
+        assert!(validate(RangeValidation::MD5, &headers, b"hello", range).is_ok());
+        assert!(validate(RangeValidation::CRC64, &headers, b"hello", range).is_ok());
+        assert!(validate(RangeValidation::MD5, &headers, b"hellp", range).is_err());
+        assert!(validate(RangeValidation::CRC64, &headers, b"hellp", range).is_err());
+        assert!(validate(
+            RangeValidation::MD5,
+            &Headers::from(std::collections::HashMap::new()),
+            b"hello",
+            range
+        )
+        .is_err());
+    }
+}
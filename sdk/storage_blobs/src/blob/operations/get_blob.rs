@@ -13,6 +13,7 @@ pub struct GetBlobBuilder {
     blob_client: BlobClient,
     range: Option<Range>,
     blob_versioning: Option<BlobVersioning>,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     lease_id: Option<LeaseId>,
     chunk_size: u64,
@@ -24,6 +25,7 @@ impl GetBlobBuilder {
         Self {
             blob_client,
             blob_versioning: None,
+            if_tags: None,
             timeout: None,
             range: None,
             lease_id: None,
@@ -36,6 +38,7 @@ impl GetBlobBuilder {
         range: Range => Some(range),
         chunk_size: u64 => chunk_size,
         blob_versioning: BlobVersioning => Some(blob_versioning),
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
         lease_id: LeaseId => Some(lease_id),
     }
@@ -67,6 +70,7 @@ impl GetBlobBuilder {
                 }
 
                 request.add_optional_header(&this.lease_id);
+                request.add_optional_header(&this.if_tags);
 
                 let response = this.blob_client.send(&mut ctx, &mut request).await?;
 
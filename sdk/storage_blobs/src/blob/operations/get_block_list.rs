@@ -11,6 +11,7 @@ pub struct GetBlockListBuilder {
     block_list_type: BlockListType,
     blob_versioning: Option<BlobVersioning>,
     lease_id: Option<LeaseId>,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     context: Context,
 }
@@ -23,6 +24,7 @@ impl GetBlockListBuilder {
             blob_versioning: None,
             lease_id: None,
             context: Context::new(),
+            if_tags: None,
             timeout: None,
         }
     }
@@ -31,6 +33,7 @@ impl GetBlockListBuilder {
         block_list_type: BlockListType => block_list_type,
         blob_versioning: BlobVersioning => Some(blob_versioning),
         lease_id: LeaseId => Some(lease_id),
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
     }
 
@@ -46,6 +49,7 @@ impl GetBlockListBuilder {
             let mut request =
                 self.blob_client
                     .prepare_request(url, azure_core::Method::Get, None)?;
+            request.add_optional_header(&self.if_tags);
             request.add_optional_header(&self.lease_id);
 
             let response = self
@@ -8,6 +8,7 @@ pub struct GetMetadataBuilder {
     blob_client: BlobClient,
     blob_versioning: Option<BlobVersioning>,
     lease_id: Option<LeaseId>,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     context: Context,
 }
@@ -19,6 +20,7 @@ impl GetMetadataBuilder {
             blob_versioning: None,
             lease_id: None,
             context: Context::new(),
+            if_tags: None,
             timeout: None,
         }
     }
@@ -26,6 +28,7 @@ impl GetMetadataBuilder {
     setters! {
         blob_versioning: BlobVersioning => Some(blob_versioning),
         lease_id: LeaseId => Some(lease_id),
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
     }
 
@@ -38,6 +41,7 @@ impl GetMetadataBuilder {
             self.timeout.append_to_url_query(&mut url);
 
             let mut request = self.blob_client.prepare_request(url, Method::Get, None)?;
+            request.add_optional_header(&self.if_tags);
             request.add_optional_header(&self.lease_id);
 
             let response = self
@@ -1,12 +1,16 @@
-use crate::{blob::PageRangeList, prelude::*};
+use crate::{blob::PageRangeList, headers::PREVIOUS_SNAPSHOT_URL, prelude::*};
 use azure_core::{collect_pinned_stream, headers::*, prelude::*, RequestId};
 use chrono::{DateTime, Utc};
 use std::str::from_utf8;
+use url::Url;
 
 pub struct GetPageRangesBuilder {
     blob_client: BlobClient,
     blob_versioning: Option<BlobVersioning>,
+    previous_snapshot: Option<Snapshot>,
+    previous_snapshot_url: Option<Url>,
     lease_id: Option<LeaseId>,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     context: Context,
 }
@@ -16,15 +20,21 @@ impl<'a> GetPageRangesBuilder {
         Self {
             blob_client,
             blob_versioning: None,
+            previous_snapshot: None,
+            previous_snapshot_url: None,
             lease_id: None,
             context: Context::new(),
+            if_tags: None,
             timeout: None,
         }
     }
 
     setters! {
         blob_versioning: BlobVersioning => Some(blob_versioning),
+        previous_snapshot: Snapshot => Some(previous_snapshot),
+        previous_snapshot_url: Url => Some(previous_snapshot_url),
         lease_id: LeaseId => Some(lease_id),
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
     }
 
@@ -34,12 +44,20 @@ impl<'a> GetPageRangesBuilder {
 
             url.query_pairs_mut().append_pair("comp", "pagelist");
             self.blob_versioning.append_to_url_query(&mut url);
+            if let Some(previous_snapshot) = &self.previous_snapshot {
+                url.query_pairs_mut()
+                    .append_pair("prevsnapshot", previous_snapshot.as_str());
+            }
             self.timeout.append_to_url_query(&mut url);
 
             let mut request =
                 self.blob_client
                     .prepare_request(url, azure_core::Method::Get, None)?;
+            request.add_optional_header(&self.if_tags);
             request.add_optional_header(&self.lease_id);
+            if let Some(previous_snapshot_url) = &self.previous_snapshot_url {
+                request.insert_header(PREVIOUS_SNAPSHOT_URL, previous_snapshot_url.to_string());
+            }
 
             let response = self
                 .blob_client
@@ -6,6 +6,7 @@ use chrono::{DateTime, Utc};
 pub struct GetPropertiesBuilder {
     blob_client: BlobClient,
     blob_versioning: Option<BlobVersioning>,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     lease_id: Option<LeaseId>,
     context: Context,
@@ -16,6 +17,7 @@ impl GetPropertiesBuilder {
         Self {
             blob_client,
             blob_versioning: None,
+            if_tags: None,
             timeout: None,
             lease_id: None,
             context: Context::new(),
@@ -24,6 +26,7 @@ impl GetPropertiesBuilder {
 
     setters! {
         blob_versioning: BlobVersioning => Some(blob_versioning),
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
         lease_id: LeaseId => Some(lease_id),
     }
@@ -38,6 +41,7 @@ impl GetPropertiesBuilder {
             let mut request =
                 self.blob_client
                     .prepare_request(url, azure_core::Method::Head, None)?;
+            request.add_optional_header(&self.if_tags);
             request.add_optional_header(&self.lease_id);
 
             let response = self
@@ -0,0 +1,119 @@
+use crate::{blob::Tags, prelude::*};
+use azure_core::{collect_pinned_stream, headers::*, prelude::*, Method, RequestId};
+use azure_storage::xml::read_xml;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone)]
+pub struct GetTagsBuilder {
+    blob_client: BlobClient,
+    snapshot: Option<Snapshot>,
+    version_id: Option<VersionId>,
+    lease_id: Option<LeaseId>,
+    if_tags: Option<IfTags>,
+    timeout: Option<Timeout>,
+    context: Context,
+}
+
+impl GetTagsBuilder {
+    pub(crate) fn new(blob_client: BlobClient) -> Self {
+        Self {
+            blob_client,
+            snapshot: None,
+            version_id: None,
+            lease_id: None,
+            if_tags: None,
+            timeout: None,
+            context: Context::new(),
+        }
+    }
+
+    setters! {
+        snapshot: Snapshot => Some(snapshot),
+        version_id: VersionId => Some(version_id),
+        lease_id: LeaseId => Some(lease_id),
+        if_tags: IfTags => Some(if_tags),
+        timeout: Timeout => Some(timeout),
+        context: Context => context,
+    }
+
+    pub fn into_future(mut self) -> Response {
+        Box::pin(async move {
+            let mut url = self.blob_client.url_with_segments(None)?;
+
+            url.query_pairs_mut().append_pair("comp", "tags");
+            self.snapshot.as_ref().append_to_url_query(&mut url);
+            self.version_id.as_ref().append_to_url_query(&mut url);
+            self.timeout.append_to_url_query(&mut url);
+
+            let mut request = self.blob_client.prepare_request(url, Method::Get, None)?;
+            request.add_optional_header(&self.lease_id);
+            request.add_optional_header(&self.if_tags);
+
+            let response = self
+                .blob_client
+                .send(&mut self.context, &mut request)
+                .await?;
+
+            GetTagsResponse::try_from(response).await
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetTagsResponse {
+    pub request_id: RequestId,
+    pub date: DateTime<Utc>,
+    pub tags: Tags,
+}
+
+impl GetTagsResponse {
+    async fn try_from(response: azure_core::Response) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let body = collect_pinned_stream(body).await?;
+
+        Ok(Self {
+            request_id: request_id_from_headers(&headers)?,
+            date: date_from_headers(&headers)?,
+            tags: read_xml(&body)?,
+        })
+    }
+}
+
+pub type Response = futures::future::BoxFuture<'static, azure_core::Result<GetTagsResponse>>;
+
+#[cfg(feature = "into_future")]
+impl std::future::IntoFuture for GetTagsBuilder {
+    type IntoFuture = Response;
+    type Output = <Response as std::future::Future>::Output;
+    fn into_future(self) -> Self::IntoFuture {
+        Self::into_future(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_xml_round_trip() {
+        let body = br#"<?xml version="1.0" encoding="utf-8"?>
+<Tags>
+    <TagSet>
+        <Tag><Key>project</Key><Value>contoso</Value></Tag>
+        <Tag><Key>owner</Key><Value>a &amp; b</Value></Tag>
+    </TagSet>
+</Tags>"#;
+        let tags: Tags = read_xml(body).unwrap();
+        let pairs = tags
+            .iter()
+            .map(|tag| (tag.key.as_str(), tag.value.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(pairs, vec![("project", "contoso"), ("owner", "a & b")]);
+
+        let round_tripped: Tags = read_xml(tags.to_xml().as_bytes()).unwrap();
+        assert_eq!(round_tripped, tags);
+
+        let empty: Tags = read_xml(b"<Tags><TagSet /></Tags>").unwrap();
+        assert_eq!(empty.iter().count(), 0);
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{
+    blob::{copy_status_from_headers, CopyStatus},
+    prelude::*,
+};
+use azure_core::{headers::*, prelude::*, RequestId};
+use azure_storage::core::{copy_id_from_headers, CopyId};
+use chrono::{DateTime, Utc};
+use std::convert::{TryFrom, TryInto};
+use url::Url;
+
+/// Copies the changes of a page blob snapshot since the last incremental copy.
+///
+/// The source must be the url of a snapshot, public or authorized with a SAS token. The first
+/// copy copies the whole snapshot; the next ones only the pages that changed.
+#[derive(Debug, Clone)]
+pub struct IncrementalCopyBlobBuilder {
+    blob_client: BlobClient,
+    source_url: Url,
+    if_modified_since_condition: Option<IfModifiedSinceCondition>,
+    if_match_condition: Option<IfMatchCondition>,
+    timeout: Option<Timeout>,
+    context: Context,
+}
+
+impl IncrementalCopyBlobBuilder {
+    pub(crate) fn new(blob_client: BlobClient, source_url: Url) -> Self {
+        Self {
+            blob_client,
+            source_url,
+            if_modified_since_condition: None,
+            if_match_condition: None,
+            timeout: None,
+            context: Context::new(),
+        }
+    }
+
+    setters! {
+        if_modified_since_condition: IfModifiedSinceCondition => Some(if_modified_since_condition),
+        if_match_condition: IfMatchCondition => Some(if_match_condition),
+        timeout: Timeout => Some(timeout),
+        context: Context => context,
+    }
+
+    pub fn into_future(mut self) -> Response {
+        Box::pin(async move {
+            let mut url = self.blob_client.url_with_segments(None)?;
+
+            url.query_pairs_mut().append_pair("comp", "incrementalcopy");
+            self.timeout.append_to_url_query(&mut url);
+
+            let mut request =
+                self.blob_client
+                    .prepare_request(url, azure_core::Method::Put, None)?;
+            request.insert_header(COPY_SOURCE, self.source_url.to_string());
+            request.add_optional_header(&self.if_modified_since_condition);
+            request.add_optional_header(&self.if_match_condition);
+
+            let response = self
+                .blob_client
+                .send(&mut self.context, &mut request)
+                .await?;
+
+            response.headers().try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncrementalCopyBlobResponse {
+    pub etag: String,
+    pub last_modified: DateTime<Utc>,
+    pub request_id: RequestId,
+    pub date: DateTime<Utc>,
+    pub copy_id: CopyId,
+    pub copy_status: CopyStatus,
+}
+
+impl TryFrom<&Headers> for IncrementalCopyBlobResponse {
+    type Error = crate::Error;
+
+    fn try_from(headers: &Headers) -> azure_core::Result<Self> {
+        Ok(Self {
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+            request_id: request_id_from_headers(headers)?,
+            date: date_from_headers(headers)?,
+            copy_id: copy_id_from_headers(headers)?,
+            copy_status: copy_status_from_headers(headers)?,
+        })
+    }
+}
+
+pub type Response =
+    futures::future::BoxFuture<'static, azure_core::Result<IncrementalCopyBlobResponse>>;
+
+#[cfg(feature = "into_future")]
+impl std::future::IntoFuture for IncrementalCopyBlobBuilder {
+    type IntoFuture = Response;
+    type Output = <Response as std::future::Future>::Output;
+    fn into_future(self) -> Self::IntoFuture {
+        Self::into_future(self)
+    }
+}
@@ -1,5 +1,6 @@
 mod acquire_lease;
 mod append_block;
+mod append_block_from_url;
 mod break_lease;
 mod change_lease;
 mod clear_page;
@@ -8,24 +9,34 @@ mod copy_blob_from_url;
 mod delete_blob;
 mod delete_blob_snapshot;
 mod delete_blob_version;
+mod download_blob;
 mod get_blob;
 mod get_block_list;
 mod get_metadata;
 mod get_page_ranges;
 mod get_properties;
+mod get_tags;
+mod incremental_copy_blob;
 mod put_append_blob;
 mod put_block;
 mod put_block_blob;
 mod put_block_list;
 mod put_page_blob;
+mod query_blob;
 mod release_lease;
 mod renew_lease;
+mod seal_append_blob;
 mod set_blob_tier;
 mod set_metadata;
 mod set_properties;
+mod set_tags;
+mod undelete_blob;
 mod update_page;
+mod update_page_from_url;
+mod upload_blob;
 pub use acquire_lease::*;
 pub use append_block::*;
+pub use append_block_from_url::*;
 pub use break_lease::*;
 pub use change_lease::*;
 pub use clear_page::*;
@@ -34,19 +45,28 @@ pub use copy_blob_from_url::*;
 pub use delete_blob::*;
 pub use delete_blob_snapshot::*;
 pub use delete_blob_version::*;
+pub use download_blob::*;
 pub use get_blob::*;
 pub use get_block_list::*;
 pub use get_metadata::*;
 pub use get_page_ranges::*;
 pub use get_properties::*;
+pub use get_tags::*;
+pub use incremental_copy_blob::*;
 pub use put_append_blob::*;
 pub use put_block::*;
 pub use put_block_blob::*;
 pub use put_block_list::*;
 pub use put_page_blob::*;
+pub use query_blob::*;
 pub use release_lease::*;
 pub use renew_lease::*;
+pub use seal_append_blob::*;
 pub use set_blob_tier::*;
 pub use set_metadata::*;
 pub use set_properties::*;
+pub use set_tags::*;
+pub use undelete_blob::*;
 pub use update_page::*;
+pub use update_page_from_url::*;
+pub use upload_blob::*;
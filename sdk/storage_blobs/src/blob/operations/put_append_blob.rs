@@ -12,6 +12,7 @@ pub struct PutAppendBlobBuilder {
     metadata: Option<Metadata>,
     // TODO: Support tags
     lease_id: Option<LeaseId>,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     context: Context,
 }
@@ -27,6 +28,7 @@ impl PutAppendBlobBuilder {
             metadata: None,
             lease_id: None,
             context: Context::new(),
+            if_tags: None,
             timeout: None,
         }
     }
@@ -39,6 +41,7 @@ impl PutAppendBlobBuilder {
         metadata: Metadata => Some(metadata),
         lease_id: LeaseId => Some(lease_id),
 
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
     }
 
@@ -51,6 +54,7 @@ impl PutAppendBlobBuilder {
             let mut request =
                 self.blob_client
                     .prepare_request(url, azure_core::Method::Put, None)?;
+            request.add_optional_header(&self.if_tags);
             request.insert_header(BLOB_TYPE, "AppendBlob");
             request.add_optional_header(&self.content_type);
             request.add_optional_header(&self.content_encoding);
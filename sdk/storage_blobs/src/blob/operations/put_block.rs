@@ -8,7 +8,6 @@ pub struct PutBlockBuilder {
     blob_client: BlobClient,
     block_id: BlockId,
     body: Bytes,
-    #[allow(unused)]
     hash: Option<Hash>,
     timeout: Option<Timeout>,
     lease_id: Option<LeaseId>,
@@ -32,6 +31,7 @@ impl<'a> PutBlockBuilder {
         hash: Hash => Some(hash),
         timeout: Timeout => Some(timeout),
         lease_id: LeaseId => Some(lease_id),
+        context: Context => context,
     }
 
     pub fn into_future(mut self) -> Response {
@@ -47,6 +47,7 @@ impl<'a> PutBlockBuilder {
                 azure_core::Method::Put,
                 Some(self.body.clone()),
             )?;
+            request.add_optional_header(&self.hash);
             request.add_optional_header(&self.lease_id);
 
             let response = self
@@ -1,4 +1,4 @@
-use crate::prelude::*;
+use crate::{blob::Tags, prelude::*};
 use azure_core::{headers::*, prelude::*, RequestId};
 use azure_storage::{headers::consistency_from_headers, ConsistencyCRC64, ConsistencyMD5};
 use bytes::Bytes;
@@ -15,8 +15,9 @@ pub struct PutBlockBlobBuilder {
     content_disposition: Option<ContentDisposition>,
     metadata: Option<Metadata>,
     access_tier: Option<AccessTier>,
-    // TODO: Support tags
+    tags: Option<Tags>,
     lease_id: Option<LeaseId>,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     context: Context,
 }
@@ -33,8 +34,10 @@ impl PutBlockBlobBuilder {
             content_disposition: None,
             metadata: None,
             access_tier: None,
+            tags: None,
             lease_id: None,
             context: Context::new(),
+            if_tags: None,
             timeout: None,
         }
     }
@@ -47,8 +50,11 @@ impl PutBlockBlobBuilder {
         content_disposition: ContentDisposition => Some(content_disposition),
         metadata: Metadata => Some(metadata),
         access_tier: AccessTier => Some(access_tier),
+        tags: Tags => Some(tags),
         lease_id: LeaseId => Some(lease_id),
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
+        context: Context => context,
     }
 
     pub fn into_future(mut self) -> Response {
@@ -62,6 +68,7 @@ impl PutBlockBlobBuilder {
                 azure_core::Method::Put,
                 Some(self.body.clone()),
             )?;
+            request.add_optional_header(&self.if_tags);
             request.insert_header(BLOB_TYPE, "BlockBlob");
             request.add_optional_header(&self.hash);
             request.add_optional_header(&self.content_type);
@@ -74,6 +81,7 @@ impl PutBlockBlobBuilder {
                 }
             }
             request.add_optional_header(&self.access_tier);
+            request.add_optional_header(&self.tags);
             request.add_optional_header(&self.lease_id);
 
             let response = self
@@ -1,4 +1,4 @@
-use crate::prelude::*;
+use crate::{blob::Tags, prelude::*};
 use azure_core::{headers::*, prelude::*, RequestId};
 use azure_storage::{headers::content_md5_from_headers, ConsistencyMD5};
 use bytes::Bytes;
@@ -15,8 +15,9 @@ pub struct PutBlockListBuilder {
     content_md5: Option<BlobContentMD5>,
     metadata: Option<Metadata>,
     access_tier: Option<AccessTier>,
-    // TODO: Support tags
+    tags: Option<Tags>,
     lease_id: Option<LeaseId>,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     context: Context,
 }
@@ -33,8 +34,10 @@ impl PutBlockListBuilder {
             content_md5: None,
             metadata: None,
             access_tier: None,
+            tags: None,
             lease_id: None,
             context: Context::new(),
+            if_tags: None,
             timeout: None,
         }
     }
@@ -47,8 +50,11 @@ impl PutBlockListBuilder {
         content_md5: BlobContentMD5 => Some(content_md5),
         metadata: Metadata => Some(metadata),
         access_tier: AccessTier => Some(access_tier),
+        tags: Tags => Some(tags),
         lease_id: LeaseId => Some(lease_id),
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
+        context: Context => context,
     }
 
     pub fn into_future(mut self) -> Response {
@@ -71,6 +77,7 @@ impl PutBlockListBuilder {
             let mut request =
                 self.blob_client
                     .prepare_request(url, azure_core::Method::Put, Some(body_bytes))?;
+            request.add_optional_header(&self.if_tags);
             request.insert_header("Content-MD5", &md5);
             request.add_optional_header(&self.content_type);
             request.add_optional_header(&self.content_encoding);
@@ -83,6 +90,7 @@ impl PutBlockListBuilder {
                 }
             }
             request.add_optional_header(&self.access_tier);
+            request.add_optional_header(&self.tags);
             request.add_optional_header(&self.lease_id);
 
             let response = self
@@ -16,6 +16,7 @@ pub struct PutPageBlobBuilder {
     // TODO: Support tags
     lease_id: Option<LeaseId>,
     sequence_number: Option<SequenceNumber>,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     context: Context,
 }
@@ -33,6 +34,7 @@ impl PutPageBlobBuilder {
             lease_id: None,
             sequence_number: None,
             context: Context::new(),
+            if_tags: None,
             timeout: None,
         }
     }
@@ -46,6 +48,7 @@ impl PutPageBlobBuilder {
         lease_id: LeaseId => Some(lease_id),
         sequence_number: SequenceNumber => Some(sequence_number),
 
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
     }
 
@@ -58,6 +61,7 @@ impl PutPageBlobBuilder {
             let mut request =
                 self.blob_client
                     .prepare_request(url, azure_core::Method::Put, None)?;
+            request.add_optional_header(&self.if_tags);
             request.insert_header(BLOB_TYPE, "PageBlob");
             request.insert_header(BLOB_CONTENT_LENGTH, &format!("{}", self.length));
             request.add_optional_header(&self.content_type);
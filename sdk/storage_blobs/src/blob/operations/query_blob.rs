@@ -0,0 +1,466 @@
+use crate::{
+    avro::{AvroReader, AvroValue},
+    prelude::*,
+};
+use azure_core::{
+    error::{Error, ErrorKind},
+    headers::VERSION,
+    prelude::*,
+    Method,
+};
+use azure_storage::xml::escape_xml;
+use bytes::Bytes;
+use futures::stream::{BoxStream, Stream, StreamExt, TryStreamExt};
+
+/// The first service version supporting Arrow output and Parquet input.
+const ARROW_PARQUET_VERSION: &str = "2020-10-02";
+
+/// The format of the blob, or of the query results.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QuerySerialization {
+    Delimited(DelimitedTextConfiguration),
+    Json(JsonTextConfiguration),
+    /// The schema of the results. Only valid for the output.
+    Arrow(Vec<ArrowField>),
+    /// Only valid for the input.
+    Parquet,
+}
+
+/// A CSV dialect.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DelimitedTextConfiguration {
+    pub column_separator: char,
+    pub field_quote: char,
+    pub record_separator: char,
+    pub escape_char: Option<char>,
+    pub has_headers: bool,
+}
+
+impl Default for DelimitedTextConfiguration {
+    fn default() -> Self {
+        Self {
+            column_separator: ',',
+            field_quote: '"',
+            record_separator: '\n',
+            escape_char: None,
+            has_headers: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonTextConfiguration {
+    pub record_separator: char,
+}
+
+impl Default for JsonTextConfiguration {
+    fn default() -> Self {
+        Self {
+            record_separator: '\n',
+        }
+    }
+}
+
+create_enum!(
+    ArrowFieldType,
+    (Int64, "int64"),
+    (Bool, "bool"),
+    (Timestamp, "timestamp[ms]"),
+    (String, "string"),
+    (Double, "double"),
+    (Decimal, "decimal")
+);
+
+/// A column of the Arrow output. `precision` and `scale` only apply to decimals.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArrowField {
+    pub field_type: ArrowFieldType,
+    pub name: Option<String>,
+    pub precision: Option<u32>,
+    pub scale: Option<u32>,
+}
+
+impl ArrowField {
+    pub fn new(field_type: ArrowFieldType) -> Self {
+        Self {
+            field_type,
+            name: None,
+            precision: None,
+            scale: None,
+        }
+    }
+}
+
+impl QuerySerialization {
+    fn push_xml(&self, s: &mut String, name: &str) {
+        s.push_str(&format!("<{}><Format>", name));
+        match self {
+            QuerySerialization::Delimited(configuration) => {
+                push_element(s, "Type", "delimited");
+                s.push_str("<DelimitedTextConfiguration>");
+                push_char(s, "ColumnSeparator", configuration.column_separator);
+                push_char(s, "FieldQuote", configuration.field_quote);
+                push_char(s, "RecordSeparator", configuration.record_separator);
+                push_element(
+                    s,
+                    "EscapeChar",
+                    &configuration
+                        .escape_char
+                        .map(|escape_char| escape_xml(&escape_char.to_string()))
+                        .unwrap_or_default(),
+                );
+                push_element(s, "HasHeaders", &configuration.has_headers.to_string());
+                s.push_str("</DelimitedTextConfiguration>");
+            }
+            QuerySerialization::Json(configuration) => {
+                push_element(s, "Type", "json");
+                s.push_str("<JsonTextConfiguration>");
+                push_char(s, "RecordSeparator", configuration.record_separator);
+                s.push_str("</JsonTextConfiguration>");
+            }
+            QuerySerialization::Arrow(fields) => {
+                push_element(s, "Type", "arrow");
+                s.push_str("<ArrowConfiguration><Schema>");
+                for field in fields {
+                    s.push_str("<Field>");
+                    push_element(s, "Type", field.field_type.as_ref());
+                    if let Some(name) = &field.name {
+                        push_element(s, "Name", &escape_xml(name));
+                    }
+                    if let Some(precision) = field.precision {
+                        push_element(s, "Precision", &precision.to_string());
+                    }
+                    if let Some(scale) = field.scale {
+                        push_element(s, "Scale", &scale.to_string());
+                    }
+                    s.push_str("</Field>");
+                }
+                s.push_str("</Schema></ArrowConfiguration>");
+            }
+            QuerySerialization::Parquet => {
+                push_element(s, "Type", "parquet");
+                s.push_str("<ParquetTextConfiguration></ParquetTextConfiguration>");
+            }
+        }
+        s.push_str(&format!("</Format></{}>", name));
+    }
+}
+
+fn push_element(s: &mut String, name: &str, value: &str) {
+    s.push_str(&format!("<{}>{}</{}>", name, value, name));
+}
+
+fn push_char(s: &mut String, name: &str, value: char) {
+    push_element(s, name, &escape_xml(&value.to_string()));
+}
+
+/// A record of the results of a query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryRecord {
+    /// A chunk of the results, in the output format.
+    Data(Bytes),
+    Progress {
+        bytes_scanned: i64,
+        total_bytes: i64,
+    },
+    /// An error found while processing the blob. The query stops after a fatal error.
+    Error {
+        fatal: bool,
+        name: String,
+        description: String,
+        position: i64,
+    },
+    End {
+        total_bytes: i64,
+    },
+}
+
+impl TryFrom<AvroValue> for QueryRecord {
+    type Error = Error;
+
+    fn try_from(value: AvroValue) -> azure_core::Result<Self> {
+        let record = match value {
+            AvroValue::Record(record) => record,
+            value => {
+                return Err(Error::with_message(ErrorKind::DataConversion, || {
+                    format!("unexpected query record {:?}", value)
+                }))
+            }
+        };
+
+        // the records are named com.microsoft.azure.storage.queryBlobContents.<kind>
+        match record.name.rsplit('.').next() {
+            Some("resultData") => Ok(QueryRecord::Data(Bytes::copy_from_slice(
+                record.get_bytes("data")?,
+            ))),
+            Some("progress") => Ok(QueryRecord::Progress {
+                bytes_scanned: record.get_long("bytesScanned")?,
+                total_bytes: record.get_long("totalBytes")?,
+            }),
+            Some("error") => Ok(QueryRecord::Error {
+                fatal: record.get_bool("fatal")?,
+                name: record.get_str("name")?.to_owned(),
+                description: record.get_str("description")?.to_owned(),
+                position: record.get_long("position")?,
+            }),
+            Some("end") => Ok(QueryRecord::End {
+                total_bytes: record.get_long("totalBytes")?,
+            }),
+            _ => Err(Error::with_message(ErrorKind::DataConversion, || {
+                format!("unexpected query record {}", record.name)
+            })),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct QueryBlobBuilder {
+    blob_client: BlobClient,
+    expression: String,
+    input_serialization: Option<QuerySerialization>,
+    output_serialization: Option<QuerySerialization>,
+    snapshot: Option<Snapshot>,
+    lease_id: Option<LeaseId>,
+    if_tags: Option<IfTags>,
+    timeout: Option<Timeout>,
+    context: Context,
+}
+
+impl QueryBlobBuilder {
+    pub(crate) fn new(blob_client: BlobClient, expression: String) -> Self {
+        Self {
+            blob_client,
+            expression,
+            input_serialization: None,
+            output_serialization: None,
+            snapshot: None,
+            lease_id: None,
+            if_tags: None,
+            timeout: None,
+            context: Context::new(),
+        }
+    }
+
+    setters! {
+        input_serialization: QuerySerialization => Some(input_serialization),
+        output_serialization: QuerySerialization => Some(output_serialization),
+        snapshot: Snapshot => Some(snapshot),
+        lease_id: LeaseId => Some(lease_id),
+        if_tags: IfTags => Some(if_tags),
+        timeout: Timeout => Some(timeout),
+        context: Context => context,
+    }
+
+    /// Runs the query, streaming its results as they are decoded.
+    ///
+    /// A failure to send the query is returned as the first item of the stream.
+    pub fn into_stream(mut self) -> BoxStream<'static, azure_core::Result<QueryRecord>> {
+        let records = async move {
+            let mut url = self.blob_client.url_with_segments(None)?;
+
+            url.query_pairs_mut().append_pair("comp", "query");
+            self.snapshot.as_ref().append_to_url_query(&mut url);
+            self.timeout.append_to_url_query(&mut url);
+
+            let body = self.to_xml()?;
+            let mut request =
+                self.blob_client
+                    .prepare_request(url, Method::Post, Some(body.into()))?;
+            if matches!(self.input_serialization, Some(QuerySerialization::Parquet))
+                || matches!(
+                    self.output_serialization,
+                    Some(QuerySerialization::Arrow(_))
+                )
+            {
+                request.insert_header(VERSION, ARROW_PARQUET_VERSION);
+            }
+            request.add_optional_header(&self.lease_id);
+            request.add_optional_header(&self.if_tags);
+
+            let response = self
+                .blob_client
+                .send(&mut self.context, &mut request)
+                .await?;
+
+            let (_, _, body) = response.deconstruct();
+            Ok::<_, Error>(query_records(body))
+        };
+
+        futures::stream::once(records).try_flatten().boxed()
+    }
+
+    fn to_xml(&self) -> azure_core::Result<String> {
+        if let Some(QuerySerialization::Arrow(_)) = self.input_serialization {
+            return Err(Error::message(
+                ErrorKind::Other,
+                "Arrow is only supported as output serialization",
+            ));
+        }
+        if let Some(QuerySerialization::Parquet) = self.output_serialization {
+            return Err(Error::message(
+                ErrorKind::Other,
+                "Parquet is only supported as input serialization",
+            ));
+        }
+
+        let mut s = String::new();
+        s.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<QueryRequest>");
+        push_element(&mut s, "QueryType", "SQL");
+        push_element(&mut s, "Expression", &escape_xml(&self.expression));
+        if let Some(input_serialization) = &self.input_serialization {
+            input_serialization.push_xml(&mut s, "InputSerialization");
+        }
+        if let Some(output_serialization) = &self.output_serialization {
+            output_serialization.push_xml(&mut s, "OutputSerialization");
+        }
+        s.push_str("</QueryRequest>");
+        Ok(s)
+    }
+}
+
+fn query_records<S>(body: S) -> impl Stream<Item = azure_core::Result<QueryRecord>>
+where
+    S: Stream<Item = azure_core::Result<Bytes>> + Unpin,
+{
+    AvroReader::new(body)
+        .into_stream()
+        .and_then(|value| async move { QueryRecord::try_from(value) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avro::tests::{write_bytes, write_file, write_long};
+    use azure_storage::core::clients::{AsStorageClient, StorageAccountClient};
+
+    const SCHEMA: &str = r#"[
+        {
+            "type": "record",
+            "name": "com.microsoft.azure.storage.queryBlobContents.resultData",
+            "fields": [{"name": "data", "type": "bytes"}]
+        },
+        {
+            "type": "record",
+            "name": "com.microsoft.azure.storage.queryBlobContents.error",
+            "fields": [
+                {"name": "fatal", "type": "boolean"},
+                {"name": "name", "type": "string"},
+                {"name": "description", "type": "string"},
+                {"name": "position", "type": "long"}
+            ]
+        },
+        {
+            "type": "record",
+            "name": "com.microsoft.azure.storage.queryBlobContents.progress",
+            "fields": [
+                {"name": "bytesScanned", "type": "long"},
+                {"name": "totalBytes", "type": "long"}
+            ]
+        },
+        {
+            "type": "record",
+            "name": "com.microsoft.azure.storage.queryBlobContents.end",
+            "fields": [{"name": "totalBytes", "type": "long"}]
+        }
+    ]"#;
+
+    fn query_blob_builder() -> QueryBlobBuilder {
+        let blob_client = StorageAccountClient::new_emulator_default()
+            .storage_client()
+            .container_client("container")
+            .blob_client("blob");
+        QueryBlobBuilder::new(
+            (*blob_client).clone(),
+            "SELECT _2 FROM BlobStorage WHERE _1 < 'b'".to_owned(),
+        )
+    }
+
+    #[test]
+    fn test_query_request_xml() {
+        let builder = query_blob_builder()
+            .input_serialization(QuerySerialization::Delimited(DelimitedTextConfiguration {
+                column_separator: ';',
+                escape_char: Some('\\'),
+                has_headers: true,
+                ..Default::default()
+            }))
+            .output_serialization(QuerySerialization::Arrow(vec![ArrowField {
+                name: Some("price".to_owned()),
+                precision: Some(4),
+                scale: Some(2),
+                ..ArrowField::new(ArrowFieldType::Decimal)
+            }]));
+
+        assert_eq!(
+            builder.to_xml().unwrap(),
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<QueryRequest>\
+            <QueryType>SQL</QueryType>\
+            <Expression>SELECT _2 FROM BlobStorage WHERE _1 &lt; &apos;b&apos;</Expression>\
+            <InputSerialization><Format><Type>delimited</Type><DelimitedTextConfiguration>\
+            <ColumnSeparator>;</ColumnSeparator><FieldQuote>&quot;</FieldQuote>\
+            <RecordSeparator>\n</RecordSeparator><EscapeChar>\\</EscapeChar>\
+            <HasHeaders>true</HasHeaders></DelimitedTextConfiguration></Format></InputSerialization>\
+            <OutputSerialization><Format><Type>arrow</Type><ArrowConfiguration><Schema>\
+            <Field><Type>decimal</Type><Name>price</Name><Precision>4</Precision><Scale>2</Scale></Field>\
+            </Schema></ArrowConfiguration></Format></OutputSerialization>\
+            </QueryRequest>"
+        );
+
+        assert!(query_blob_builder()
+            .input_serialization(QuerySerialization::Arrow(Vec::new()))
+            .to_xml()
+            .is_err());
+        assert!(query_blob_builder()
+            .output_serialization(QuerySerialization::Parquet)
+            .to_xml()
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_query_records() {
+        let mut data = Vec::new();
+        write_long(&mut data, 0);
+        write_bytes(&mut data, b"a,1\n");
+        write_long(&mut data, 2);
+        write_long(&mut data, 4);
+        write_long(&mut data, 10);
+
+        let mut error = Vec::new();
+        write_long(&mut error, 1);
+        error.push(0);
+        write_bytes(&mut error, b"InvalidColumnOrdinal");
+        write_bytes(&mut error, b"Column ordinal is out of range.");
+        write_long(&mut error, 7);
+        write_long(&mut error, 3);
+        write_long(&mut error, 10);
+
+        let file = write_file(SCHEMA, &[(2, data), (2, error)]);
+        let chunks: Vec<azure_core::Result<Bytes>> = file
+            .chunks(5)
+            .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+            .collect();
+
+        let records: Vec<QueryRecord> = query_records(futures::stream::iter(chunks))
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            records,
+            vec![
+                QueryRecord::Data(Bytes::from_static(b"a,1\n")),
+                QueryRecord::Progress {
+                    bytes_scanned: 4,
+                    total_bytes: 10
+                },
+                QueryRecord::Error {
+                    fatal: false,
+                    name: "InvalidColumnOrdinal".to_owned(),
+                    description: "Column ordinal is out of range.".to_owned(),
+                    position: 7
+                },
+                QueryRecord::End { total_bytes: 10 },
+            ]
+        );
+    }
+}
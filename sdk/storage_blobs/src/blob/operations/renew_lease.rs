@@ -9,6 +9,7 @@ use chrono::{DateTime, Utc};
 #[derive(Debug, Clone)]
 pub struct RenewLeaseBuilder {
     blob_lease_client: BlobLeaseClient,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     context: Context,
 }
@@ -18,12 +19,14 @@ impl RenewLeaseBuilder {
         Self {
             blob_lease_client,
             context: Context::new(),
+            if_tags: None,
             timeout: None,
         }
     }
 
     setters! {
 
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
     }
 
@@ -37,6 +40,7 @@ impl RenewLeaseBuilder {
             let mut request =
                 self.blob_lease_client
                     .prepare_request(url, azure_core::Method::Put, None)?;
+            request.add_optional_header(&self.if_tags);
             request.insert_header(LEASE_ACTION, "renew");
             request.add_mandatory_header(self.blob_lease_client.lease_id());
 
@@ -0,0 +1,97 @@
+use crate::{headers::BLOB_SEALED, prelude::*};
+use azure_core::{headers::*, prelude::*, RequestId};
+use chrono::{DateTime, Utc};
+use std::convert::{TryFrom, TryInto};
+
+/// Seals an append blob, making it read only.
+#[derive(Debug, Clone)]
+pub struct SealAppendBlobBuilder {
+    blob_client: BlobClient,
+    condition_append_position: Option<ConditionAppendPosition>,
+    if_modified_since_condition: Option<IfModifiedSinceCondition>,
+    if_match_condition: Option<IfMatchCondition>,
+    lease_id: Option<LeaseId>,
+    timeout: Option<Timeout>,
+    context: Context,
+}
+
+impl SealAppendBlobBuilder {
+    pub(crate) fn new(blob_client: BlobClient) -> Self {
+        Self {
+            blob_client,
+            condition_append_position: None,
+            if_modified_since_condition: None,
+            if_match_condition: None,
+            lease_id: None,
+            timeout: None,
+            context: Context::new(),
+        }
+    }
+
+    setters! {
+        condition_append_position: ConditionAppendPosition => Some(condition_append_position),
+        if_modified_since_condition: IfModifiedSinceCondition => Some(if_modified_since_condition),
+        if_match_condition: IfMatchCondition => Some(if_match_condition),
+        lease_id: LeaseId => Some(lease_id),
+        timeout: Timeout => Some(timeout),
+        context: Context => context,
+    }
+
+    pub fn into_future(mut self) -> Response {
+        Box::pin(async move {
+            let mut url = self.blob_client.url_with_segments(None)?;
+
+            url.query_pairs_mut().append_pair("comp", "seal");
+            self.timeout.append_to_url_query(&mut url);
+
+            let mut request =
+                self.blob_client
+                    .prepare_request(url, azure_core::Method::Put, None)?;
+            request.add_optional_header(&self.condition_append_position);
+            request.add_optional_header(&self.if_modified_since_condition);
+            request.add_optional_header(&self.if_match_condition);
+            request.add_optional_header(&self.lease_id);
+
+            let response = self
+                .blob_client
+                .send(&mut self.context, &mut request)
+                .await?;
+
+            response.headers().try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SealAppendBlobResponse {
+    pub etag: String,
+    pub last_modified: DateTime<Utc>,
+    pub request_id: RequestId,
+    pub date: DateTime<Utc>,
+    pub sealed: bool,
+}
+
+impl TryFrom<&Headers> for SealAppendBlobResponse {
+    type Error = crate::Error;
+
+    fn try_from(headers: &Headers) -> azure_core::Result<Self> {
+        Ok(Self {
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+            request_id: request_id_from_headers(headers)?,
+            date: date_from_headers(headers)?,
+            sealed: headers.get_as(&BLOB_SEALED)?,
+        })
+    }
+}
+
+pub type Response = futures::future::BoxFuture<'static, azure_core::Result<SealAppendBlobResponse>>;
+
+#[cfg(feature = "into_future")]
+impl std::future::IntoFuture for SealAppendBlobBuilder {
+    type IntoFuture = Response;
+    type Output = <Response as std::future::Future>::Output;
+    fn into_future(self) -> Self::IntoFuture {
+        Self::into_future(self)
+    }
+}
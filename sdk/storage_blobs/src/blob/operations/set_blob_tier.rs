@@ -1,5 +1,5 @@
-use crate::prelude::*;
-use azure_core::{headers::*, prelude::*, RequestId};
+use crate::{prelude::*, service::BatchOperation};
+use azure_core::{headers::*, prelude::*, Request, RequestId};
 use std::convert::{TryFrom, TryInto};
 
 #[derive(Debug, Clone)]
@@ -10,6 +10,7 @@ pub struct SetBlobTierBuilder {
     rehydrate_priority: Option<RehydratePriority>,
     // URI Parameters
     blob_versioning: Option<BlobVersioning>,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     context: Context,
 }
@@ -22,6 +23,7 @@ impl SetBlobTierBuilder {
             context: Context::new(),
             rehydrate_priority: Some(RehydratePriority::Standard),
             blob_versioning: None,
+            if_tags: None,
             timeout: None,
         }
     }
@@ -30,6 +32,7 @@ impl SetBlobTierBuilder {
         access_tier: AccessTier => access_tier,
         rehydrate_priority: RehydratePriority => Some(rehydrate_priority),
         blob_versioning: BlobVersioning => Some(blob_versioning),
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
     }
 
@@ -43,6 +46,7 @@ impl SetBlobTierBuilder {
             let mut request =
                 self.blob_client
                     .prepare_request(url, azure_core::Method::Put, None)?;
+            request.add_optional_header(&self.if_tags);
             request.add_mandatory_header(&self.access_tier);
             request.add_optional_header(&self.rehydrate_priority);
 
@@ -53,6 +57,21 @@ impl SetBlobTierBuilder {
             response.headers().try_into()
         })
     }
+
+    /// Creates the operation to set the tier of the blob as part of a
+    /// [`BlobBatch`](crate::service::BlobBatch).
+    pub fn to_batch_operation(&self) -> azure_core::Result<BatchOperation> {
+        let mut url = self.blob_client.url_with_segments(None)?;
+        url.query_pairs_mut().append_pair("comp", "tier");
+        self.blob_versioning.append_to_url_query(&mut url);
+
+        let mut request = Request::new(url, azure_core::Method::Put);
+        request.add_optional_header(&self.if_tags);
+        request.add_mandatory_header(&self.access_tier);
+        request.add_optional_header(&self.rehydrate_priority);
+
+        Ok(BatchOperation::new(request))
+    }
 }
 
 #[derive(Debug, Clone)]
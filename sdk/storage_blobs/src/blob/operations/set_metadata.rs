@@ -7,6 +7,7 @@ use std::convert::{TryFrom, TryInto};
 pub struct SetMetadataBuilder {
     blob_client: BlobClient,
     lease_id: Option<LeaseId>,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     metadata: Option<Metadata>,
     context: Context,
@@ -18,6 +19,7 @@ impl SetMetadataBuilder {
             blob_client,
             lease_id: None,
             context: Context::new(),
+            if_tags: None,
             timeout: None,
             metadata: None,
         }
@@ -25,6 +27,7 @@ impl SetMetadataBuilder {
 
     setters! {
         lease_id: LeaseId => Some(lease_id),
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
 
         metadata: Metadata => Some(metadata),
@@ -38,6 +41,7 @@ impl SetMetadataBuilder {
             self.timeout.append_to_url_query(&mut url);
 
             let mut request = self.blob_client.prepare_request(url, Method::Put, None)?;
+            request.add_optional_header(&self.if_tags);
             request.add_optional_header(&self.lease_id);
             if let Some(metadata) = &self.metadata {
                 for m in metadata.iter() {
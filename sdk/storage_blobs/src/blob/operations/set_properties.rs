@@ -13,6 +13,7 @@ use std::convert::{TryFrom, TryInto};
 pub struct SetPropertiesBuilder {
     blob_client: BlobClient,
     lease_id: Option<LeaseId>,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     cache_control: Option<BlobCacheControl>,
     content_type: Option<BlobContentType>,
@@ -28,6 +29,7 @@ impl SetPropertiesBuilder {
         Self {
             blob_client,
             lease_id: None,
+            if_tags: None,
             timeout: None,
             cache_control: None,
             content_type: None,
@@ -65,6 +67,7 @@ impl SetPropertiesBuilder {
 
     setters! {
         lease_id: LeaseId => Some(lease_id),
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
         cache_control: BlobCacheControl => Some(cache_control),
         content_type: BlobContentType => Some(content_type),
@@ -83,6 +86,7 @@ impl SetPropertiesBuilder {
             self.timeout.append_to_url_query(&mut url);
 
             let mut request = self.blob_client.prepare_request(url, Method::Put, None)?;
+            request.add_optional_header(&self.if_tags);
             request.add_optional_header(&self.lease_id);
             request.add_optional_header(&self.cache_control);
             request.add_optional_header(&self.content_type);
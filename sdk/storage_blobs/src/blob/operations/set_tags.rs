@@ -0,0 +1,90 @@
+use crate::{blob::Tags, prelude::*};
+use azure_core::{headers::*, prelude::*, Method, RequestId};
+use chrono::{DateTime, Utc};
+use std::convert::{TryFrom, TryInto};
+
+/// Replaces all the tags of the blob.
+#[derive(Debug, Clone)]
+pub struct SetTagsBuilder {
+    blob_client: BlobClient,
+    tags: Tags,
+    version_id: Option<VersionId>,
+    lease_id: Option<LeaseId>,
+    if_tags: Option<IfTags>,
+    timeout: Option<Timeout>,
+    context: Context,
+}
+
+impl SetTagsBuilder {
+    pub(crate) fn new(blob_client: BlobClient, tags: Tags) -> Self {
+        Self {
+            blob_client,
+            tags,
+            version_id: None,
+            lease_id: None,
+            if_tags: None,
+            timeout: None,
+            context: Context::new(),
+        }
+    }
+
+    setters! {
+        version_id: VersionId => Some(version_id),
+        lease_id: LeaseId => Some(lease_id),
+        if_tags: IfTags => Some(if_tags),
+        timeout: Timeout => Some(timeout),
+        context: Context => context,
+    }
+
+    pub fn into_future(mut self) -> Response {
+        Box::pin(async move {
+            let mut url = self.blob_client.url_with_segments(None)?;
+
+            url.query_pairs_mut().append_pair("comp", "tags");
+            self.version_id.as_ref().append_to_url_query(&mut url);
+            self.timeout.append_to_url_query(&mut url);
+
+            let body = self.tags.to_xml();
+            let mut request =
+                self.blob_client
+                    .prepare_request(url, Method::Put, Some(body.into()))?;
+            request.add_optional_header(&self.lease_id);
+            request.add_optional_header(&self.if_tags);
+
+            let response = self
+                .blob_client
+                .send(&mut self.context, &mut request)
+                .await?;
+
+            response.headers().try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SetTagsResponse {
+    pub request_id: RequestId,
+    pub date: DateTime<Utc>,
+}
+
+impl TryFrom<&Headers> for SetTagsResponse {
+    type Error = crate::Error;
+
+    fn try_from(headers: &Headers) -> Result<Self, Self::Error> {
+        Ok(SetTagsResponse {
+            request_id: request_id_from_headers(headers)?,
+            date: date_from_headers(headers)?,
+        })
+    }
+}
+
+pub type Response = futures::future::BoxFuture<'static, azure_core::Result<SetTagsResponse>>;
+
+#[cfg(feature = "into_future")]
+impl std::future::IntoFuture for SetTagsBuilder {
+    type IntoFuture = Response;
+    type Output = <Response as std::future::Future>::Output;
+    fn into_future(self) -> Self::IntoFuture {
+        Self::into_future(self)
+    }
+}
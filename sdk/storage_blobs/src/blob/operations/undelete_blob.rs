@@ -0,0 +1,72 @@
+use crate::prelude::*;
+use azure_core::{headers::*, prelude::*, Method, RequestId};
+use chrono::{DateTime, Utc};
+use std::convert::{TryFrom, TryInto};
+
+/// Restores the soft deleted blob and its soft deleted snapshots.
+#[derive(Debug, Clone)]
+pub struct UndeleteBlobBuilder {
+    blob_client: BlobClient,
+    timeout: Option<Timeout>,
+    context: Context,
+}
+
+impl UndeleteBlobBuilder {
+    pub(crate) fn new(blob_client: BlobClient) -> Self {
+        Self {
+            blob_client,
+            timeout: None,
+            context: Context::new(),
+        }
+    }
+
+    setters! {
+        timeout: Timeout => Some(timeout),
+        context: Context => context,
+    }
+
+    pub fn into_future(mut self) -> Response {
+        Box::pin(async move {
+            let mut url = self.blob_client.url_with_segments(None)?;
+
+            url.query_pairs_mut().append_pair("comp", "undelete");
+            self.timeout.append_to_url_query(&mut url);
+
+            let mut request = self.blob_client.prepare_request(url, Method::Put, None)?;
+
+            let response = self
+                .blob_client
+                .send(&mut self.context, &mut request)
+                .await?;
+            response.headers().try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UndeleteBlobResponse {
+    pub request_id: RequestId,
+    pub date: DateTime<Utc>,
+}
+
+impl TryFrom<&Headers> for UndeleteBlobResponse {
+    type Error = crate::Error;
+
+    fn try_from(headers: &Headers) -> Result<Self, Self::Error> {
+        Ok(UndeleteBlobResponse {
+            request_id: request_id_from_headers(headers)?,
+            date: date_from_headers(headers)?,
+        })
+    }
+}
+
+pub type Response = futures::future::BoxFuture<'static, azure_core::Result<UndeleteBlobResponse>>;
+
+#[cfg(feature = "into_future")]
+impl std::future::IntoFuture for UndeleteBlobBuilder {
+    type IntoFuture = Response;
+    type Output = <Response as std::future::Future>::Output;
+    fn into_future(self) -> Self::IntoFuture {
+        Self::into_future(self)
+    }
+}
@@ -13,6 +13,7 @@ pub struct UpdatePageBuilder {
     sequence_number_condition: Option<SequenceNumberCondition>,
     if_modified_since_condition: Option<IfModifiedSinceCondition>,
     if_match_condition: Option<IfMatchCondition>,
+    if_tags: Option<IfTags>,
     timeout: Option<Timeout>,
     lease_id: Option<LeaseId>,
     context: Context,
@@ -29,6 +30,7 @@ impl UpdatePageBuilder {
             if_modified_since_condition: None,
             if_match_condition: None,
             context: Context::new(),
+            if_tags: None,
             timeout: None,
             lease_id: None,
         }
@@ -39,6 +41,7 @@ impl UpdatePageBuilder {
         sequence_number_condition: SequenceNumberCondition => Some(sequence_number_condition),
         if_modified_since_condition: IfModifiedSinceCondition => Some(if_modified_since_condition),
         if_match_condition: IfMatchCondition => Some(if_match_condition),
+        if_tags: IfTags => Some(if_tags),
         timeout: Timeout => Some(timeout),
         lease_id: LeaseId => Some(lease_id),
     }
@@ -55,6 +58,7 @@ impl UpdatePageBuilder {
                 azure_core::Method::Put,
                 Some(self.content.clone()),
             )?;
+            request.add_optional_header(&self.if_tags);
             request.insert_header(PAGE_WRITE, "update");
             request.insert_header(BLOB_TYPE, "PageBlob");
             request.add_mandatory_header(&self.ba512_range);
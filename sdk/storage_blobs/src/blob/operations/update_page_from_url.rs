@@ -0,0 +1,157 @@
+use crate::{blob::SourceContentMD5, headers::SOURCE_RANGE, prelude::*, BA512Range};
+use azure_core::{
+    error::{Error, ErrorKind},
+    headers::*,
+    prelude::*,
+    RequestId,
+};
+use azure_storage::{headers::consistency_from_headers, ConsistencyCRC64, ConsistencyMD5};
+use chrono::{DateTime, Utc};
+use std::convert::{TryFrom, TryInto};
+use url::Url;
+
+/// Writes the pages of `ba512_range` with the bytes of `source_range` read from `source_url`.
+///
+/// Both ranges must have the same length. The source must be public or authorized with a SAS
+/// token in the url.
+#[derive(Debug, Clone)]
+pub struct UpdatePageFromUrlBuilder {
+    blob_client: BlobClient,
+    ba512_range: BA512Range,
+    source_url: Url,
+    source_range: Range,
+    source_content_md5: Option<SourceContentMD5>,
+    sequence_number_condition: Option<SequenceNumberCondition>,
+    if_modified_since_condition: Option<IfModifiedSinceCondition>,
+    if_match_condition: Option<IfMatchCondition>,
+    if_source_since_condition: Option<IfSourceModifiedSinceCondition>,
+    if_source_match_condition: Option<IfSourceMatchCondition>,
+    lease_id: Option<LeaseId>,
+    if_tags: Option<IfTags>,
+    timeout: Option<Timeout>,
+    context: Context,
+}
+
+impl UpdatePageFromUrlBuilder {
+    pub(crate) fn new(
+        blob_client: BlobClient,
+        ba512_range: BA512Range,
+        source_url: Url,
+        source_range: Range,
+    ) -> Self {
+        Self {
+            blob_client,
+            ba512_range,
+            source_url,
+            source_range,
+            source_content_md5: None,
+            sequence_number_condition: None,
+            if_modified_since_condition: None,
+            if_match_condition: None,
+            if_source_since_condition: None,
+            if_source_match_condition: None,
+            lease_id: None,
+            if_tags: None,
+            timeout: None,
+            context: Context::new(),
+        }
+    }
+
+    setters! {
+        source_content_md5: SourceContentMD5 => Some(source_content_md5),
+        sequence_number_condition: SequenceNumberCondition => Some(sequence_number_condition),
+        if_modified_since_condition: IfModifiedSinceCondition => Some(if_modified_since_condition),
+        if_match_condition: IfMatchCondition => Some(if_match_condition),
+        if_source_since_condition: IfSourceModifiedSinceCondition => Some(if_source_since_condition),
+        if_source_match_condition: IfSourceMatchCondition => Some(if_source_match_condition),
+        lease_id: LeaseId => Some(lease_id),
+        if_tags: IfTags => Some(if_tags),
+        timeout: Timeout => Some(timeout),
+        context: Context => context,
+    }
+
+    pub fn into_future(mut self) -> Response {
+        Box::pin(async move {
+            if self.source_range.len() != self.ba512_range.size() {
+                return Err(Error::with_message(ErrorKind::Other, || {
+                    format!(
+                        "the source range has {} bytes but the page range has {}",
+                        self.source_range.len(),
+                        self.ba512_range.size()
+                    )
+                }));
+            }
+
+            let mut url = self.blob_client.url_with_segments(None)?;
+
+            url.query_pairs_mut().append_pair("comp", "page");
+            self.timeout.append_to_url_query(&mut url);
+
+            let mut request =
+                self.blob_client
+                    .prepare_request(url, azure_core::Method::Put, None)?;
+            request.insert_header(PAGE_WRITE, "update");
+            request.add_mandatory_header(&self.ba512_range);
+            request.insert_header(COPY_SOURCE, self.source_url.to_string());
+            request.insert_header(SOURCE_RANGE, self.source_range.to_string());
+            request.add_optional_header(&self.source_content_md5);
+            request.add_optional_header(&self.sequence_number_condition);
+            request.add_optional_header(&self.if_modified_since_condition);
+            request.add_optional_header(&self.if_match_condition);
+            request.add_optional_header(&self.if_source_since_condition);
+            request.add_optional_header(&self.if_source_match_condition);
+            request.add_optional_header(&self.lease_id);
+            request.add_optional_header(&self.if_tags);
+
+            let response = self
+                .blob_client
+                .send(&mut self.context, &mut request)
+                .await?;
+
+            response.headers().try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpdatePageFromUrlResponse {
+    pub etag: String,
+    pub last_modified: DateTime<Utc>,
+    pub content_md5: Option<ConsistencyMD5>,
+    pub content_crc64: Option<ConsistencyCRC64>,
+    pub sequence_number: u64,
+    pub request_id: RequestId,
+    pub date: DateTime<Utc>,
+    pub request_server_encrypted: bool,
+}
+
+impl TryFrom<&Headers> for UpdatePageFromUrlResponse {
+    type Error = crate::Error;
+
+    fn try_from(headers: &Headers) -> azure_core::Result<Self> {
+        let (content_md5, content_crc64) = consistency_from_headers(headers)?;
+
+        Ok(Self {
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+            content_md5,
+            content_crc64,
+            sequence_number: sequence_number_from_headers(headers)?,
+            request_id: request_id_from_headers(headers)?,
+            date: date_from_headers(headers)?,
+            request_server_encrypted: request_server_encrypted_from_headers(headers)?,
+        })
+    }
+}
+
+pub type Response =
+    futures::future::BoxFuture<'static, azure_core::Result<UpdatePageFromUrlResponse>>;
+
+#[cfg(feature = "into_future")]
+impl std::future::IntoFuture for UpdatePageFromUrlBuilder {
+    type IntoFuture = Response;
+    type Output = <Response as std::future::Future>::Output;
+    fn into_future(self) -> Self::IntoFuture {
+        Self::into_future(self)
+    }
+}
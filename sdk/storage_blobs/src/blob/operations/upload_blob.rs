@@ -0,0 +1,386 @@
+use crate::{blob::Tags, prelude::*};
+use azure_core::{
+    error::{Error, ErrorKind, ResultExt},
+    prelude::*,
+    RequestId,
+};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::{AsyncRead, AsyncReadExt, StreamExt, TryStreamExt};
+use std::sync::Arc;
+
+/// Uploads smaller than this are sent with a single Put Blob request.
+const DEFAULT_SINGLE_UPLOAD_THRESHOLD: u64 = 32 * 1024 * 1024;
+const DEFAULT_BLOCK_SIZE: u64 = 8 * 1024 * 1024;
+const MAX_BLOCK_SIZE: u64 = 4000 * 1024 * 1024;
+const MAX_BLOCKS: u64 = 50_000;
+const DEFAULT_PARALLELISM: usize = 8;
+const DEFAULT_BLOCK_RETRIES: u32 = 3;
+
+type ProgressCallback = Arc<dyn Fn(u64) + Send + Sync>;
+
+/// Sets the blob properties and conditions of an [`UploadBlobBuilder`] on a Put Blob or Put Block
+/// List builder, which share the same setters.
+macro_rules! forward_options {
+    ($upload:expr, $builder:expr) => {{
+        let upload: &UploadBlobBuilder = $upload;
+        let mut builder = $builder.context(upload.context.clone());
+        if let Some(content_type) = upload.content_type.clone() {
+            builder = builder.content_type(content_type);
+        }
+        if let Some(content_encoding) = upload.content_encoding.clone() {
+            builder = builder.content_encoding(content_encoding);
+        }
+        if let Some(content_language) = upload.content_language.clone() {
+            builder = builder.content_language(content_language);
+        }
+        if let Some(content_disposition) = upload.content_disposition.clone() {
+            builder = builder.content_disposition(content_disposition);
+        }
+        if let Some(metadata) = upload.metadata.clone() {
+            builder = builder.metadata(metadata);
+        }
+        if let Some(access_tier) = upload.access_tier {
+            builder = builder.access_tier(access_tier);
+        }
+        if let Some(tags) = upload.tags.clone() {
+            builder = builder.tags(tags);
+        }
+        if let Some(lease_id) = upload.lease_id {
+            builder = builder.lease_id(lease_id);
+        }
+        if let Some(if_tags) = upload.if_tags.clone() {
+            builder = builder.if_tags(if_tags);
+        }
+        if let Some(timeout) = upload.timeout {
+            builder = builder.timeout(timeout);
+        }
+        builder
+    }};
+}
+
+/// Uploads a stream of unknown length as a block blob.
+///
+/// Streams shorter than `single_upload_threshold` are uploaded with a single Put Blob request.
+/// Longer ones are staged as blocks, `parallelism` of them at a time, and then committed with
+/// Put Block List, so that at most `parallelism` blocks are held in memory.
+#[derive(Clone)]
+pub struct UploadBlobBuilder {
+    blob_client: BlobClient,
+    block_size: Option<u64>,
+    content_length: Option<u64>,
+    single_upload_threshold: u64,
+    parallelism: usize,
+    block_retries: u32,
+    progress: Option<ProgressCallback>,
+    content_type: Option<ContentType>,
+    content_encoding: Option<ContentEncoding>,
+    content_language: Option<ContentLanguage>,
+    content_disposition: Option<ContentDisposition>,
+    metadata: Option<Metadata>,
+    access_tier: Option<AccessTier>,
+    tags: Option<Tags>,
+    lease_id: Option<LeaseId>,
+    if_tags: Option<IfTags>,
+    timeout: Option<Timeout>,
+    context: Context,
+}
+
+impl UploadBlobBuilder {
+    pub(crate) fn new(blob_client: BlobClient) -> Self {
+        Self {
+            blob_client,
+            block_size: None,
+            content_length: None,
+            single_upload_threshold: DEFAULT_SINGLE_UPLOAD_THRESHOLD,
+            parallelism: DEFAULT_PARALLELISM,
+            block_retries: DEFAULT_BLOCK_RETRIES,
+            progress: None,
+            content_type: None,
+            content_encoding: None,
+            content_language: None,
+            content_disposition: None,
+            metadata: None,
+            access_tier: None,
+            tags: None,
+            lease_id: None,
+            if_tags: None,
+            timeout: None,
+            context: Context::new(),
+        }
+    }
+
+    setters! {
+        block_size: u64 => Some(block_size),
+        content_length: u64 => Some(content_length),
+        single_upload_threshold: u64 => single_upload_threshold,
+        parallelism: usize => parallelism,
+        block_retries: u32 => block_retries,
+        content_type: ContentType => Some(content_type),
+        content_encoding: ContentEncoding => Some(content_encoding),
+        content_language: ContentLanguage => Some(content_language),
+        content_disposition: ContentDisposition => Some(content_disposition),
+        metadata: Metadata => Some(metadata),
+        access_tier: AccessTier => Some(access_tier),
+        tags: Tags => Some(tags),
+        lease_id: LeaseId => Some(lease_id),
+        if_tags: IfTags => Some(if_tags),
+        timeout: Timeout => Some(timeout),
+        context: Context => context,
+    }
+
+    /// Calls `progress` with the total number of bytes uploaded each time a block is staged.
+    pub fn progress<F>(self, progress: F) -> Self
+    where
+        F: Fn(u64) + Send + Sync + 'static,
+    {
+        Self {
+            progress: Some(Arc::new(progress)),
+            ..self
+        }
+    }
+
+    /// Uploads everything read from `reader`, such as a `SeekableStream`.
+    pub async fn from_reader<R>(self, mut reader: R) -> azure_core::Result<UploadBlobResponse>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let first = read_chunk(&mut reader, self.single_upload_threshold).await?;
+        if (first.len() as u64) < self.single_upload_threshold {
+            return self.upload_single(first).await;
+        }
+
+        let block_size = self.chosen_block_size();
+        if let Some(content_length) = self.content_length {
+            if content_length.div_ceil(block_size) > MAX_BLOCKS {
+                return Err(too_many_blocks(block_size));
+            }
+        }
+        let initial_blocks = split_bytes(first, block_size);
+        let remaining_blocks = futures::stream::try_unfold(reader, move |mut reader| async move {
+            let block = read_chunk(&mut reader, block_size).await?;
+            azure_core::Result::Ok(if block.is_empty() {
+                None
+            } else {
+                Some((block, reader))
+            })
+        });
+
+        // every block ID of a blob must have the same length
+        let prefix = uuid::Uuid::new_v4();
+        let this = &self;
+        let staged = futures::stream::iter(initial_blocks.into_iter().map(azure_core::Result::Ok))
+            .chain(remaining_blocks)
+            .enumerate()
+            .map(|(index, block)| {
+                let block_id = BlockId::new(format!("{}-{:08}", prefix, index));
+                async move {
+                    // fail before staging anything past the last block a blob can have
+                    if index as u64 >= MAX_BLOCKS {
+                        return Err(too_many_blocks(block_size));
+                    }
+                    this.stage_block(block_id, block?).await
+                }
+            })
+            .buffered(self.parallelism.max(1));
+        futures::pin_mut!(staged);
+
+        let mut block_list = BlockList::default();
+        let mut content_length = 0;
+        while let Some((block_id, len)) = staged.try_next().await? {
+            block_list
+                .blocks
+                .push(BlobBlockType::new_uncommitted(block_id));
+            content_length += len;
+            if let Some(progress) = &self.progress {
+                progress(content_length);
+            }
+        }
+
+        let builder = forward_options!(&self, self.blob_client.put_block_list(block_list));
+        let response = builder.into_future().await?;
+
+        Ok(UploadBlobResponse {
+            etag: response.etag,
+            last_modified: response.last_modified,
+            request_id: response.request_id,
+            date: response.date,
+            content_length,
+        })
+    }
+
+    /// The block size, chosen so that a stream of `content_length` bytes fits in the maximum
+    /// number of blocks if not set.
+    fn chosen_block_size(&self) -> u64 {
+        self.block_size.unwrap_or_else(|| {
+            let content_length = self.content_length.unwrap_or(0);
+            let block_size = content_length.div_ceil(MAX_BLOCKS);
+            block_size.clamp(DEFAULT_BLOCK_SIZE, MAX_BLOCK_SIZE)
+        })
+    }
+
+    async fn upload_single(&self, body: Bytes) -> azure_core::Result<UploadBlobResponse> {
+        let content_length = body.len() as u64;
+        let builder = forward_options!(self, self.blob_client.put_block_blob(body));
+        let response = builder.into_future().await?;
+        if let Some(progress) = &self.progress {
+            progress(content_length);
+        }
+
+        Ok(UploadBlobResponse {
+            etag: response.etag,
+            last_modified: response.last_modified,
+            request_id: response.request_id,
+            date: response.date,
+            content_length,
+        })
+    }
+
+    /// Stages a block, sending it again up to `block_retries` times if it was corrupted on the
+    /// way, which the pipeline does not retry.
+    async fn stage_block(
+        &self,
+        block_id: BlockId,
+        block: Bytes,
+    ) -> azure_core::Result<(BlockId, u64)> {
+        let mut retries = 0;
+        loop {
+            let mut builder = self
+                .blob_client
+                .put_block(block_id.clone(), block.clone())
+                .hash(md5::compute(&block))
+                .context(self.context.clone());
+            if let Some(lease_id) = self.lease_id {
+                builder = builder.lease_id(lease_id);
+            }
+            if let Some(timeout) = self.timeout {
+                builder = builder.timeout(timeout);
+            }
+            match builder.into_future().await {
+                Ok(_) => return Ok((block_id, block.len() as u64)),
+                Err(err) if retries < self.block_retries && is_retryable(&err) => {
+                    log::debug!("retrying block {:?} after error: {}", block_id, err);
+                    retries += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for UploadBlobBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UploadBlobBuilder")
+            .field("blob_client", &self.blob_client)
+            .field("block_size", &self.block_size)
+            .field("content_length", &self.content_length)
+            .field("single_upload_threshold", &self.single_upload_threshold)
+            .field("parallelism", &self.parallelism)
+            .field("block_retries", &self.block_retries)
+            .field("progress", &self.progress.as_ref().map(|_| "Fn(u64)"))
+            .field("content_type", &self.content_type)
+            .field("content_encoding", &self.content_encoding)
+            .field("content_language", &self.content_language)
+            .field("content_disposition", &self.content_disposition)
+            .field("metadata", &self.metadata)
+            .field("access_tier", &self.access_tier)
+            .field("tags", &self.tags)
+            .field("lease_id", &self.lease_id)
+            .field("if_tags", &self.if_tags)
+            .field("timeout", &self.timeout)
+            .field("context", &self.context)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UploadBlobResponse {
+    pub etag: String,
+    pub last_modified: DateTime<Utc>,
+    pub request_id: RequestId,
+    pub date: DateTime<Utc>,
+    /// The number of bytes uploaded.
+    pub content_length: u64,
+}
+
+/// Whether the block didn't match its MD5 when it reached the service, so that sending it again
+/// may succeed. Throttling, server errors and transport errors are retried by the pipeline.
+fn is_retryable(err: &Error) -> bool {
+    matches!(
+        err.kind(),
+        ErrorKind::HttpResponse { status: 400, error_code: Some(error_code) }
+            if error_code == "Md5Mismatch"
+    )
+}
+
+fn too_many_blocks(block_size: u64) -> Error {
+    Error::with_message(ErrorKind::Other, || {
+        format!(
+            "the data needs more than {} blocks of {} bytes, the most a blob can have; use a larger block size",
+            MAX_BLOCKS, block_size
+        )
+    })
+}
+
+/// Reads `len` bytes, or less if the reader ends first.
+async fn read_chunk<R>(reader: &mut R, len: u64) -> azure_core::Result<Bytes>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut buffer = Vec::new();
+    reader
+        .take(len)
+        .read_to_end(&mut buffer)
+        .await
+        .context(ErrorKind::Io, "failed to read the data to upload")?;
+    Ok(buffer.into())
+}
+
+fn split_bytes(mut bytes: Bytes, block_size: u64) -> Vec<Bytes> {
+    let mut blocks = Vec::new();
+    while !bytes.is_empty() {
+        let len = bytes.len().min(block_size as usize);
+        blocks.push(bytes.split_to(len));
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_chunk() {
+        let mut reader = futures::io::Cursor::new(b"0123456789".to_vec());
+        let chunk = futures::executor::block_on(read_chunk(&mut reader, 4)).unwrap();
+        assert_eq!(chunk.as_ref(), b"0123");
+        let chunk = futures::executor::block_on(read_chunk(&mut reader, 8)).unwrap();
+        assert_eq!(chunk.as_ref(), b"456789");
+        let chunk = futures::executor::block_on(read_chunk(&mut reader, 8)).unwrap();
+        assert!(chunk.is_empty());
+
+        let blocks = split_bytes(Bytes::from_static(b"0123456789"), 4);
+        assert_eq!(blocks, vec!["0123", "4567", "89"]);
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        let error = |status, error_code: Option<&str>| {
+            Error::message(
+                ErrorKind::HttpResponse {
+                    status,
+                    error_code: error_code.map(ToOwned::to_owned),
+                },
+                "",
+            )
+        };
+        assert!(is_retryable(&error(400, Some("Md5Mismatch"))));
+        assert!(!is_retryable(&error(400, Some("InvalidBlockId"))));
+        assert!(!is_retryable(&error(400, None)));
+        // already retried by the pipeline
+        assert!(!is_retryable(&error(500, None)));
+        assert!(!is_retryable(&error(503, None)));
+        assert!(!is_retryable(&error(429, None)));
+        assert!(!is_retryable(&Error::message(ErrorKind::Io, "reset")));
+    }
+}
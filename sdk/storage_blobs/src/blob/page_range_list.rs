@@ -23,15 +23,24 @@ struct PageRange {
     pub end: End,
 }
 
+/// The page and clear ranges of a diff are interleaved, so they are read as a single list.
+#[derive(Debug, Deserialize)]
+enum PageListItem {
+    PageRange(PageRange),
+    ClearRange(PageRange),
+}
+
 #[derive(Debug, Deserialize)]
 struct PageList {
-    #[serde(rename = "PageRange")]
-    pub page_list: Option<Vec<PageRange>>,
+    #[serde(rename = "$value", default)]
+    pub items: Vec<PageListItem>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct PageRangeList {
     pub ranges: Vec<Range>,
+    /// The ranges cleared since the previous snapshot, when getting a diff.
+    pub clear_ranges: Vec<Range>,
 }
 
 impl PageRangeList {
@@ -39,12 +48,16 @@ impl PageRangeList {
         let pl: PageList =
             serde_xml_rs::de::from_reader(xml.as_bytes()).map_kind(ErrorKind::DataConversion)?;
 
-        let mut prl = PageRangeList { ranges: Vec::new() };
+        let mut prl = PageRangeList::default();
 
-        if let Some(range_list) = pl.page_list {
-            for range in range_list {
-                prl.ranges
-                    .push(Range::new(range.start.value, range.end.value));
+        for item in pl.items {
+            match item {
+                PageListItem::PageRange(range) => prl
+                    .ranges
+                    .push(Range::new(range.start.value, range.end.value)),
+                PageListItem::ClearRange(range) => prl
+                    .clear_ranges
+                    .push(Range::new(range.start.value, range.end.value)),
             }
         }
 
@@ -76,5 +89,29 @@ mod test {
         assert!(prl.ranges[0].end == 511);
         assert!(prl.ranges[1].start == 1024);
         assert!(prl.ranges[1].end == 1535);
+        assert!(prl.clear_ranges.is_empty());
+    }
+
+    #[test]
+    fn try_parse_diff() {
+        let page_list = "<?xml version=\"1.0\" encoding=\"utf-8\"?>
+            <PageList>
+              <PageRange><Start>0</Start><End>511</End></PageRange>
+              <ClearRange><Start>512</Start><End>1023</End></ClearRange>
+              <PageRange><Start>1024</Start><End>2047</End></PageRange>
+              <ClearRange><Start>4096</Start><End>4607</End></ClearRange>
+            </PageList>";
+
+        let prl = PageRangeList::try_from_xml(page_list).unwrap();
+        assert_eq!(prl.ranges, vec![Range::new(0, 511), Range::new(1024, 2047)]);
+        assert_eq!(
+            prl.clear_ranges,
+            vec![Range::new(512, 1023), Range::new(4096, 4607)]
+        );
+
+        let prl =
+            PageRangeList::try_from_xml("<?xml version=\"1.0\" encoding=\"utf-8\"?><PageList />")
+                .unwrap();
+        assert_eq!(prl, PageRangeList::default());
     }
 }
@@ -1,4 +1,4 @@
-use crate::{blob::operations::*, prelude::*, BA512Range};
+use crate::{blob::operations::*, blob::Tags, prelude::*, BA512Range};
 use azure_core::Method;
 use azure_core::{
     error::{Error, ErrorKind},
@@ -9,7 +9,9 @@ use azure_storage::core::{
     clients::StorageCredentials,
     prelude::*,
     shared_access_signature::{
-        service_sas::{BlobSharedAccessSignatureBuilder, BlobSignedResource, SetResources},
+        service_sas::{
+            BlobSharedAccessSignatureBuilder, BlobSignedResource, SetResources, UserDelegationKey,
+        },
         SasToken,
     },
 };
@@ -89,6 +91,13 @@ impl BlobClient {
         Ok(blob)
     }
 
+    /// Downloads the blob into a file or writer, fetching ranges concurrently.
+    ///
+    /// This is much faster than `get` for large blobs.
+    pub fn download(&self) -> DownloadBlobBuilder {
+        DownloadBlobBuilder::new(self.clone())
+    }
+
     pub fn get_properties(&self) -> GetPropertiesBuilder {
         GetPropertiesBuilder::new(self.clone())
     }
@@ -109,6 +118,17 @@ impl BlobClient {
         SetMetadataBuilder::new(self.clone())
     }
 
+    /// Gets the index tags of the blob, or of one of its snapshots with `snapshot` or of one of its
+    /// versions with `version_id`.
+    pub fn get_tags(&self) -> GetTagsBuilder {
+        GetTagsBuilder::new(self.clone())
+    }
+
+    /// Replaces the index tags of the blob, or of one of its versions with `version_id`.
+    pub fn set_tags(&self, tags: impl Into<Tags>) -> SetTagsBuilder {
+        SetTagsBuilder::new(self.clone(), tags.into())
+    }
+
     pub fn set_blob_tier(&self) -> SetBlobTierBuilder {
         SetBlobTierBuilder::new(self.clone())
     }
@@ -121,6 +141,17 @@ impl BlobClient {
         UpdatePageBuilder::new(self.clone(), ba512_range, content.into())
     }
 
+    /// Writes the pages of `ba512_range` with the bytes of `source_range` of the blob at
+    /// `copy_source`.
+    pub fn update_page_from_url(
+        &self,
+        ba512_range: BA512Range,
+        copy_source: Url,
+        source_range: impl Into<Range>,
+    ) -> UpdatePageFromUrlBuilder {
+        UpdatePageFromUrlBuilder::new(self.clone(), ba512_range, copy_source, source_range.into())
+    }
+
     pub fn get_page_ranges(&self) -> GetPageRangesBuilder {
         GetPageRangesBuilder::new(self.clone())
     }
@@ -137,6 +168,16 @@ impl BlobClient {
         DeleteBlobVersionBuilder::new(self.clone(), version_id)
     }
 
+    /// Restores the soft deleted blob and its soft deleted snapshots.
+    pub fn undelete(&self) -> UndeleteBlobBuilder {
+        UndeleteBlobBuilder::new(self.clone())
+    }
+
+    /// Runs a SQL `expression` on the contents of the blob.
+    pub fn query(&self, expression: impl Into<String>) -> QueryBlobBuilder {
+        QueryBlobBuilder::new(self.clone(), expression.into())
+    }
+
     pub fn copy(&self, copy_source: Url) -> CopyBlobBuilder {
         CopyBlobBuilder::new(self.clone(), copy_source)
     }
@@ -145,6 +186,12 @@ impl BlobClient {
         CopyBlobFromUrlBuilder::new(self.clone(), copy_source)
     }
 
+    /// Copies the pages of a page blob snapshot changed since the last incremental copy to this
+    /// blob.
+    pub fn incremental_copy(&self, copy_source: Url) -> IncrementalCopyBlobBuilder {
+        IncrementalCopyBlobBuilder::new(self.clone(), copy_source)
+    }
+
     pub fn put_page_blob(&self, length: u128) -> PutPageBlobBuilder {
         PutPageBlobBuilder::new(self.clone(), length)
     }
@@ -165,10 +212,24 @@ impl BlobClient {
         PutBlockBlobBuilder::new(self.clone(), body.into())
     }
 
+    /// Uploads a stream as a block blob, staging blocks concurrently if it is large.
+    pub fn upload(&self) -> UploadBlobBuilder {
+        UploadBlobBuilder::new(self.clone())
+    }
+
     pub fn append_block(&self, body: impl Into<Bytes>) -> AppendBlockBuilder {
         AppendBlockBuilder::new(self.clone(), body.into())
     }
 
+    pub fn append_block_from_url(&self, copy_source: Url) -> AppendBlockFromUrlBuilder {
+        AppendBlockFromUrlBuilder::new(self.clone(), copy_source)
+    }
+
+    /// Seals an append blob, after which no block can be appended.
+    pub fn seal(&self) -> SealAppendBlobBuilder {
+        SealAppendBlobBuilder::new(self.clone())
+    }
+
     pub fn put_block(
         &self,
         block_id: impl Into<BlockId>,
@@ -213,6 +274,25 @@ impl BlobClient {
         }
     }
 
+    /// Creates a builder for a user delegation SAS, signed with a key from
+    /// `BlobServiceClient::get_user_delegation_key` instead of the account key.
+    pub fn user_delegation_shared_access_signature(
+        &self,
+        user_delegation_key: UserDelegationKey,
+    ) -> BlobSharedAccessSignatureBuilder<(), SetResources, ()> {
+        let canonicalized_resource = format!(
+            "/blob/{}/{}/{}",
+            self.container_client.storage_account_client().account(),
+            self.container_client.container_name(),
+            self.blob_name()
+        );
+        BlobSharedAccessSignatureBuilder::new_user_delegation(
+            user_delegation_key,
+            canonicalized_resource,
+        )
+        .with_resources(BlobSignedResource::Blob)
+    }
+
     pub fn generate_signed_blob_url<T>(&self, signature: &T) -> azure_core::Result<url::Url>
     where
         T: SasToken,
@@ -1,8 +1,10 @@
 use crate::container::operations::ListContainersBuilder;
+use crate::service::{operations::*, BlobBatch, BlobServiceProperties};
 use azure_core::{Context, Request, Response};
 use azure_storage::core::clients::{
     AsStorageClient, ServiceType, StorageAccountClient, StorageClient,
 };
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
 
 pub trait AsBlobServiceClient {
@@ -35,6 +37,39 @@ impl BlobServiceClient {
         ListContainersBuilder::new(self.clone())
     }
 
+    pub fn get_blob_service_properties(&self) -> GetBlobServicePropertiesBuilder {
+        GetBlobServicePropertiesBuilder::new(self.clone())
+    }
+
+    /// Sets the properties of the Blob service. The settings left to `None` are not changed.
+    pub fn set_blob_service_properties(
+        &self,
+        properties: BlobServiceProperties,
+    ) -> SetBlobServicePropertiesBuilder {
+        SetBlobServicePropertiesBuilder::new(self.clone(), properties)
+    }
+
+    pub fn get_blob_service_stats(&self) -> GetBlobServiceStatsBuilder {
+        GetBlobServiceStatsBuilder::new(self.clone())
+    }
+
+    /// Reads the events of the change feed of the account, which must have it enabled.
+    pub fn get_change_feed(&self) -> GetChangeFeedBuilder {
+        GetChangeFeedBuilder::new(self.clone())
+    }
+
+    /// Submits a batch of operations on blobs of any container of the account.
+    pub fn submit_batch(&self, batch: BlobBatch) -> SubmitBatchBuilder {
+        SubmitBatchBuilder::new(self.clone(), None, batch)
+    }
+
+    /// Gets a key valid until `expiry` to sign user delegation SAS tokens with.
+    ///
+    /// The client must use Azure AD credentials.
+    pub fn get_user_delegation_key(&self, expiry: DateTime<Utc>) -> GetUserDelegationKeyBuilder {
+        GetUserDelegationKeyBuilder::new(self.clone(), expiry)
+    }
+
     pub(crate) async fn send(
         &self,
         context: &mut Context,
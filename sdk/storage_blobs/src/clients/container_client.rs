@@ -1,4 +1,9 @@
-use crate::{container::operations::*, prelude::PublicAccess};
+use crate::{
+    clients::BlobServiceClient,
+    container::operations::*,
+    prelude::PublicAccess,
+    service::{operations::SubmitBatchBuilder, BlobBatch},
+};
 use azure_core::{
     error::{Error, ErrorKind},
     prelude::*,
@@ -10,7 +15,9 @@ use azure_storage::{
         AsStorageClient, ServiceType, StorageAccountClient, StorageClient, StorageCredentials,
     },
     shared_access_signature::{
-        service_sas::{BlobSharedAccessSignatureBuilder, BlobSignedResource, SetResources},
+        service_sas::{
+            BlobSharedAccessSignatureBuilder, BlobSignedResource, SetResources, UserDelegationKey,
+        },
         SasToken,
     },
 };
@@ -86,6 +93,11 @@ impl ContainerClient {
         SetACLBuilder::new(self.clone(), public_access)
     }
 
+    /// Restores the soft deleted container with the given version as this container.
+    pub fn restore(&self, deleted_version: impl Into<String>) -> RestoreBuilder {
+        RestoreBuilder::new(self.clone(), deleted_version.into())
+    }
+
     pub fn get_properties(&self) -> GetPropertiesBuilder {
         GetPropertiesBuilder::new(self.clone())
     }
@@ -105,6 +117,14 @@ impl ContainerClient {
         BreakLeaseBuilder::new(self.clone())
     }
 
+    /// Submits a batch of operations on blobs of this container.
+    pub fn submit_batch(&self, batch: BlobBatch) -> SubmitBatchBuilder {
+        let service_client = BlobServiceClient {
+            storage_client: self.storage_client.clone(),
+        };
+        SubmitBatchBuilder::new(service_client, Some(self.container_name.clone()), batch)
+    }
+
     pub(crate) async fn send(
         &self,
         context: &mut Context,
@@ -145,6 +165,24 @@ impl ContainerClient {
         }
     }
 
+    /// Creates a builder for a user delegation SAS, signed with a key from
+    /// `BlobServiceClient::get_user_delegation_key` instead of the account key.
+    pub fn user_delegation_shared_access_signature(
+        &self,
+        user_delegation_key: UserDelegationKey,
+    ) -> BlobSharedAccessSignatureBuilder<(), SetResources, ()> {
+        let canonicalized_resource = format!(
+            "/blob/{}/{}",
+            self.storage_account_client().account(),
+            self.container_name(),
+        );
+        BlobSharedAccessSignatureBuilder::new_user_delegation(
+            user_delegation_key,
+            canonicalized_resource,
+        )
+        .with_resources(BlobSignedResource::Container)
+    }
+
     pub fn generate_signed_container_url<T>(&self, signature: &T) -> azure_core::Result<url::Url>
     where
         T: SasToken,
@@ -54,6 +54,12 @@ pub struct Container {
     pub has_immutability_policy: bool,
     pub has_legal_hold: bool,
     pub metadata: HashMap<String, String>,
+    /// Whether the container is soft deleted. Only listed with `include_deleted`.
+    pub deleted: Option<bool>,
+    /// The version of a deleted container, used to restore it.
+    pub version: Option<String>,
+    pub deleted_time: Option<DateTime<Utc>>,
+    pub remaining_retention_days: Option<u32>,
 }
 
 impl AsRef<str> for Container {
@@ -75,6 +81,10 @@ impl Container {
             has_immutability_policy: false,
             has_legal_hold: false,
             metadata: HashMap::new(),
+            deleted: None,
+            version: None,
+            deleted_time: None,
+            remaining_retention_days: None,
         }
     }
 
@@ -120,6 +130,10 @@ impl Container {
             has_immutability_policy,
             has_legal_hold,
             metadata,
+            deleted: None,
+            version: None,
+            deleted_time: None,
+            remaining_retention_days: None,
         })
     }
 
@@ -134,6 +148,11 @@ impl Container {
             cast_optional(elem, &["Properties", "PublicAccess"])?.unwrap_or(PublicAccess::None);
         let has_immutability_policy = cast_must(elem, &["Properties", "HasImmutabilityPolicy"])?;
         let has_legal_hold = cast_must(elem, &["Properties", "HasLegalHold"])?;
+        let deleted = cast_optional(elem, &["Deleted"])?;
+        let version = cast_optional(elem, &["Version"])?;
+        let deleted_time = cast_optional(elem, &["Properties", "DeletedTime"])?;
+        let remaining_retention_days =
+            cast_optional(elem, &["Properties", "RemainingRetentionDays"])?;
         let metadata = {
             let mut hm = HashMap::new();
             let metadata = traverse(elem, &["Metadata"], true)?;
@@ -188,6 +207,47 @@ impl Container {
             has_immutability_policy,
             has_legal_hold,
             metadata,
+            deleted,
+            version,
+            deleted_time,
+            remaining_retention_days,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::parsing::from_azure_time;
+
+    #[test]
+    fn parse_deleted_container() {
+        let elem: Element = "<Container>
+            <Name>mycontainer</Name>
+            <Deleted>true</Deleted>
+            <Version>01D60F8BB59A4652</Version>
+            <Properties>
+                <Last-Modified>Thu, 16 Apr 2020 08:23:08 GMT</Last-Modified>
+                <Etag>\"0x8D7E1E2EA07EC21\"</Etag>
+                <LeaseStatus>unlocked</LeaseStatus>
+                <LeaseState>expired</LeaseState>
+                <HasImmutabilityPolicy>false</HasImmutabilityPolicy>
+                <HasLegalHold>false</HasLegalHold>
+                <DeletedTime>Thu, 16 Apr 2020 08:25:01 GMT</DeletedTime>
+                <RemainingRetentionDays>6</RemainingRetentionDays>
+            </Properties>
+        </Container>"
+            .parse()
+            .unwrap();
+
+        let container = Container::parse(&elem).unwrap();
+        assert_eq!(container.name, "mycontainer");
+        assert_eq!(container.deleted, Some(true));
+        assert_eq!(container.version.as_deref(), Some("01D60F8BB59A4652"));
+        assert_eq!(
+            container.deleted_time,
+            Some(from_azure_time("Thu, 16 Apr 2020 08:25:01 GMT").unwrap())
+        );
+        assert_eq!(container.remaining_retention_days, Some(6));
+    }
+}
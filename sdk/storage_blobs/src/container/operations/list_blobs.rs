@@ -351,4 +351,124 @@ mod tests {
         let bytes = Bytes::from(S);
         let _list_blobs_response_internal: ListBlobsResponseInternal = read_xml(&bytes).unwrap();
     }
+
+    #[test]
+    fn deserde_tags() {
+        const S: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>
+<EnumerationResults ServiceEndpoint=\"https://azureskdforrust.blob.core.windows.net/\" ContainerName=\"osa2\">
+    <Blobs>
+        <Blob>
+            <Name>blob0.txt</Name>
+            <Properties>
+                <Creation-Time>Thu, 01 Jul 2021 10:44:59 GMT</Creation-Time>
+                <Last-Modified>Thu, 01 Jul 2021 10:44:59 GMT</Last-Modified>
+                <Etag>0x8D93C7D4629C227</Etag>
+                <Content-Length>8</Content-Length>
+                <Content-Type>text/plain</Content-Type>
+                <BlobType>BlockBlob</BlobType>
+                <LeaseStatus>unlocked</LeaseStatus>
+                <LeaseState>available</LeaseState>
+                <ServerEncrypted>true</ServerEncrypted>
+                <TagCount>2</TagCount>
+            </Properties>
+            <Tags>
+                <TagSet>
+                    <Tag><Key>project</Key><Value>contoso</Value></Tag>
+                    <Tag><Key>status</Key><Value>done</Value></Tag>
+                </TagSet>
+            </Tags>
+        </Blob>
+    </Blobs>
+    <NextMarker />
+</EnumerationResults>";
+
+        let list_blobs_response_internal: ListBlobsResponseInternal =
+            read_xml(S.as_bytes()).unwrap();
+        let blob = &list_blobs_response_internal.blobs.blobs[0];
+        assert_eq!(blob.properties.tag_count, Some(2));
+        let tags = blob
+            .tags
+            .iter()
+            .flat_map(|tags| tags.iter())
+            .map(|tag| (tag.key.as_str(), tag.value.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(tags, vec![("project", "contoso"), ("status", "done")]);
+    }
+
+    #[test]
+    fn deserde_deleted_versions_and_snapshots() {
+        const S: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>
+<EnumerationResults ServiceEndpoint=\"https://azureskdforrust.blob.core.windows.net/\" ContainerName=\"osa2\">
+    <Blobs>
+        <Blob>
+            <Name>blob0.txt</Name>
+            <Snapshot>2021-07-01T10:45:03.1234567Z</Snapshot>
+            <Properties>
+                <Creation-Time>Thu, 01 Jul 2021 10:44:59 GMT</Creation-Time>
+                <Last-Modified>Thu, 01 Jul 2021 10:44:59 GMT</Last-Modified>
+                <Etag>0x8D93C7D4629C227</Etag>
+                <Content-Length>8</Content-Length>
+                <Content-Type>text/plain</Content-Type>
+                <BlobType>BlockBlob</BlobType>
+                <LeaseStatus>unlocked</LeaseStatus>
+                <LeaseState>available</LeaseState>
+                <ServerEncrypted>true</ServerEncrypted>
+            </Properties>
+        </Blob>
+        <Blob>
+            <Name>blob0.txt</Name>
+            <VersionId>2021-07-01T10:44:59.8751234Z</VersionId>
+            <IsCurrentVersion>true</IsCurrentVersion>
+            <Properties>
+                <Creation-Time>Thu, 01 Jul 2021 10:44:59 GMT</Creation-Time>
+                <Last-Modified>Thu, 01 Jul 2021 10:44:59 GMT</Last-Modified>
+                <Etag>0x8D93C7D4629C227</Etag>
+                <Content-Length>8</Content-Length>
+                <Content-Type>text/plain</Content-Type>
+                <BlobType>BlockBlob</BlobType>
+                <LeaseStatus>unlocked</LeaseStatus>
+                <LeaseState>available</LeaseState>
+                <ServerEncrypted>true</ServerEncrypted>
+            </Properties>
+        </Blob>
+        <Blob>
+            <Name>blob1.txt</Name>
+            <Deleted>true</Deleted>
+            <Properties>
+                <Creation-Time>Thu, 01 Jul 2021 10:44:59 GMT</Creation-Time>
+                <Last-Modified>Thu, 01 Jul 2021 10:44:59 GMT</Last-Modified>
+                <Etag>0x8D93C7D4629C227</Etag>
+                <Content-Length>8</Content-Length>
+                <Content-Type>text/plain</Content-Type>
+                <BlobType>BlockBlob</BlobType>
+                <LeaseStatus>unlocked</LeaseStatus>
+                <LeaseState>available</LeaseState>
+                <ServerEncrypted>true</ServerEncrypted>
+                <DeletedTime>Fri, 02 Jul 2021 08:00:00 GMT</DeletedTime>
+                <RemainingRetentionDays>6</RemainingRetentionDays>
+            </Properties>
+        </Blob>
+    </Blobs>
+    <NextMarker />
+</EnumerationResults>";
+
+        let blobs = read_xml::<ListBlobsResponseInternal>(S.as_bytes())
+            .unwrap()
+            .blobs
+            .blobs;
+        assert_eq!(blobs.len(), 3);
+
+        assert!(blobs[0].snapshot.is_some());
+        assert_eq!(blobs[0].version_id, None);
+
+        assert_eq!(
+            blobs[1].version_id.as_deref(),
+            Some("2021-07-01T10:44:59.8751234Z")
+        );
+        assert_eq!(blobs[1].is_current_version, Some(true));
+
+        assert_eq!(blobs[2].deleted, Some(true));
+        assert!(blobs[2].properties.deleted_time.is_some());
+        assert_eq!(blobs[2].properties.remaining_retention_days, Some(6));
+    }
 }
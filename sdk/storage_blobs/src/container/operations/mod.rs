@@ -8,6 +8,7 @@ pub mod list_blobs;
 pub mod list_containers;
 pub mod release_lease;
 pub mod renew_lease;
+pub mod restore;
 pub mod set_acl;
 pub use self::acquire_lease::*;
 pub use self::break_lease::*;
@@ -19,4 +20,5 @@ pub use self::list_blobs::*;
 pub use self::list_containers::*;
 pub use self::release_lease::*;
 pub use self::renew_lease::*;
+pub use self::restore::*;
 pub use self::set_acl::*;
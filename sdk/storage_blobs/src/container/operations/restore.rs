@@ -0,0 +1,104 @@
+use crate::prelude::*;
+use azure_core::{headers::*, prelude::*, Method, RequestId};
+use chrono::{DateTime, Utc};
+use std::convert::{TryFrom, TryInto};
+
+const DELETED_CONTAINER_NAME: HeaderName = HeaderName::from_static("x-ms-deleted-container-name");
+const DELETED_CONTAINER_VERSION: HeaderName =
+    HeaderName::from_static("x-ms-deleted-container-version");
+
+/// Restores a soft deleted container, with its blobs and metadata, as the container of the
+/// client.
+///
+/// The deleted container has the name of the client unless `deleted_container_name` is set, which
+/// restores it under a new name. The version is the one returned when listing containers with
+/// `include_deleted`.
+#[derive(Debug, Clone)]
+pub struct RestoreBuilder {
+    container_client: ContainerClient,
+    deleted_container_name: Option<String>,
+    deleted_container_version: String,
+    timeout: Option<Timeout>,
+    context: Context,
+}
+
+impl RestoreBuilder {
+    pub(crate) fn new(
+        container_client: ContainerClient,
+        deleted_container_version: String,
+    ) -> Self {
+        Self {
+            container_client,
+            deleted_container_name: None,
+            deleted_container_version,
+            timeout: None,
+            context: Context::new(),
+        }
+    }
+
+    setters! {
+        deleted_container_name: String => Some(deleted_container_name),
+        timeout: Timeout => Some(timeout),
+        context: Context => context,
+    }
+
+    pub fn into_future(mut self) -> Response {
+        Box::pin(async move {
+            let mut url = self.container_client.url_with_segments(None)?;
+
+            url.query_pairs_mut()
+                .append_pair("restype", "container")
+                .append_pair("comp", "undelete");
+            self.timeout.append_to_url_query(&mut url);
+
+            let mut request = self
+                .container_client
+                .prepare_request(url, Method::Put, None)?;
+            request.insert_header(
+                DELETED_CONTAINER_NAME,
+                self.deleted_container_name
+                    .as_deref()
+                    .unwrap_or_else(|| self.container_client.container_name())
+                    .to_owned(),
+            );
+            request.insert_header(
+                DELETED_CONTAINER_VERSION,
+                self.deleted_container_version.clone(),
+            );
+
+            let response = self
+                .container_client
+                .send(&mut self.context, &mut request)
+                .await?;
+            response.headers().try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RestoreResponse {
+    pub request_id: RequestId,
+    pub date: DateTime<Utc>,
+}
+
+impl TryFrom<&Headers> for RestoreResponse {
+    type Error = crate::Error;
+
+    fn try_from(headers: &Headers) -> Result<Self, Self::Error> {
+        Ok(RestoreResponse {
+            request_id: request_id_from_headers(headers)?,
+            date: date_from_headers(headers)?,
+        })
+    }
+}
+
+pub type Response = futures::future::BoxFuture<'static, azure_core::Result<RestoreResponse>>;
+
+#[cfg(feature = "into_future")]
+impl std::future::IntoFuture for RestoreBuilder {
+    type IntoFuture = Response;
+    type Output = <Response as std::future::Future>::Output;
+    fn into_future(self) -> Self::IntoFuture {
+        Self::into_future(self)
+    }
+}
@@ -18,7 +18,8 @@ impl Header for Hash {
     fn value(&self) -> headers::HeaderValue {
         match self {
             Hash::MD5(md5) => base64::encode(md5),
-            Hash::CRC64(crc64) => format!("{}", crc64),
+            // the service expects the little-endian bytes of the CRC64
+            Hash::CRC64(crc64) => base64::encode(crc64.to_le_bytes()),
         }
         .into()
     }
@@ -29,3 +30,59 @@ impl From<md5::Digest> for Hash {
         Hash::MD5(md5.0)
     }
 }
+
+/// The reflected polynomial of the CRC64 computed by the storage service.
+const CRC64_POLYNOMIAL: u64 = 0x9A6C_9329_AC4B_C9B5;
+
+const CRC64_TABLE: [u64; 256] = {
+    let mut table = [0u64; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u64;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ CRC64_POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Computes the CRC64 of `data` the way the storage service does for `x-ms-content-crc64`.
+pub(crate) fn crc64(data: &[u8]) -> u64 {
+    !data.iter().fold(!0u64, |crc, byte| {
+        CRC64_TABLE[((crc ^ u64::from(*byte)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc64() {
+        assert_eq!(crc64(b""), 0);
+        assert_eq!(crc64(b"123456789"), 0xAE8B_1486_0A79_9888);
+    }
+
+    #[test]
+    fn test_hash_header() {
+        let hash = Hash::CRC64(crc64(b"123456789"));
+        assert_eq!(hash.name(), CONTENT_CRC64);
+        assert_eq!(hash.value().as_str(), "iJh5CoYUi64=");
+        assert_eq!(
+            base64::decode(hash.value().as_str()).unwrap(),
+            0xAE8B_1486_0A79_9888u64.to_le_bytes()
+        );
+
+        let hash = Hash::MD5(md5::compute(b"123456789").0);
+        assert_eq!(hash.name(), CONTENT_MD5);
+        assert_eq!(hash.value().as_str(), "JfnnlDI7RTiF9RgfG2JNCw==");
+    }
+}
@@ -1,3 +1,10 @@
 use azure_core::headers::HeaderName;
 
 pub const REHYDRATE_PRIORITY: HeaderName = HeaderName::from_static("x-ms-rehydrate-priority");
+pub const SOURCE_RANGE: HeaderName = HeaderName::from_static("x-ms-source-range");
+pub const BLOB_SEALED: HeaderName = HeaderName::from_static("x-ms-blob-sealed");
+pub const INCREMENTAL_COPY: HeaderName = HeaderName::from_static("x-ms-incremental-copy");
+pub const PREVIOUS_SNAPSHOT_URL: HeaderName = HeaderName::from_static("x-ms-previous-snapshot-url");
+pub const BLOB_APPEND_OFFSET: HeaderName = HeaderName::from_static("x-ms-blob-append-offset");
+pub const BLOB_COMMITTED_BLOCK_COUNT: HeaderName =
+    HeaderName::from_static("x-ms-blob-committed-block-count");
@@ -0,0 +1,39 @@
+use azure_core::headers::{self, Header};
+
+/// A SQL-like condition on the tags of the blob, such as `"Project" = 'Contoso' AND "Status" <> 'Done'`.
+///
+/// The operation fails with `412 Precondition Failed` when the blob tags do not match it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IfTags(String);
+
+impl IfTags {
+    pub fn new(condition: impl Into<String>) -> Self {
+        Self(condition.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for IfTags {
+    fn from(condition: String) -> Self {
+        Self(condition)
+    }
+}
+
+impl From<&str> for IfTags {
+    fn from(condition: &str) -> Self {
+        Self(condition.to_owned())
+    }
+}
+
+impl Header for IfTags {
+    fn name(&self) -> headers::HeaderName {
+        azure_storage::headers::IF_TAGS
+    }
+
+    fn value(&self) -> headers::HeaderValue {
+        self.0.clone().into()
+    }
+}
@@ -8,6 +8,7 @@ extern crate azure_core;
 pub use azure_core::error::{Error, ErrorKind, ResultExt};
 
 mod access_tier;
+mod avro;
 mod ba512_range;
 #[allow(clippy::module_inception)]
 pub mod blob;
@@ -25,7 +26,9 @@ pub mod container;
 mod delete_snapshot_method;
 mod hash;
 mod headers;
+mod if_tags;
 pub mod prelude;
+pub mod service;
 mod snapshot;
 mod version_id;
 
@@ -43,6 +46,7 @@ pub use condition_append_position::ConditionAppendPosition;
 pub use condition_max_size::ConditionMaxSize;
 pub use delete_snapshot_method::DeleteSnapshotsMethod;
 pub use hash::Hash;
+pub use if_tags::IfTags;
 pub use snapshot::Snapshot;
 pub use version_id::VersionId;
 
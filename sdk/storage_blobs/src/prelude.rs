@@ -1,6 +1,6 @@
 pub use super::container::PublicAccess;
 pub use crate::{
-    blob::{Blob, BlobBlockType, BlockList, BlockListType},
+    blob::{operations::RangeValidation, Blob, BlobBlockType, BlockList, BlockListType},
     clients::{
         AsBlobClient, AsBlobLeaseClient, AsBlobServiceClient, AsContainerClient,
         AsContainerLeaseClient, BlobClient, BlobLeaseClient, BlobServiceClient, ContainerClient,
@@ -8,6 +8,6 @@ pub use crate::{
     },
     AccessTier, BlobCacheControl, BlobContentDisposition, BlobContentEncoding, BlobContentLanguage,
     BlobContentMD5, BlobContentType, BlobVersioning, BlockId, ConditionAppendPosition,
-    ConditionMaxSize, DeleteSnapshotsMethod, Hash, RehydratePriority, Snapshot, VersionId,
+    ConditionMaxSize, DeleteSnapshotsMethod, Hash, IfTags, RehydratePriority, Snapshot, VersionId,
 };
 pub use azure_storage::core::{StoredAccessPolicy, StoredAccessPolicyList};
@@ -0,0 +1,317 @@
+use azure_core::{
+    error::{Error, ErrorKind},
+    headers::{HeaderName, HeaderValue, Headers},
+    Request, StatusCode,
+};
+use azure_storage::core::clients::{ServiceType, StorageAccountClient};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use uuid::Uuid;
+
+/// The largest number of operations a batch can hold.
+pub const MAX_BATCH_OPERATIONS: usize = 256;
+
+/// An operation of a [`BlobBatch`].
+///
+/// It is created with `to_batch_operation` on the builders of the operations that can be batched:
+/// [`DeleteBlobBuilder`](crate::blob::operations::DeleteBlobBuilder) and
+/// [`SetBlobTierBuilder`](crate::blob::operations::SetBlobTierBuilder).
+#[derive(Debug, Clone)]
+pub struct BatchOperation {
+    request: Request,
+}
+
+impl BatchOperation {
+    pub(crate) fn new(request: Request) -> Self {
+        Self { request }
+    }
+}
+
+/// Up to [`MAX_BATCH_OPERATIONS`] operations sent as a single `multipart/mixed` request.
+///
+/// The sub-requests are signed when the batch is submitted, with the credentials of the client
+/// submitting it.
+#[derive(Debug, Clone)]
+pub struct BlobBatch {
+    batch_uuid: Uuid,
+    operations: Vec<BatchOperation>,
+}
+
+impl Default for BlobBatch {
+    fn default() -> Self {
+        Self {
+            batch_uuid: Uuid::new_v4(),
+            operations: Vec::new(),
+        }
+    }
+}
+
+impl BlobBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, operation: BatchOperation) -> &mut Self {
+        self.operations.push(operation);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    pub(crate) fn content_type(&self) -> String {
+        format!(
+            "multipart/mixed; boundary=batch_{}",
+            self.batch_uuid.hyphenated()
+        )
+    }
+
+    /// Signs the sub-requests and writes them as the body of the batch request.
+    pub(crate) fn to_body(
+        &self,
+        storage_account_client: &StorageAccountClient,
+    ) -> azure_core::Result<String> {
+        if self.operations.is_empty() || self.operations.len() > MAX_BATCH_OPERATIONS {
+            return Err(Error::with_message(ErrorKind::Other, || {
+                format!(
+                    "a batch must have between 1 and {} operations, but it has {}",
+                    MAX_BATCH_OPERATIONS,
+                    self.operations.len()
+                )
+            }));
+        }
+
+        let mut s = String::new();
+        for (content_id, operation) in self.operations.iter().enumerate() {
+            let mut request = operation.request.clone();
+            storage_account_client.authorize_batch_subrequest(&mut request, ServiceType::Blob)?;
+
+            s.push_str(&format!("--batch_{}\r\n", self.batch_uuid.hyphenated()));
+            s.push_str("Content-Type: application/http\r\n");
+            s.push_str("Content-Transfer-Encoding: binary\r\n");
+            s.push_str(&format!("Content-ID: {}\r\n\r\n", content_id));
+            s.push_str(&format!(
+                "{} {} HTTP/1.1\r\n",
+                request.method(),
+                request.path_and_query()
+            ));
+            for (header_name, header_value) in request.headers().iter() {
+                s.push_str(&format!(
+                    "{}: {}\r\n",
+                    header_name.as_str(),
+                    header_value.as_str()
+                ));
+            }
+            s.push_str("\r\n");
+        }
+        s.push_str(&format!("--batch_{}--\r\n", self.batch_uuid.hyphenated()));
+
+        Ok(s)
+    }
+}
+
+/// The response to an operation of a batch.
+#[derive(Debug, Clone)]
+pub struct BatchItemResponse {
+    pub status: StatusCode,
+    pub error_code: Option<String>,
+    pub headers: Headers,
+}
+
+impl BatchItemResponse {
+    pub fn is_success(&self) -> bool {
+        self.status.is_success()
+    }
+
+    /// Turns a failed operation into an [`ErrorKind::HttpResponse`] error.
+    pub fn into_result(self) -> azure_core::Result<Self> {
+        if self.is_success() {
+            Ok(self)
+        } else {
+            Err(Error::with_message(
+                ErrorKind::http_response(self.status as u16, self.error_code.clone()),
+                || format!("batch operation failed with status {}", self.status),
+            ))
+        }
+    }
+}
+
+/// Parses the `multipart/mixed` body of a batch response, returning the responses in the order of
+/// the operations.
+pub(crate) fn parse_batch_response(
+    content_type: &str,
+    body: &[u8],
+) -> azure_core::Result<Vec<BatchItemResponse>> {
+    let boundary = content_type
+        .split(';')
+        .filter_map(|param| param.trim().strip_prefix("boundary="))
+        .next()
+        .map(|boundary| boundary.trim_matches('"'))
+        .ok_or_else(|| {
+            Error::with_message(ErrorKind::DataConversion, || {
+                format!("no boundary in the batch response content type {content_type}")
+            })
+        })?;
+    let body = std::str::from_utf8(body)?;
+
+    let mut items = Vec::new();
+    for part in body.split(&format!("--{}", boundary)).skip(1) {
+        if part.starts_with("--") {
+            break;
+        }
+        items.push(parse_part(part)?);
+    }
+    items.sort_by_key(|(content_id, _)| *content_id);
+
+    Ok(items.into_iter().map(|(_, item)| item).collect())
+}
+
+/// Parses a part made of MIME headers, with the `Content-ID`, and of the HTTP response.
+fn parse_part(part: &str) -> azure_core::Result<(usize, BatchItemResponse)> {
+    let mut lines = part.lines().map(|line| line.trim_end_matches('\r'));
+
+    let mut content_id = None;
+    for line in lines.by_ref().skip_while(|line| line.is_empty()) {
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-id") {
+                content_id = Some(value.trim().parse::<usize>().map_err(|_| {
+                    Error::with_message(ErrorKind::DataConversion, || {
+                        format!("invalid Content-ID `{}`", value.trim())
+                    })
+                })?);
+            }
+        }
+    }
+    let content_id = content_id.ok_or_else(|| {
+        Error::message(ErrorKind::DataConversion, "batch part without Content-ID")
+    })?;
+
+    let status_line = lines
+        .next()
+        .ok_or_else(|| Error::message(ErrorKind::DataConversion, "missing HTTP status line"))?;
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse::<u16>().ok())
+        .and_then(|status| StatusCode::try_from(status).ok())
+        .ok_or_else(|| {
+            Error::with_message(ErrorKind::DataConversion, || {
+                format!("invalid HTTP status line `{status_line}`")
+            })
+        })?;
+
+    let mut headers = HashMap::new();
+    for line in lines.take_while(|line| !line.is_empty()) {
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(
+                HeaderName::from(name.trim().to_lowercase()),
+                HeaderValue::from(value.trim().to_owned()),
+            );
+        }
+    }
+    let headers = Headers::from(headers);
+    let error_code = headers.get_optional_string(&azure_core::headers::ERROR_CODE);
+
+    Ok((
+        content_id,
+        BatchItemResponse {
+            status,
+            error_code,
+            headers,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_storage::core::clients::AsStorageClient;
+
+    #[test]
+    fn test_parse_batch_response() {
+        let body = "--batchresponse_66925647-d0cb-4109-b6d3-28efe3e1e5ed\r\n\
+Content-Type: application/http\r\n\
+Content-ID: 1\r\n\
+\r\n\
+HTTP/1.1 404 The specified blob does not exist.\r\n\
+x-ms-error-code: BlobNotFound\r\n\
+x-ms-request-id: 778fdc83-801e-0000-62ff-0334671e2852\r\n\
+x-ms-version: 2019-12-12\r\n\
+Content-Length: 216\r\n\
+Content-Type: application/xml\r\n\
+\r\n\
+<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+<Error><Code>BlobNotFound</Code><Message>The specified blob does not exist.</Message></Error>\r\n\
+--batchresponse_66925647-d0cb-4109-b6d3-28efe3e1e5ed\r\n\
+Content-Type: application/http\r\n\
+Content-ID: 0\r\n\
+\r\n\
+HTTP/1.1 202 Accepted\r\n\
+x-ms-delete-type-permanent: true\r\n\
+x-ms-request-id: 778fdc83-801e-0000-62ff-0334671e284f\r\n\
+x-ms-version: 2019-12-12\r\n\
+\r\n\
+--batchresponse_66925647-d0cb-4109-b6d3-28efe3e1e5ed--\r\n";
+
+        let items = parse_batch_response(
+            "multipart/mixed; boundary=batchresponse_66925647-d0cb-4109-b6d3-28efe3e1e5ed",
+            body.as_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].status, StatusCode::Accepted);
+        assert!(items[0].is_success());
+        assert_eq!(
+            items[0]
+                .headers
+                .get_optional_str(&HeaderName::from_static("x-ms-delete-type-permanent")),
+            Some("true")
+        );
+        assert_eq!(items[1].status, StatusCode::NotFound);
+        assert_eq!(items[1].error_code.as_deref(), Some("BlobNotFound"));
+        assert!(matches!(
+            items[1].clone().into_result().unwrap_err().kind(),
+            ErrorKind::HttpResponse { status: 404, error_code: Some(code) } if code == "BlobNotFound"
+        ));
+    }
+
+    #[test]
+    fn test_batch_body() {
+        let storage_account_client = StorageAccountClient::new_emulator_default();
+        let url = storage_account_client
+            .storage_client()
+            .blob_url_with_segments(["container", "blob"])
+            .unwrap();
+
+        let mut batch = BlobBatch::new();
+        assert!(batch.to_body(&storage_account_client).is_err());
+
+        batch.add(BatchOperation::new(Request::new(
+            url,
+            azure_core::Method::Delete,
+        )));
+        let body = batch.to_body(&storage_account_client).unwrap();
+        let boundary = format!("batch_{}", batch.batch_uuid.hyphenated());
+
+        assert!(body.starts_with(&format!(
+            "--{}\r\nContent-Type: application/http\r\nContent-Transfer-Encoding: binary\r\nContent-ID: 0\r\n\r\nDELETE /devstoreaccount1/container/blob HTTP/1.1\r\n",
+            boundary
+        )));
+        assert!(body.contains("\r\nauthorization: SharedKey devstoreaccount1:"));
+        assert!(!body.contains("x-ms-version"));
+        assert!(body.ends_with(&format!("\r\n\r\n--{}--\r\n", boundary)));
+        assert_eq!(
+            batch.content_type(),
+            format!("multipart/mixed; boundary={}", boundary)
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use azure_storage::xml::escape_xml;
+use std::fmt::Display;
+
+/// The properties of the Blob service of a storage account.
+///
+/// The settings left to `None` are not changed by
+/// [`set_blob_service_properties`](crate::clients::BlobServiceClient::set_blob_service_properties).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct BlobServiceProperties {
+    pub logging: Option<Logging>,
+    pub hour_metrics: Option<Metrics>,
+    pub minute_metrics: Option<Metrics>,
+    pub cors: Option<Cors>,
+    pub default_service_version: Option<String>,
+    pub delete_retention_policy: Option<DeleteRetentionPolicy>,
+    pub static_website: Option<StaticWebsite>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Logging {
+    pub version: String,
+    pub delete: bool,
+    pub read: bool,
+    pub write: bool,
+    pub retention_policy: RetentionPolicy,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Metrics {
+    pub version: String,
+    pub enabled: bool,
+    /// Whether the metrics include a summary per API. Only sent when the metrics are enabled.
+    #[serde(rename = "IncludeAPIs")]
+    pub include_apis: Option<bool>,
+    pub retention_policy: RetentionPolicy,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct RetentionPolicy {
+    pub enabled: bool,
+    pub days: Option<u32>,
+}
+
+/// The CORS rules. An empty list removes all the rules of the account.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Cors {
+    #[serde(default)]
+    pub cors_rule: Vec<CorsRule>,
+}
+
+/// A CORS rule. The lists are comma separated.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CorsRule {
+    pub allowed_origins: String,
+    pub allowed_methods: String,
+    pub max_age_in_seconds: u64,
+    #[serde(default)]
+    pub exposed_headers: String,
+    #[serde(default)]
+    pub allowed_headers: String,
+}
+
+/// How long deleted blobs are kept before being removed permanently.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DeleteRetentionPolicy {
+    pub enabled: bool,
+    pub days: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct StaticWebsite {
+    pub enabled: bool,
+    pub index_document: Option<String>,
+    pub error_document404_path: Option<String>,
+    pub default_index_document_path: Option<String>,
+}
+
+impl BlobServiceProperties {
+    pub fn to_xml(&self) -> String {
+        let mut s = String::new();
+        s.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<StorageServiceProperties>");
+        if let Some(logging) = &self.logging {
+            s.push_str("<Logging>");
+            push_element(&mut s, "Version", escape_xml(&logging.version));
+            push_element(&mut s, "Delete", logging.delete);
+            push_element(&mut s, "Read", logging.read);
+            push_element(&mut s, "Write", logging.write);
+            logging.retention_policy.push_xml(&mut s);
+            s.push_str("</Logging>");
+        }
+        if let Some(hour_metrics) = &self.hour_metrics {
+            hour_metrics.push_xml(&mut s, "HourMetrics");
+        }
+        if let Some(minute_metrics) = &self.minute_metrics {
+            minute_metrics.push_xml(&mut s, "MinuteMetrics");
+        }
+        if let Some(cors) = &self.cors {
+            s.push_str("<Cors>");
+            for rule in &cors.cors_rule {
+                s.push_str("<CorsRule>");
+                push_element(&mut s, "AllowedOrigins", escape_xml(&rule.allowed_origins));
+                push_element(&mut s, "AllowedMethods", escape_xml(&rule.allowed_methods));
+                push_element(&mut s, "MaxAgeInSeconds", rule.max_age_in_seconds);
+                push_element(&mut s, "ExposedHeaders", escape_xml(&rule.exposed_headers));
+                push_element(&mut s, "AllowedHeaders", escape_xml(&rule.allowed_headers));
+                s.push_str("</CorsRule>");
+            }
+            s.push_str("</Cors>");
+        }
+        if let Some(default_service_version) = &self.default_service_version {
+            push_element(
+                &mut s,
+                "DefaultServiceVersion",
+                escape_xml(default_service_version),
+            );
+        }
+        if let Some(delete_retention_policy) = &self.delete_retention_policy {
+            s.push_str("<DeleteRetentionPolicy>");
+            push_element(&mut s, "Enabled", delete_retention_policy.enabled);
+            if let Some(days) = delete_retention_policy.days {
+                push_element(&mut s, "Days", days);
+            }
+            s.push_str("</DeleteRetentionPolicy>");
+        }
+        if let Some(static_website) = &self.static_website {
+            s.push_str("<StaticWebsite>");
+            push_element(&mut s, "Enabled", static_website.enabled);
+            if let Some(index_document) = &static_website.index_document {
+                push_element(&mut s, "IndexDocument", escape_xml(index_document));
+            }
+            if let Some(error_document404_path) = &static_website.error_document404_path {
+                push_element(
+                    &mut s,
+                    "ErrorDocument404Path",
+                    escape_xml(error_document404_path),
+                );
+            }
+            if let Some(default_index_document_path) = &static_website.default_index_document_path {
+                push_element(
+                    &mut s,
+                    "DefaultIndexDocumentPath",
+                    escape_xml(default_index_document_path),
+                );
+            }
+            s.push_str("</StaticWebsite>");
+        }
+        s.push_str("</StorageServiceProperties>");
+        s
+    }
+}
+
+impl Metrics {
+    fn push_xml(&self, s: &mut String, name: &str) {
+        s.push_str(&format!("<{}>", name));
+        push_element(s, "Version", escape_xml(&self.version));
+        push_element(s, "Enabled", self.enabled);
+        // the service rejects IncludeAPIs when the metrics are disabled
+        if let (true, Some(include_apis)) = (self.enabled, self.include_apis) {
+            push_element(s, "IncludeAPIs", include_apis);
+        }
+        self.retention_policy.push_xml(s);
+        s.push_str(&format!("</{}>", name));
+    }
+}
+
+impl RetentionPolicy {
+    fn push_xml(&self, s: &mut String) {
+        s.push_str("<RetentionPolicy>");
+        push_element(s, "Enabled", self.enabled);
+        if let Some(days) = self.days {
+            push_element(s, "Days", days);
+        }
+        s.push_str("</RetentionPolicy>");
+    }
+}
+
+fn push_element(s: &mut String, name: &str, value: impl Display) {
+    s.push_str(&format!("<{}>{}</{}>", name, value, name));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_storage::xml::read_xml;
+
+    #[test]
+    fn parse_blob_service_properties() {
+        let body = br#"<?xml version="1.0" encoding="utf-8"?>
+<StorageServiceProperties>
+    <Logging>
+        <Version>1.0</Version>
+        <Read>true</Read>
+        <Write>true</Write>
+        <Delete>false</Delete>
+        <RetentionPolicy><Enabled>true</Enabled><Days>7</Days></RetentionPolicy>
+    </Logging>
+    <HourMetrics>
+        <Version>1.0</Version>
+        <Enabled>true</Enabled>
+        <IncludeAPIs>true</IncludeAPIs>
+        <RetentionPolicy><Enabled>true</Enabled><Days>7</Days></RetentionPolicy>
+    </HourMetrics>
+    <MinuteMetrics>
+        <Version>1.0</Version>
+        <Enabled>false</Enabled>
+        <RetentionPolicy><Enabled>false</Enabled></RetentionPolicy>
+    </MinuteMetrics>
+    <Cors>
+        <CorsRule>
+            <AllowedOrigins>http://contoso.com,http://fabrikam.com</AllowedOrigins>
+            <AllowedMethods>GET,PUT</AllowedMethods>
+            <MaxAgeInSeconds>500</MaxAgeInSeconds>
+            <ExposedHeaders>x-ms-meta-*</ExposedHeaders>
+            <AllowedHeaders>x-ms-meta-target*</AllowedHeaders>
+        </CorsRule>
+        <CorsRule>
+            <AllowedOrigins>*</AllowedOrigins>
+            <AllowedMethods>GET</AllowedMethods>
+            <MaxAgeInSeconds>60</MaxAgeInSeconds>
+            <ExposedHeaders />
+            <AllowedHeaders />
+        </CorsRule>
+    </Cors>
+    <DefaultServiceVersion>2019-12-12</DefaultServiceVersion>
+    <DeleteRetentionPolicy><Enabled>true</Enabled><Days>5</Days></DeleteRetentionPolicy>
+    <StaticWebsite>
+        <Enabled>true</Enabled>
+        <IndexDocument>index.html</IndexDocument>
+        <ErrorDocument404Path>errors/404.html</ErrorDocument404Path>
+    </StaticWebsite>
+</StorageServiceProperties>"#;
+
+        let properties: BlobServiceProperties = read_xml(body).unwrap();
+        let logging = properties.logging.as_ref().unwrap();
+        assert!(logging.read && logging.write && !logging.delete);
+        assert_eq!(logging.retention_policy.days, Some(7));
+        assert_eq!(
+            properties.hour_metrics.as_ref().unwrap().include_apis,
+            Some(true)
+        );
+        assert_eq!(
+            properties.minute_metrics.as_ref().unwrap().include_apis,
+            None
+        );
+
+        let cors = properties.cors.as_ref().unwrap();
+        assert_eq!(cors.cors_rule.len(), 2);
+        assert_eq!(cors.cors_rule[0].max_age_in_seconds, 500);
+        assert_eq!(cors.cors_rule[1].allowed_headers, "");
+
+        assert_eq!(
+            properties.default_service_version.as_deref(),
+            Some("2019-12-12")
+        );
+        assert_eq!(
+            properties.delete_retention_policy,
+            Some(DeleteRetentionPolicy {
+                enabled: true,
+                days: Some(5)
+            })
+        );
+        let static_website = properties.static_website.as_ref().unwrap();
+        assert_eq!(static_website.index_document.as_deref(), Some("index.html"));
+        assert_eq!(static_website.default_index_document_path, None);
+
+        let round_tripped: BlobServiceProperties =
+            read_xml(properties.to_xml().as_bytes()).unwrap();
+        assert_eq!(round_tripped, properties);
+    }
+
+    #[test]
+    fn partial_blob_service_properties_to_xml() {
+        let properties = BlobServiceProperties {
+            cors: Some(Cors::default()),
+            static_website: Some(StaticWebsite {
+                enabled: true,
+                index_document: Some("index.html".to_owned()),
+                error_document404_path: None,
+                default_index_document_path: None,
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            properties.to_xml(),
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<StorageServiceProperties>\
+            <Cors></Cors>\
+            <StaticWebsite><Enabled>true</Enabled><IndexDocument>index.html</IndexDocument></StaticWebsite>\
+            </StorageServiceProperties>"
+        );
+    }
+}
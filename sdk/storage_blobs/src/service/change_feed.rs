@@ -0,0 +1,310 @@
+use crate::avro::{AvroRecord, AvroValue};
+use azure_core::error::{Error, ErrorKind, ResultExt};
+use chrono::{DateTime, TimeZone, Timelike, Utc};
+
+/// The container the change feed is written to.
+pub(crate) const CHANGE_FEED_CONTAINER: &str = "$blobchangefeed";
+pub(crate) const SEGMENTS_MANIFEST_PATH: &str = "meta/segments.json";
+pub(crate) const SEGMENTS_PREFIX: &str = "idx/segments/";
+
+/// The position after the last event returned by a change feed reader.
+///
+/// Pass it to [`GetChangeFeedBuilder::cursor`](crate::service::operations::GetChangeFeedBuilder::cursor)
+/// to resume reading from there. It can be saved with serde, but it doesn't include the time
+/// window of the reader.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeFeedCursor {
+    pub(crate) segment_time: DateTime<Utc>,
+    pub(crate) shard_index: usize,
+    pub(crate) chunk_path: String,
+    pub(crate) event_index: usize,
+}
+
+impl ChangeFeedCursor {
+    /// The start of the hourly segment holding the last event.
+    pub fn segment_time(&self) -> DateTime<Utc> {
+        self.segment_time
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlobChangeFeedEventType {
+    BlobCreated,
+    BlobDeleted,
+    BlobPropertiesUpdated,
+    BlobSnapshotCreated,
+    BlobTierChanged,
+    BlobAsyncOperationInitiated,
+    RestorePointMarkerCreated,
+    Control,
+    Other(String),
+}
+
+impl From<&str> for BlobChangeFeedEventType {
+    fn from(s: &str) -> Self {
+        match s {
+            "BlobCreated" => Self::BlobCreated,
+            "BlobDeleted" => Self::BlobDeleted,
+            "BlobPropertiesUpdated" => Self::BlobPropertiesUpdated,
+            "BlobSnapshotCreated" => Self::BlobSnapshotCreated,
+            "BlobTierChanged" => Self::BlobTierChanged,
+            "BlobAsyncOperationInitiated" => Self::BlobAsyncOperationInitiated,
+            "RestorePointMarkerCreated" => Self::RestorePointMarkerCreated,
+            "Control" => Self::Control,
+            s => Self::Other(s.to_owned()),
+        }
+    }
+}
+
+/// A change to a blob of the account, recorded in the change feed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlobChangeFeedEvent {
+    pub id: String,
+    /// The path of the blob, as `/blobServices/default/containers/<container>/blobs/<blob>`.
+    pub subject: String,
+    pub topic: String,
+    pub event_type: BlobChangeFeedEventType,
+    pub event_time: DateTime<Utc>,
+    pub data: BlobChangeFeedEventData,
+    pub data_version: Option<String>,
+    pub metadata_version: Option<String>,
+    pub schema_version: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlobChangeFeedEventData {
+    /// The operation that triggered the event, such as `PutBlob` or `DeleteBlob`.
+    pub api: String,
+    pub client_request_id: Option<String>,
+    pub request_id: Option<String>,
+    pub etag: Option<String>,
+    pub content_type: Option<String>,
+    pub content_length: Option<i64>,
+    pub content_offset: Option<i64>,
+    pub blob_type: Option<String>,
+    pub url: String,
+    pub destination_url: Option<String>,
+    pub source_url: Option<String>,
+    pub recursive: Option<bool>,
+    /// Orders the events of a blob.
+    pub sequencer: Option<String>,
+    pub snapshot: Option<String>,
+}
+
+impl TryFrom<AvroValue> for BlobChangeFeedEvent {
+    type Error = Error;
+
+    fn try_from(value: AvroValue) -> azure_core::Result<Self> {
+        let record = match value {
+            AvroValue::Record(record) => record,
+            value => {
+                return Err(Error::with_message(ErrorKind::DataConversion, || {
+                    format!("unexpected change feed event {:?}", value)
+                }))
+            }
+        };
+
+        let event_time = record.get_str("eventTime")?;
+        let event_time = DateTime::parse_from_rfc3339(event_time)
+            .with_context(ErrorKind::DataConversion, || {
+                format!("invalid change feed event time {}", event_time)
+            })?
+            .with_timezone(&Utc);
+
+        Ok(Self {
+            id: record.get_str("id")?.to_owned(),
+            subject: record.get_str("subject")?.to_owned(),
+            topic: record.get_str("topic")?.to_owned(),
+            event_type: record.get_str("eventType")?.into(),
+            event_time,
+            data: BlobChangeFeedEventData::try_from(record.get_record("data")?)?,
+            data_version: record
+                .get_optional_str("dataVersion")?
+                .map(ToOwned::to_owned),
+            metadata_version: record
+                .get_optional_str("metadataVersion")?
+                .map(ToOwned::to_owned),
+            schema_version: record.get_optional_long("schemaVersion")?,
+        })
+    }
+}
+
+impl TryFrom<&AvroRecord> for BlobChangeFeedEventData {
+    type Error = Error;
+
+    fn try_from(record: &AvroRecord) -> azure_core::Result<Self> {
+        let optional_string = |field| -> azure_core::Result<Option<String>> {
+            Ok(record.get_optional_str(field)?.map(ToOwned::to_owned))
+        };
+
+        Ok(Self {
+            api: record.get_str("api")?.to_owned(),
+            client_request_id: optional_string("clientRequestId")?,
+            request_id: optional_string("requestId")?,
+            etag: optional_string("etag")?,
+            content_type: optional_string("contentType")?,
+            content_length: record.get_optional_long("contentLength")?,
+            content_offset: record.get_optional_long("contentOffset")?,
+            blob_type: optional_string("blobType")?,
+            url: record.get_str("url")?.to_owned(),
+            destination_url: optional_string("destinationUrl")?,
+            source_url: optional_string("sourceUrl")?,
+            recursive: record.get_optional_bool("recursive")?,
+            sequencer: optional_string("sequencer")?,
+            snapshot: optional_string("snapshot")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SegmentsManifest {
+    /// The start of the last segment that can be read.
+    pub last_consumable: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SegmentManifest {
+    /// The directories of the shards, prefixed with the container name.
+    pub chunk_file_paths: Vec<String>,
+}
+
+/// Parses the start time of a segment from the path of its manifest, such as
+/// `idx/segments/2022/06/21/1800/meta.json`.
+pub(crate) fn segment_time(path: &str) -> Option<DateTime<Utc>> {
+    let path = path
+        .strip_prefix(SEGMENTS_PREFIX)?
+        .strip_suffix("/meta.json")?;
+    let mut parts = path.split('/');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    let hhmm = parts.next()?;
+    // check the characters before slicing, as a multi-byte one would not split on a boundary
+    if parts.next().is_some() || hhmm.len() != 4 || !hhmm.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let hour = hhmm[..2].parse().ok()?;
+    let minute = hhmm[2..].parse().ok()?;
+    Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+        .single()
+}
+
+/// Rounds `time` down to the hour, the granularity of the segments.
+pub(crate) fn segment_floor(time: DateTime<Utc>) -> DateTime<Utc> {
+    time.with_minute(0)
+        .and_then(|time| time.with_second(0))
+        .and_then(|time| time.with_nanosecond(0))
+        .unwrap_or(time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn string(value: &str) -> AvroValue {
+        AvroValue::String(value.to_owned())
+    }
+
+    #[test]
+    fn parse_segment_time() {
+        assert_eq!(
+            segment_time("idx/segments/2022/06/21/1800/meta.json"),
+            Some(Utc.with_ymd_and_hms(2022, 6, 21, 18, 0, 0).unwrap())
+        );
+        assert_eq!(
+            segment_time("idx/segments/2022/06/21/1800/other.json"),
+            None
+        );
+        assert_eq!(segment_time("idx/segments/2022/06/1800/meta.json"), None);
+        assert_eq!(segment_time("idx/segments/2022/06/21/1é0/meta.json"), None);
+        assert_eq!(segment_time("idx/segments/2022/06/21/+800/meta.json"), None);
+        assert_eq!(
+            segment_floor(Utc.with_ymd_and_hms(2022, 6, 21, 18, 42, 7).unwrap()),
+            Utc.with_ymd_and_hms(2022, 6, 21, 18, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_change_feed_event() {
+        let data = AvroRecord {
+            name: "BlobChangeEventData".to_owned(),
+            fields: HashMap::from([
+                ("api".to_owned(), string("PutBlob")),
+                (
+                    "clientRequestId".to_owned(),
+                    string("00c0b6b7-8bff-4a5a-9a2d-0d3a7e4e5f22"),
+                ),
+                (
+                    "requestId".to_owned(),
+                    string("2f7c2a4c-601e-0005-4b1e-9a5f6b000000"),
+                ),
+                ("etag".to_owned(), string("0x8D9F2171BE32588")),
+                ("contentType".to_owned(), string("text/plain")),
+                ("contentLength".to_owned(), AvroValue::Long(12)),
+                (
+                    "blobType".to_owned(),
+                    AvroValue::Enum("BlockBlob".to_owned()),
+                ),
+                (
+                    "url".to_owned(),
+                    string("https://contoso.blob.core.windows.net/container/blob.txt"),
+                ),
+                (
+                    "sequencer".to_owned(),
+                    string("0000000000000001000000000000021e"),
+                ),
+                ("snapshot".to_owned(), AvroValue::Null),
+            ]),
+        };
+        let event = AvroValue::Record(AvroRecord {
+            name: "BlobChangeEvent".to_owned(),
+            fields: HashMap::from([
+                ("schemaVersion".to_owned(), AvroValue::Int(3)),
+                (
+                    "topic".to_owned(),
+                    string("/subscriptions/00000000-0000-0000-0000-000000000000/resourceGroups/group/providers/Microsoft.Storage/storageAccounts/contoso"),
+                ),
+                (
+                    "subject".to_owned(),
+                    string("/blobServices/default/containers/container/blobs/blob.txt"),
+                ),
+                ("eventType".to_owned(), AvroValue::Enum("BlobCreated".to_owned())),
+                ("eventTime".to_owned(), string("2022-06-21T18:12:01.1234567Z")),
+                ("id".to_owned(), string("62616073-8020-0000-00ff-233467060cc0")),
+                ("data".to_owned(), AvroValue::Record(data)),
+                ("dataVersion".to_owned(), string("")),
+                ("metadataVersion".to_owned(), string("1")),
+            ]),
+        });
+
+        let event = BlobChangeFeedEvent::try_from(event).unwrap();
+        assert_eq!(event.event_type, BlobChangeFeedEventType::BlobCreated);
+        assert_eq!(
+            event.event_time,
+            Utc.with_ymd_and_hms(2022, 6, 21, 18, 12, 1).unwrap()
+                + chrono::Duration::nanoseconds(123_456_700)
+        );
+        assert_eq!(event.schema_version, Some(3));
+        assert_eq!(event.data.api, "PutBlob");
+        assert_eq!(event.data.content_length, Some(12));
+        assert_eq!(event.data.blob_type.as_deref(), Some("BlockBlob"));
+        assert_eq!(event.data.snapshot, None);
+        assert_eq!(event.data.recursive, None);
+
+        let cursor = ChangeFeedCursor {
+            segment_time: Utc.with_ymd_and_hms(2022, 6, 21, 18, 0, 0).unwrap(),
+            shard_index: 1,
+            chunk_path: "log/01/2022/06/21/1800/00000.avro".to_owned(),
+            event_index: 5,
+        };
+        let serialized = serde_json::to_string(&cursor).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ChangeFeedCursor>(&serialized).unwrap(),
+            cursor
+        );
+    }
+}
@@ -0,0 +1,11 @@
+mod blob_batch;
+mod blob_service_properties;
+mod change_feed;
+pub mod operations;
+
+pub(crate) use blob_batch::parse_batch_response;
+pub use blob_batch::{BatchItemResponse, BatchOperation, BlobBatch, MAX_BATCH_OPERATIONS};
+pub use blob_service_properties::*;
+pub use change_feed::{
+    BlobChangeFeedEvent, BlobChangeFeedEventData, BlobChangeFeedEventType, ChangeFeedCursor,
+};
@@ -0,0 +1,79 @@
+use crate::{clients::BlobServiceClient, service::BlobServiceProperties};
+use azure_core::{collect_pinned_stream, prelude::*, Method, Response as AzureResponse};
+use azure_storage::core::{headers::CommonStorageResponseHeaders, xml::read_xml};
+use std::convert::TryInto;
+
+#[derive(Debug, Clone)]
+pub struct GetBlobServicePropertiesBuilder {
+    client: BlobServiceClient,
+    timeout: Option<Timeout>,
+    context: Context,
+}
+
+impl GetBlobServicePropertiesBuilder {
+    pub(crate) fn new(client: BlobServiceClient) -> Self {
+        Self {
+            client,
+            timeout: None,
+            context: Context::new(),
+        }
+    }
+
+    setters! {
+        timeout: Timeout => Some(timeout),
+        context: Context => context,
+    }
+
+    pub fn into_future(mut self) -> Response {
+        Box::pin(async move {
+            let mut url = self
+                .client
+                .storage_client
+                .storage_account_client()
+                .blob_storage_url()
+                .clone();
+            url.query_pairs_mut()
+                .append_pair("restype", "service")
+                .append_pair("comp", "properties");
+            self.timeout.append_to_url_query(&mut url);
+
+            let mut request = self
+                .client
+                .storage_client
+                .prepare_request(url, Method::Get, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            GetBlobServicePropertiesResponse::try_from(response).await
+        })
+    }
+}
+
+pub type Response =
+    futures::future::BoxFuture<'static, azure_core::Result<GetBlobServicePropertiesResponse>>;
+
+#[cfg(feature = "into_future")]
+impl std::future::IntoFuture for GetBlobServicePropertiesBuilder {
+    type IntoFuture = Response;
+    type Output = <Response as std::future::Future>::Output;
+    fn into_future(self) -> Self::IntoFuture {
+        Self::into_future(self)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetBlobServicePropertiesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub blob_service_properties: BlobServiceProperties,
+}
+
+impl GetBlobServicePropertiesResponse {
+    async fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let body = collect_pinned_stream(body).await?;
+
+        Ok(Self {
+            common_storage_response_headers: (&headers).try_into()?,
+            blob_service_properties: read_xml(&body)?,
+        })
+    }
+}
@@ -0,0 +1,156 @@
+use crate::clients::BlobServiceClient;
+use azure_core::{
+    collect_pinned_stream,
+    error::{ErrorKind, ResultExt},
+    prelude::*,
+    Method, Response as AzureResponse,
+};
+use azure_storage::core::{headers::CommonStorageResponseHeaders, xml::read_xml};
+use chrono::{DateTime, Utc};
+use std::convert::TryInto;
+
+/// Gets the geo-replication status of the account.
+///
+/// The request is sent to the secondary endpoint, so the account must have read-access
+/// geo-redundant replication enabled.
+#[derive(Debug, Clone)]
+pub struct GetBlobServiceStatsBuilder {
+    client: BlobServiceClient,
+    timeout: Option<Timeout>,
+    context: Context,
+}
+
+impl GetBlobServiceStatsBuilder {
+    pub(crate) fn new(client: BlobServiceClient) -> Self {
+        Self {
+            client,
+            timeout: None,
+            context: Context::new(),
+        }
+    }
+
+    setters! {
+        timeout: Timeout => Some(timeout),
+        context: Context => context,
+    }
+
+    pub fn into_future(mut self) -> Response {
+        Box::pin(async move {
+            let mut url = self
+                .client
+                .storage_client
+                .storage_account_client()
+                .blob_storage_secondary_url()
+                .clone();
+            url.query_pairs_mut()
+                .append_pair("restype", "service")
+                .append_pair("comp", "stats");
+            self.timeout.append_to_url_query(&mut url);
+
+            let mut request = self
+                .client
+                .storage_client
+                .prepare_request(url, Method::Get, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            GetBlobServiceStatsResponse::try_from(response).await
+        })
+    }
+}
+
+pub type Response =
+    futures::future::BoxFuture<'static, azure_core::Result<GetBlobServiceStatsResponse>>;
+
+#[cfg(feature = "into_future")]
+impl std::future::IntoFuture for GetBlobServiceStatsBuilder {
+    type IntoFuture = Response;
+    type Output = <Response as std::future::Future>::Output;
+    fn into_future(self) -> Self::IntoFuture {
+        Self::into_future(self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GeoReplicationStatus {
+    Live,
+    Bootstrap,
+    Unavailable,
+}
+
+#[derive(Debug, Clone)]
+pub struct GetBlobServiceStatsResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub status: GeoReplicationStatus,
+    /// The time before which all the primary writes are available on the secondary.
+    pub last_sync_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct StorageServiceStats {
+    geo_replication: GeoReplication,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GeoReplication {
+    status: GeoReplicationStatus,
+    last_sync_time: Option<String>,
+}
+
+impl GetBlobServiceStatsResponse {
+    async fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let body = collect_pinned_stream(body).await?;
+        let (status, last_sync_time) = parse_stats(&body)?;
+
+        Ok(Self {
+            common_storage_response_headers: (&headers).try_into()?,
+            status,
+            last_sync_time,
+        })
+    }
+}
+
+fn parse_stats(body: &[u8]) -> azure_core::Result<(GeoReplicationStatus, Option<DateTime<Utc>>)> {
+    let stats: StorageServiceStats = read_xml(body)?;
+    let last_sync_time = stats
+        .geo_replication
+        .last_sync_time
+        // the time is empty while the status is bootstrap
+        .filter(|time| !time.is_empty())
+        .map(|time| DateTime::parse_from_rfc2822(&time))
+        .transpose()
+        .context(ErrorKind::DataConversion, "failed to parse last sync time")?
+        .map(|time| time.with_timezone(&Utc));
+    Ok((stats.geo_replication.status, last_sync_time))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_stats() {
+        let body = br#"<?xml version="1.0" encoding="utf-8"?>
+<StorageServiceStats>
+    <GeoReplication>
+        <Status>live</Status>
+        <LastSyncTime>Wed, 01 Jun 2022 10:20:30 GMT</LastSyncTime>
+    </GeoReplication>
+</StorageServiceStats>"#;
+        let (status, last_sync_time) = parse_stats(body).unwrap();
+        assert_eq!(status, GeoReplicationStatus::Live);
+        assert_eq!(
+            last_sync_time,
+            Some(Utc.with_ymd_and_hms(2022, 6, 1, 10, 20, 30).unwrap())
+        );
+
+        let body = br#"<StorageServiceStats><GeoReplication><Status>bootstrap</Status><LastSyncTime /></GeoReplication></StorageServiceStats>"#;
+        let (status, last_sync_time) = parse_stats(body).unwrap();
+        assert_eq!(status, GeoReplicationStatus::Bootstrap);
+        assert_eq!(last_sync_time, None);
+    }
+}
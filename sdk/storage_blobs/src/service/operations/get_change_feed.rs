@@ -0,0 +1,306 @@
+use crate::{
+    avro::AvroReader,
+    clients::{AsBlobClient, AsContainerClient, BlobServiceClient, ContainerClient},
+    service::change_feed::*,
+};
+use azure_core::{
+    error::{ErrorKind, ResultExt},
+    prelude::*,
+};
+use bytes::Bytes;
+use chrono::{DateTime, Datelike, Utc};
+use futures::stream::{BoxStream, StreamExt};
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// Reads the change feed of the account, a page per chunk file.
+///
+/// The change feed is split in hourly segments, so the time window is applied with an hour
+/// granularity.
+#[derive(Debug, Clone)]
+pub struct GetChangeFeedBuilder {
+    client: BlobServiceClient,
+    start_time: Option<DateTime<Utc>>,
+    end_time: Option<DateTime<Utc>>,
+    cursor: Option<ChangeFeedCursor>,
+    context: Context,
+}
+
+impl GetChangeFeedBuilder {
+    pub(crate) fn new(client: BlobServiceClient) -> Self {
+        Self {
+            client,
+            start_time: None,
+            end_time: None,
+            cursor: None,
+            context: Context::new(),
+        }
+    }
+
+    setters! {
+        start_time: DateTime<Utc> => Some(start_time),
+        end_time: DateTime<Utc> => Some(end_time),
+        cursor: ChangeFeedCursor => Some(cursor),
+        context: Context => context,
+    }
+
+    pub fn into_stream(self) -> BoxStream<'static, azure_core::Result<GetChangeFeedResponse>> {
+        futures::stream::try_unfold(ChangeFeedReader::new(self), |mut reader| async move {
+            Ok(reader.next_page().await?.map(|page| (page, reader)))
+        })
+        .boxed()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetChangeFeedResponse {
+    pub events: Vec<BlobChangeFeedEvent>,
+    /// The position after the last event of the page.
+    pub cursor: ChangeFeedCursor,
+}
+
+/// Walks the segments, their shards and the chunks of each shard in order.
+struct ChangeFeedReader {
+    container_client: Arc<ContainerClient>,
+    context: Context,
+    start_time: Option<DateTime<Utc>>,
+    end_time: Option<DateTime<Utc>>,
+    /// Where to resume from, until it has been reached.
+    resume: Option<ChangeFeedCursor>,
+    /// The segments left to read, listed with the first page.
+    segments: Option<VecDeque<(DateTime<Utc>, String)>>,
+    segment_time: Option<DateTime<Utc>>,
+    shards: VecDeque<(usize, String)>,
+    shard_index: usize,
+    chunks: VecDeque<String>,
+}
+
+impl ChangeFeedReader {
+    fn new(builder: GetChangeFeedBuilder) -> Self {
+        Self {
+            container_client: builder
+                .client
+                .storage_client
+                .container_client(CHANGE_FEED_CONTAINER),
+            context: builder.context,
+            start_time: builder.start_time,
+            end_time: builder.end_time,
+            resume: builder.cursor,
+            segments: None,
+            segment_time: None,
+            shards: VecDeque::new(),
+            shard_index: 0,
+            chunks: VecDeque::new(),
+        }
+    }
+
+    async fn next_page(&mut self) -> azure_core::Result<Option<GetChangeFeedResponse>> {
+        if self.segments.is_none() {
+            self.segments = Some(self.list_segments().await?);
+        }
+
+        loop {
+            if let Some(chunk_path) = self.chunks.pop_front() {
+                let mut events = self.read_chunk(&chunk_path).await?;
+                let event_index = events.len();
+                if let Some(resume) = self.resume.take() {
+                    if resume.chunk_path == chunk_path {
+                        events.drain(..resume.event_index.min(events.len()));
+                    }
+                }
+                if events.is_empty() {
+                    continue;
+                }
+
+                return Ok(Some(GetChangeFeedResponse {
+                    events,
+                    cursor: ChangeFeedCursor {
+                        // the chunks are only listed once a segment has been read
+                        segment_time: self.segment_time.unwrap_or_default(),
+                        shard_index: self.shard_index,
+                        chunk_path,
+                        event_index,
+                    },
+                }));
+            }
+
+            if let Some((shard_index, shard_path)) = self.shards.pop_front() {
+                self.shard_index = shard_index;
+                let mut chunks = self.list_blob_names(&shard_path).await?;
+                match &self.resume {
+                    Some(resume) if resume.shard_index == shard_index => {
+                        chunks.retain(|chunk_path| chunk_path >= &resume.chunk_path)
+                    }
+                    _ => self.resume = None,
+                }
+                self.chunks = chunks.into();
+                continue;
+            }
+
+            let (segment_time, segment_path) =
+                match self.segments.as_mut().and_then(VecDeque::pop_front) {
+                    Some(segment) => segment,
+                    None => return Ok(None),
+                };
+            self.segment_time = Some(segment_time);
+
+            let manifest: SegmentManifest = self.read_json(&segment_path).await?;
+            let mut shards = manifest
+                .chunk_file_paths
+                .into_iter()
+                .map(|path| {
+                    path.strip_prefix(CHANGE_FEED_CONTAINER)
+                        .map(|path| path.trim_start_matches('/').to_owned())
+                        .unwrap_or(path)
+                })
+                .enumerate()
+                .collect::<VecDeque<_>>();
+            match &self.resume {
+                Some(resume) if resume.segment_time == segment_time => {
+                    shards.retain(|(shard_index, _)| *shard_index >= resume.shard_index)
+                }
+                _ => self.resume = None,
+            }
+            self.shards = shards;
+        }
+    }
+
+    /// Lists the segments of the time window that can be read, oldest first.
+    async fn list_segments(&self) -> azure_core::Result<VecDeque<(DateTime<Utc>, String)>> {
+        let manifest: SegmentsManifest = self.read_json(SEGMENTS_MANIFEST_PATH).await?;
+        let last_consumable = match manifest.last_consumable {
+            Some(last_consumable) => last_consumable,
+            None => return Ok(VecDeque::new()),
+        };
+        let start_time = match &self.resume {
+            Some(resume) => Some(resume.segment_time),
+            None => self.start_time.map(segment_floor),
+        };
+
+        let mut segments = self
+            .list_blob_names(SEGMENTS_PREFIX)
+            .await?
+            .into_iter()
+            .filter_map(|path| segment_time(&path).map(|time| (time, path)))
+            .filter(|(time, _)| {
+                // the feed starts with a segment from 1601 holding no events
+                time.year() > 1601
+                    && *time <= last_consumable
+                    && start_time.is_none_or(|start_time| *time >= start_time)
+                    && self.end_time.is_none_or(|end_time| *time < end_time)
+            })
+            .collect::<Vec<_>>();
+        segments.sort();
+
+        Ok(segments.into())
+    }
+
+    async fn list_blob_names(&self, prefix: &str) -> azure_core::Result<Vec<String>> {
+        let mut names = Vec::new();
+        let mut pages = self
+            .container_client
+            .list_blobs()
+            .prefix(prefix)
+            .context(self.context.clone())
+            .into_stream();
+        while let Some(page) = pages.next().await {
+            names.extend(page?.blobs.blobs.into_iter().map(|blob| blob.name));
+        }
+        Ok(names)
+    }
+
+    async fn read_json<T: serde::de::DeserializeOwned>(&self, path: &str) -> azure_core::Result<T> {
+        let body = self
+            .container_client
+            .blob_client(path)
+            .get_content()
+            .await?;
+        serde_json::from_slice(&body).with_context(ErrorKind::DataConversion, || {
+            format!("invalid change feed manifest {}", path)
+        })
+    }
+
+    async fn read_chunk(&self, path: &str) -> azure_core::Result<Vec<BlobChangeFeedEvent>> {
+        let body = self
+            .container_client
+            .blob_client(path)
+            .get_content()
+            .await?;
+        read_events(Bytes::from(body)).await
+    }
+}
+
+async fn read_events(chunk: Bytes) -> azure_core::Result<Vec<BlobChangeFeedEvent>> {
+    let mut reader = AvroReader::new(futures::stream::iter(vec![Ok(chunk)]));
+    let mut events = Vec::new();
+    while let Some(value) = reader.next().await? {
+        events.push(BlobChangeFeedEvent::try_from(value)?);
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avro::tests::{write_bytes, write_file, write_long};
+
+    #[tokio::test]
+    async fn test_read_events() {
+        let schema = r#"{
+            "type": "record",
+            "name": "BlobChangeEvent",
+            "namespace": "com.microsoft.azure.storage.blob",
+            "fields": [
+                {"name": "schemaVersion", "type": "int"},
+                {"name": "topic", "type": "string"},
+                {"name": "subject", "type": "string"},
+                {"name": "eventType", "type": {"type": "enum", "name": "BlobChangeEventType", "symbols": ["UnspecifiedEventType", "BlobCreated", "BlobDeleted"]}},
+                {"name": "eventTime", "type": "string"},
+                {"name": "id", "type": "string"},
+                {"name": "data", "type": {
+                    "type": "record",
+                    "name": "BlobChangeEventData",
+                    "fields": [
+                        {"name": "api", "type": "string"},
+                        {"name": "requestId", "type": "string"},
+                        {"name": "contentLength", "type": ["null", "long"]},
+                        {"name": "url", "type": "string"},
+                        {"name": "sequencer", "type": "string"}
+                    ]
+                }}
+            ]
+        }"#;
+
+        let mut record = Vec::new();
+        write_long(&mut record, 1);
+        write_bytes(&mut record, b"/subscriptions/s/resourceGroups/g/providers/Microsoft.Storage/storageAccounts/contoso");
+        write_bytes(
+            &mut record,
+            b"/blobServices/default/containers/container/blobs/blob.txt",
+        );
+        write_long(&mut record, 2);
+        write_bytes(&mut record, b"2022-06-21T18:12:01Z");
+        write_bytes(&mut record, b"62616073-8020-0000-00ff-233467060cc0");
+        write_bytes(&mut record, b"DeleteBlob");
+        write_bytes(&mut record, b"2f7c2a4c-601e-0005-4b1e-9a5f6b000000");
+        write_long(&mut record, 0);
+        write_bytes(
+            &mut record,
+            b"https://contoso.blob.core.windows.net/container/blob.txt",
+        );
+        write_bytes(&mut record, b"0000000000000001000000000000021e");
+
+        let chunk = write_file(schema, &[(1, record)]);
+        let events = read_events(chunk.into()).await.unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, BlobChangeFeedEventType::BlobDeleted);
+        assert_eq!(events[0].data.api, "DeleteBlob");
+        assert_eq!(events[0].data.content_length, None);
+        assert_eq!(events[0].data.etag, None);
+        assert_eq!(
+            events[0].data.sequencer.as_deref(),
+            Some("0000000000000001000000000000021e")
+        );
+    }
+}
@@ -0,0 +1,167 @@
+use crate::clients::BlobServiceClient;
+use azure_core::{
+    collect_pinned_stream,
+    headers::{date_from_headers, request_id_from_headers},
+    prelude::*,
+    Method, RequestId,
+};
+use azure_storage::{core::shared_access_signature::service_sas::UserDelegationKey, xml::read_xml};
+use chrono::{DateTime, SecondsFormat, Utc};
+
+/// Gets a key to sign user delegation SAS tokens with.
+///
+/// The request must be authorized with Azure AD credentials.
+#[derive(Debug, Clone)]
+pub struct GetUserDelegationKeyBuilder {
+    client: BlobServiceClient,
+    start: Option<DateTime<Utc>>,
+    expiry: DateTime<Utc>,
+    timeout: Option<Timeout>,
+    context: Context,
+}
+
+impl GetUserDelegationKeyBuilder {
+    pub(crate) fn new(client: BlobServiceClient, expiry: DateTime<Utc>) -> Self {
+        Self {
+            client,
+            start: None,
+            expiry,
+            timeout: None,
+            context: Context::new(),
+        }
+    }
+
+    setters! {
+        start: DateTime<Utc> => Some(start),
+        timeout: Timeout => Some(timeout),
+        context: Context => context,
+    }
+
+    pub fn into_future(mut self) -> Response {
+        Box::pin(async move {
+            let mut url = self
+                .client
+                .storage_client
+                .storage_account_client()
+                .blob_storage_url()
+                .clone();
+            url.query_pairs_mut()
+                .append_pair("restype", "service")
+                .append_pair("comp", "userdelegationkey");
+            self.timeout.append_to_url_query(&mut url);
+
+            let body = key_info_xml(self.start.unwrap_or_else(Utc::now), self.expiry);
+            let mut request =
+                self.client
+                    .storage_client
+                    .prepare_request(url, Method::Post, Some(body.into()))?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            GetUserDelegationKeyResponse::try_from(response).await
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetUserDelegationKeyResponse {
+    pub request_id: RequestId,
+    pub date: DateTime<Utc>,
+    pub user_delegation_key: UserDelegationKey,
+}
+
+impl GetUserDelegationKeyResponse {
+    async fn try_from(response: azure_core::Response) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let body = collect_pinned_stream(body).await?;
+        let key: UserDelegationKeyInternal = read_xml(&body)?;
+
+        Ok(Self {
+            request_id: request_id_from_headers(&headers)?,
+            date: date_from_headers(&headers)?,
+            user_delegation_key: key.into(),
+        })
+    }
+}
+
+pub type Response =
+    futures::future::BoxFuture<'static, azure_core::Result<GetUserDelegationKeyResponse>>;
+
+#[cfg(feature = "into_future")]
+impl std::future::IntoFuture for GetUserDelegationKeyBuilder {
+    type IntoFuture = Response;
+    type Output = <Response as std::future::Future>::Output;
+    fn into_future(self) -> Self::IntoFuture {
+        Self::into_future(self)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct UserDelegationKeyInternal {
+    signed_oid: String,
+    signed_tid: String,
+    signed_start: DateTime<Utc>,
+    signed_expiry: DateTime<Utc>,
+    signed_service: String,
+    signed_version: String,
+    value: String,
+}
+
+impl From<UserDelegationKeyInternal> for UserDelegationKey {
+    fn from(key: UserDelegationKeyInternal) -> Self {
+        Self {
+            signed_oid: key.signed_oid,
+            signed_tid: key.signed_tid,
+            signed_start: key.signed_start,
+            signed_expiry: key.signed_expiry,
+            signed_service: key.signed_service,
+            signed_version: key.signed_version,
+            value: key.value,
+        }
+    }
+}
+
+fn key_info_xml(start: DateTime<Utc>, expiry: DateTime<Utc>) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<KeyInfo><Start>{}</Start><Expiry>{}</Expiry></KeyInfo>",
+        start.to_rfc3339_opts(SecondsFormat::Secs, true),
+        expiry.to_rfc3339_opts(SecondsFormat::Secs, true),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn parse_user_delegation_key() {
+        let body = br#"<?xml version="1.0" encoding="utf-8"?>
+<UserDelegationKey>
+    <SignedOid>6d9be0a3-0000-0000-0000-000000000000</SignedOid>
+    <SignedTid>72f988bf-0000-0000-0000-000000000000</SignedTid>
+    <SignedStart>2022-06-01T00:00:00Z</SignedStart>
+    <SignedExpiry>2022-06-02T00:00:00Z</SignedExpiry>
+    <SignedService>b</SignedService>
+    <SignedVersion>2020-06-12</SignedVersion>
+    <Value>a2V5</Value>
+</UserDelegationKey>"#;
+        let key: UserDelegationKey = read_xml::<UserDelegationKeyInternal>(body).unwrap().into();
+        assert_eq!(key.signed_oid, "6d9be0a3-0000-0000-0000-000000000000");
+        assert_eq!(
+            key.signed_start,
+            Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            key.signed_expiry,
+            Utc.with_ymd_and_hms(2022, 6, 2, 0, 0, 0).unwrap()
+        );
+        assert_eq!(key.signed_service, "b");
+        assert_eq!(key.value, "a2V5");
+
+        assert_eq!(
+            key_info_xml(key.signed_start, key.signed_expiry),
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<KeyInfo><Start>2022-06-01T00:00:00Z</Start><Expiry>2022-06-02T00:00:00Z</Expiry></KeyInfo>"
+        );
+    }
+}
@@ -0,0 +1,12 @@
+mod get_blob_service_properties;
+mod get_blob_service_stats;
+mod get_change_feed;
+mod get_user_delegation_key;
+mod set_blob_service_properties;
+mod submit_batch;
+pub use get_blob_service_properties::*;
+pub use get_blob_service_stats::*;
+pub use get_change_feed::*;
+pub use get_user_delegation_key::*;
+pub use set_blob_service_properties::*;
+pub use submit_batch::*;
@@ -0,0 +1,79 @@
+use crate::{clients::BlobServiceClient, service::BlobServiceProperties};
+use azure_core::{error::Error, prelude::*, Method, Response as AzureResponse};
+use azure_storage::core::headers::CommonStorageResponseHeaders;
+use std::convert::TryInto;
+
+#[derive(Debug, Clone)]
+pub struct SetBlobServicePropertiesBuilder {
+    client: BlobServiceClient,
+    properties: BlobServiceProperties,
+    timeout: Option<Timeout>,
+    context: Context,
+}
+
+impl SetBlobServicePropertiesBuilder {
+    pub(crate) fn new(client: BlobServiceClient, properties: BlobServiceProperties) -> Self {
+        Self {
+            client,
+            properties,
+            timeout: None,
+            context: Context::new(),
+        }
+    }
+
+    setters! {
+        timeout: Timeout => Some(timeout),
+        context: Context => context,
+    }
+
+    pub fn into_future(mut self) -> Response {
+        Box::pin(async move {
+            let mut url = self
+                .client
+                .storage_client
+                .storage_account_client()
+                .blob_storage_url()
+                .clone();
+            url.query_pairs_mut()
+                .append_pair("restype", "service")
+                .append_pair("comp", "properties");
+            self.timeout.append_to_url_query(&mut url);
+
+            let body = self.properties.to_xml();
+            let mut request =
+                self.client
+                    .storage_client
+                    .prepare_request(url, Method::Put, Some(body.into()))?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            response.try_into()
+        })
+    }
+}
+
+pub type Response =
+    futures::future::BoxFuture<'static, azure_core::Result<SetBlobServicePropertiesResponse>>;
+
+#[cfg(feature = "into_future")]
+impl std::future::IntoFuture for SetBlobServicePropertiesBuilder {
+    type IntoFuture = Response;
+    type Output = <Response as std::future::Future>::Output;
+    fn into_future(self) -> Self::IntoFuture {
+        Self::into_future(self)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SetBlobServicePropertiesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+}
+
+impl std::convert::TryFrom<AzureResponse> for SetBlobServicePropertiesResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        Ok(Self {
+            common_storage_response_headers: response.headers().try_into()?,
+        })
+    }
+}
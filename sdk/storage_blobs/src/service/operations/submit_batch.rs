@@ -0,0 +1,112 @@
+use crate::{
+    clients::BlobServiceClient,
+    service::{parse_batch_response, BatchItemResponse, BlobBatch},
+};
+use azure_core::{
+    collect_pinned_stream,
+    headers::{date_from_headers, request_id_from_headers, CONTENT_TYPE},
+    prelude::*,
+    Method, RequestId,
+};
+use chrono::{DateTime, Utc};
+
+/// Sends the operations of a [`BlobBatch`] in a single request.
+///
+/// The batch fails as a whole only if the request itself is rejected. Otherwise the response has
+/// the outcome of every operation.
+#[derive(Debug, Clone)]
+pub struct SubmitBatchBuilder {
+    client: BlobServiceClient,
+    container_name: Option<String>,
+    batch: BlobBatch,
+    timeout: Option<Timeout>,
+    context: Context,
+}
+
+impl SubmitBatchBuilder {
+    pub(crate) fn new(
+        client: BlobServiceClient,
+        container_name: Option<String>,
+        batch: BlobBatch,
+    ) -> Self {
+        Self {
+            client,
+            container_name,
+            batch,
+            timeout: None,
+            context: Context::new(),
+        }
+    }
+
+    setters! {
+        timeout: Timeout => Some(timeout),
+        context: Context => context,
+    }
+
+    pub fn into_future(mut self) -> Response {
+        Box::pin(async move {
+            let mut url = match &self.container_name {
+                Some(container_name) => {
+                    let mut url = self
+                        .client
+                        .storage_client
+                        .blob_url_with_segments(Some(container_name.as_str()))?;
+                    url.query_pairs_mut().append_pair("restype", "container");
+                    url
+                }
+                None => self
+                    .client
+                    .storage_client
+                    .storage_account_client()
+                    .blob_storage_url()
+                    .clone(),
+            };
+            url.query_pairs_mut().append_pair("comp", "batch");
+            self.timeout.append_to_url_query(&mut url);
+
+            let body = self
+                .batch
+                .to_body(self.client.storage_client.storage_account_client())?;
+            let mut request =
+                self.client
+                    .storage_client
+                    .prepare_request(url, Method::Post, Some(body.into()))?;
+            request.insert_header(CONTENT_TYPE, self.batch.content_type());
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            SubmitBatchResponse::try_from(response).await
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SubmitBatchResponse {
+    pub request_id: RequestId,
+    pub date: DateTime<Utc>,
+    /// The responses to the operations, in the order they were added to the batch.
+    pub items: Vec<BatchItemResponse>,
+}
+
+impl SubmitBatchResponse {
+    async fn try_from(response: azure_core::Response) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let body = collect_pinned_stream(body).await?;
+
+        Ok(Self {
+            request_id: request_id_from_headers(&headers)?,
+            date: date_from_headers(&headers)?,
+            items: parse_batch_response(headers.get_str(&CONTENT_TYPE)?, &body)?,
+        })
+    }
+}
+
+pub type Response = futures::future::BoxFuture<'static, azure_core::Result<SubmitBatchResponse>>;
+
+#[cfg(feature = "into_future")]
+impl std::future::IntoFuture for SubmitBatchBuilder {
+    type IntoFuture = Response;
+    type Output = <Response as std::future::Future>::Output;
+    fn into_future(self) -> Self::IntoFuture {
+        Self::into_future(self)
+    }
+}
@@ -8,7 +8,7 @@ use crate::{
     CodeGen,
 };
 use crate::{content_type, Result};
-use autorust_openapi::{CollectionFormat, ParameterType, Response};
+use autorust_openapi::{CollectionFormat, MsLongRunningOperationOptionsFinalStateVia as FinalStateVia, ParameterType, Response};
 use heck::ToPascalCase;
 use heck::ToSnakeCase;
 use indexmap::IndexMap;
@@ -82,6 +82,7 @@ pub fn create_client(modules: &[String], endpoint: Option<&str>) -> Result<Token
             credential: std::sync::Arc<dyn azure_core::auth::TokenCredential>,
             endpoint: Option<String>,
             scopes: Option<Vec<String>>,
+            options: azure_core::ClientOptions,
         }
 
         #default_endpoint_code
@@ -92,6 +93,7 @@ pub fn create_client(modules: &[String], endpoint: Option<&str>) -> Result<Token
                     credential,
                     endpoint: None,
                     scopes: None,
+                    options: azure_core::ClientOptions::default(),
                 }
             }
 
@@ -105,10 +107,25 @@ pub fn create_client(modules: &[String], endpoint: Option<&str>) -> Result<Token
                 self
             }
 
+            pub fn retry(mut self, retry: impl Into<azure_core::RetryOptions>) -> Self {
+                self.options = self.options.retry(retry);
+                self
+            }
+
+            pub fn transport(mut self, transport: impl Into<azure_core::TransportOptions>) -> Self {
+                self.options = self.options.transport(transport);
+                self
+            }
+
+            pub fn options(mut self, options: impl Into<azure_core::ClientOptions>) -> Self {
+                self.options = options.into();
+                self
+            }
+
             pub fn build(self) -> Client {
                 let endpoint = self.endpoint.unwrap_or_else(|| DEFAULT_ENDPOINT.to_owned());
                 let scopes = self.scopes.unwrap_or_else(|| vec![format!("{}/", endpoint)]);
-                Client::new(endpoint, self.credential, scopes)
+                Client::new_with_options(endpoint, self.credential, scopes, self.options)
             }
         }
 
@@ -127,11 +144,14 @@ pub fn create_client(modules: &[String], endpoint: Option<&str>) -> Result<Token
                 self.pipeline.send(&mut context, request).await
             }
             pub fn new(endpoint: impl Into<String>, credential: std::sync::Arc<dyn azure_core::auth::TokenCredential>, scopes: Vec<String>) -> Self {
+                Self::new_with_options(endpoint, credential, scopes, azure_core::ClientOptions::default())
+            }
+            pub fn new_with_options(endpoint: impl Into<String>, credential: std::sync::Arc<dyn azure_core::auth::TokenCredential>, scopes: Vec<String>, options: azure_core::ClientOptions) -> Self {
                 let endpoint = endpoint.into();
                 let pipeline = azure_core::Pipeline::new(
                     option_env!("CARGO_PKG_NAME"),
                     option_env!("CARGO_PKG_VERSION"),
-                    azure_core::ClientOptions::default(),
+                    options,
                     Vec::new(),
                     Vec::new(),
                 );
@@ -287,6 +307,14 @@ impl WebOperationGen {
     fn produces(&self) -> Vec<&str> {
         self.0.produces.iter().map(String::as_str).collect()
     }
+
+    fn final_state_via(&self) -> FinalStateVia {
+        self.0
+            .long_running_operation_options
+            .as_ref()
+            .map(|options| options.final_state_via.clone())
+            .unwrap_or_default()
+    }
 }
 
 /// Creating a function name from the path and verb when an operationId is not specified.
@@ -339,6 +367,14 @@ fn verb_to_tokens(verb: &WebVerb) -> TokenStream {
     }
 }
 
+fn final_state_via_to_tokens(final_state_via: FinalStateVia) -> TokenStream {
+    match final_state_via {
+        FinalStateVia::AzureAsyncOperation => quote! { azure_core::lro::FinalStateVia::AzureAsyncOperation },
+        FinalStateVia::Location => quote! { azure_core::lro::FinalStateVia::Location },
+        FinalStateVia::OriginalUri => quote! { azure_core::lro::FinalStateVia::OriginalUri },
+    }
+}
+
 struct BuildRequestParamsCode {
     content_type: String,
     params: FunctionParams,
@@ -607,63 +643,8 @@ fn create_operation_code(cg: &CodeGen, operation: &WebOperationGen) -> Result<Op
         }
     }
 
-    let mut match_status = TokenStream::new();
-    for (status_code, rsp) in &success_responses {
-        match status_code {
-            autorust_openapi::StatusCode::Code(_) => {
-                let tp = create_response_type(rsp)?.map(TypeNameCode::into_token_stream);
-                let rsp_value = create_rsp_value(tp.as_ref());
-                let status_code_name = get_status_code_ident(status_code)?;
-                let response_type_name = get_response_type_ident(status_code)?;
-
-                if is_single_response {
-                    match tp {
-                        Some(_) => {
-                            match_status.extend(quote! {
-                                azure_core::StatusCode::#status_code_name => {
-                                    let rsp_body = azure_core::collect_pinned_stream(rsp_stream).await?;
-                                    #rsp_value
-                                    Ok(rsp_value)
-                                }
-                            });
-                        }
-                        None => {
-                            match_status.extend(quote! {
-                                azure_core::StatusCode::#status_code_name => {
-                                    Ok(())
-                                }
-                            });
-                        }
-                    }
-                } else {
-                    match tp {
-                        Some(_) => {
-                            match_status.extend(quote! {
-                                azure_core::StatusCode::#status_code_name => {
-                                    let rsp_body = azure_core::collect_pinned_stream(rsp_stream).await?;
-                                    #rsp_value
-                                    Ok(Response::#response_type_name(rsp_value))
-                                }
-                            });
-                        }
-                        None => {
-                            match_status.extend(quote! {
-                                azure_core::StatusCode::#status_code_name => {
-                                    Ok(Response::#response_type_name)
-                                }
-                            });
-                        }
-                    }
-                }
-            }
-            autorust_openapi::StatusCode::Default => {}
-        }
-    }
-    match_status.extend(quote! {
-        status_code => {
-            Err(azure_core::error::Error::from(azure_core::error::ErrorKind::HttpResponse { status: status_code as u16, error_code: None }))
-        }
-    });
+    let match_status = create_match_status(&success_responses, false)?;
+    let lro_match_status = create_match_status(&success_responses, true)?;
 
     let in_group = operation.0.in_group();
     let builder_instance_code = BuilderInstanceCode::new(operation, &parameters, in_group)?;
@@ -760,18 +741,48 @@ fn create_operation_code(cg: &CodeGen, operation: &WebOperationGen) -> Result<Op
             }
         }
     } else if operation.0.long_running_operation {
-        // TODO:  Long running options should also move to the Pageable stream
-        // model, however this is not possible at the moment because the
-        // continuation token is often not returned in the response body, but
-        // instead a header which we don't include as part of the response
-        // model.
-        //
-        // As is, Pageable requires implementing the Continuable trait on the
-        // response object.
+        // The status monitor is given by the `Azure-AsyncOperation` or `Location` headers
+        // rather than the response model, so polling is handled by `azure_core::lro::Poller`
+        // instead of the Pageable stream model.
         //
         // ref: https://github.com/Azure/azure-sdk-for-rust/issues/741
-        let mut fut = quote! {#[doc = "only the first response will be fetched as long running operations are not supported yet"]};
+        let final_state_via = final_state_via_to_tokens(operation.final_state_via());
+        let poll_request_code = RequestCode {
+            verb: WebVerb::Get,
+            auth: AuthCode {},
+        };
+        let mut fut = quote! {#[doc = "only the first response will be fetched, use `into_poller` to wait for the long running operation to complete"]};
         fut.extend(basic_future);
+        fut.extend(quote! {
+            #[doc = "polls the long running operation until it completes"]
+            pub fn into_poller(self) -> azure_core::lro::Poller<Response> {
+                let initial_request = {
+                    let this = self.clone();
+                    async move {
+                        let url = azure_core::Url::parse(&format!(#fpath, this.client.endpoint(), #url_str_args))?;
+                        #new_request_code
+                        #ts_request_builder
+                        req.set_body(req_body);
+                        let rsp = this.client.send(&mut req).await?;
+                        azure_core::Result::Ok((req, rsp))
+                    }
+                };
+                let get = move |url: azure_core::Url| {
+                    let this = self.clone();
+                    async move {
+                        #poll_request_code
+                        this.client.send(&mut req).await
+                    }
+                };
+                let convert = |rsp: azure_core::Response| async move {
+                    let (rsp_status, rsp_headers, rsp_stream) = rsp.deconstruct();
+                    match rsp_status {
+                        #lro_match_status
+                    }
+                };
+                azure_core::lro::Poller::new(#final_state_via, initial_request, get, convert)
+            }
+        });
         fut
     } else {
         basic_future
@@ -802,6 +813,83 @@ fn create_operation_code(cg: &CodeGen, operation: &WebOperationGen) -> Result<Op
     })
 }
 
+/// Creates the match arms turning the status code and body of a response into the `Response`.
+///
+/// The final response of a long running operation may have an empty body, when its status
+/// monitor succeeds without a result, which is reported rather than failing to deserialize it.
+fn create_match_status(success_responses: &IndexMap<autorust_openapi::StatusCode, Response>, is_lro: bool) -> Result<TokenStream> {
+    let is_single_response = success_responses.len() == 1;
+    let mut match_status = TokenStream::new();
+    for (status_code, rsp) in success_responses {
+        match status_code {
+            autorust_openapi::StatusCode::Code(_) => {
+                let tp = create_response_type(rsp)?.map(TypeNameCode::into_token_stream);
+                let mut rsp_value = create_rsp_value(tp.as_ref());
+                if is_lro && tp.as_ref().is_some_and(|tp| tp.to_string() != "bytes :: Bytes") {
+                    rsp_value = quote! {
+                        if rsp_body.is_empty() {
+                            return Err(azure_core::error::Error::message(
+                                azure_core::error::ErrorKind::DataConversion,
+                                "the long running operation completed without a result",
+                            ));
+                        }
+                        #rsp_value
+                    };
+                }
+                let status_code_name = get_status_code_ident(status_code)?;
+                let response_type_name = get_response_type_ident(status_code)?;
+
+                if is_single_response {
+                    match tp {
+                        Some(_) => {
+                            match_status.extend(quote! {
+                                azure_core::StatusCode::#status_code_name => {
+                                    let rsp_body = azure_core::collect_pinned_stream(rsp_stream).await?;
+                                    #rsp_value
+                                    Ok(rsp_value)
+                                }
+                            });
+                        }
+                        None => {
+                            match_status.extend(quote! {
+                                azure_core::StatusCode::#status_code_name => {
+                                    Ok(())
+                                }
+                            });
+                        }
+                    }
+                } else {
+                    match tp {
+                        Some(_) => {
+                            match_status.extend(quote! {
+                                azure_core::StatusCode::#status_code_name => {
+                                    let rsp_body = azure_core::collect_pinned_stream(rsp_stream).await?;
+                                    #rsp_value
+                                    Ok(Response::#response_type_name(rsp_value))
+                                }
+                            });
+                        }
+                        None => {
+                            match_status.extend(quote! {
+                                azure_core::StatusCode::#status_code_name => {
+                                    Ok(Response::#response_type_name)
+                                }
+                            });
+                        }
+                    }
+                }
+            }
+            autorust_openapi::StatusCode::Default => {}
+        }
+    }
+    match_status.extend(quote! {
+        status_code => {
+            Err(azure_core::error::Error::from(azure_core::error::ErrorKind::HttpResponse { status: status_code as u16, error_code: None }))
+        }
+    });
+    Ok(match_status)
+}
+
 fn create_rsp_value(tp: Option<&TokenStream>) -> TokenStream {
     if tp.map(|tp| tp.to_string()) == Some("bytes :: Bytes".to_owned()) {
         quote! {
@@ -1137,4 +1225,27 @@ mod tests {
         assert_eq!(None, operation.rust_module_name());
         assert_eq!("perform_connectivity_check", operation.rust_function_name());
     }
+
+    #[test]
+    fn test_lro_match_status_reports_missing_result() -> Result<()> {
+        let mut responses = IndexMap::new();
+        responses.insert(
+            autorust_openapi::StatusCode::Code(200),
+            serde_json::from_str::<Response>(r##"{"schema":{"$ref":"#/definitions/Pet"}}"##)?,
+        );
+        responses.insert(autorust_openapi::StatusCode::Code(202), serde_json::from_str::<Response>("{}")?);
+
+        let match_status = create_match_status(&responses, false)?.to_string();
+        assert!(match_status.contains("let rsp_value : models :: Pet = serde_json :: from_slice (& rsp_body) ?"));
+        assert!(!match_status.contains("rsp_body . is_empty ()"));
+
+        // the status monitor of a POST may succeed without the result model
+        let lro_match_status = create_match_status(&responses, true)?.to_string();
+        assert!(lro_match_status.contains(
+            "let rsp_body = azure_core :: collect_pinned_stream (rsp_stream) . await ? ; if rsp_body . is_empty () { return Err"
+        ));
+        assert!(lro_match_status.contains("Ok (Response :: Ok200 (rsp_value))"));
+        assert!(lro_match_status.contains("azure_core :: StatusCode :: Accepted => { Ok (Response :: Accepted202) }"));
+        Ok(())
+    }
 }